@@ -0,0 +1,468 @@
+// src/billetera.rs
+
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+use crate::dinero::Dinero;
+use crate::error::ErrorMoneda;
+use crate::fecha::FechaHora;
+use crate::formato::{EstiloFormato, formatear_con_estilo};
+use crate::locale::Locale;
+use crate::moneda::Moneda;
+
+/// Cuenta del cliente; es la única que no puede quedar en negativo sin sobregiro.
+pub const CUENTA_BILLETERA: &str = "billetera";
+/// Contrapartida de depósitos y retiros (dinero que entra o sale del sistema).
+pub const CUENTA_EXTERNA: &str = "externa";
+/// Contrapartida de los cambios de moneda.
+pub const CUENTA_CAMBIO: &str = "cambio";
+
+/// Un asiento: cuánto cambia el saldo de una cuenta (positivo aumenta, negativo disminuye).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Movimiento {
+    pub cuenta: String,
+    pub dinero: Dinero,
+}
+
+impl Movimiento {
+    pub fn new(cuenta: &str, dinero: Dinero) -> Movimiento {
+        Movimiento {
+            cuenta: cuenta.to_string(),
+            dinero,
+        }
+    }
+}
+
+/// Transacción de partida doble: sus movimientos suman cero en cada moneda.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Transaccion {
+    pub id: u64,
+    pub fecha_hora: FechaHora,
+    pub descripcion: String,
+    pub movimientos: Vec<Movimiento>,
+}
+
+impl Transaccion {
+    /// Cambio neto de una cuenta en una moneda dentro de esta transacción.
+    pub fn neto(&self, cuenta: &str, moneda: Moneda) -> i64 {
+        self.movimientos
+            .iter()
+            .filter(|movimiento| movimiento.cuenta == cuenta && movimiento.dinero.moneda == moneda)
+            .map(|movimiento| movimiento.dinero.centavos)
+            .sum()
+    }
+}
+
+/// Saldos de un cliente en varias monedas, con el historial de transacciones que los explica.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Billetera {
+    titular: String,
+    permitir_sobregiro: bool,
+    saldos: BTreeMap<(String, Moneda), i64>,
+    transacciones: Vec<Transaccion>,
+}
+
+impl Billetera {
+    /// Crea una billetera vacía que no permite sobregiros.
+    pub fn new(titular: &str) -> Billetera {
+        Billetera {
+            titular: titular.to_string(),
+            permitir_sobregiro: false,
+            saldos: BTreeMap::new(),
+            transacciones: Vec::new(),
+        }
+    }
+
+    /// Permite (o no) que la cuenta del cliente quede con saldo negativo.
+    pub fn con_sobregiro(mut self, permitir: bool) -> Billetera {
+        self.permitir_sobregiro = permitir;
+        self
+    }
+
+    pub fn titular(&self) -> &str {
+        &self.titular
+    }
+
+    pub fn transacciones(&self) -> &[Transaccion] {
+        &self.transacciones
+    }
+
+    /// Saldo del cliente en una moneda.
+    pub fn saldo(&self, moneda: Moneda) -> i64 {
+        self.saldo_de_cuenta(CUENTA_BILLETERA, moneda)
+    }
+
+    /// Saldo de cualquier cuenta del libro en una moneda.
+    pub fn saldo_de_cuenta(&self, cuenta: &str, moneda: Moneda) -> i64 {
+        self.saldos
+            .get(&(cuenta.to_string(), moneda))
+            .copied()
+            .unwrap_or(0)
+    }
+
+    /// Saldos del cliente en todas las monedas con movimientos, en el orden de `Moneda`.
+    pub fn saldos(&self) -> Vec<Dinero> {
+        self.saldos
+            .iter()
+            .filter(|((cuenta, _), _)| cuenta == CUENTA_BILLETERA)
+            .map(|((_, moneda), centavos)| Dinero::new(*centavos, *moneda))
+            .collect()
+    }
+
+    /// Ingresa dinero a la billetera desde la cuenta externa.
+    pub fn depositar(
+        &mut self,
+        dinero: Dinero,
+        descripcion: &str,
+        fecha_hora: FechaHora,
+    ) -> Result<u64, ErrorMoneda> {
+        validar_positivo(dinero)?;
+        let movimientos = vec![
+            Movimiento::new(CUENTA_BILLETERA, dinero),
+            Movimiento::new(CUENTA_EXTERNA, negar(dinero)?),
+        ];
+        self.registrar(descripcion, fecha_hora, movimientos)
+    }
+
+    /// Retira dinero de la billetera hacia la cuenta externa.
+    pub fn retirar(
+        &mut self,
+        dinero: Dinero,
+        descripcion: &str,
+        fecha_hora: FechaHora,
+    ) -> Result<u64, ErrorMoneda> {
+        validar_positivo(dinero)?;
+        let movimientos = vec![
+            Movimiento::new(CUENTA_BILLETERA, negar(dinero)?),
+            Movimiento::new(CUENTA_EXTERNA, dinero),
+        ];
+        self.registrar(descripcion, fecha_hora, movimientos)
+    }
+
+    /// Cambia `origen` por `destino` (por ejemplo el resultado de `convertir`).
+    ///
+    /// Cada moneda se equilibra contra la cuenta de cambio.
+    pub fn cambiar(
+        &mut self,
+        origen: Dinero,
+        destino: Dinero,
+        descripcion: &str,
+        fecha_hora: FechaHora,
+    ) -> Result<u64, ErrorMoneda> {
+        validar_positivo(origen)?;
+        validar_positivo(destino)?;
+        let movimientos = vec![
+            Movimiento::new(CUENTA_BILLETERA, negar(origen)?),
+            Movimiento::new(CUENTA_CAMBIO, origen),
+            Movimiento::new(CUENTA_CAMBIO, negar(destino)?),
+            Movimiento::new(CUENTA_BILLETERA, destino),
+        ];
+        self.registrar(descripcion, fecha_hora, movimientos)
+    }
+
+    /// Registra una transacción arbitraria.
+    ///
+    /// Sin sobregiro, el saldo de la billetera no puede quedar en negativo en ningún momento
+    /// del orden cronológico de `estado_de_cuenta`: una transacción con fecha anterior a
+    /// otras ya registradas se rechaza si deja sin fondos a alguna posterior.
+    ///
+    /// # Retorno
+    /// Retorna el id de la transacción, o `Err` si los movimientos no suman cero en cada
+    /// moneda, si la billetera quedaría en negativo sin sobregiro permitido o si algún saldo
+    /// se desborda. Si hay error no se modifica ningún saldo.
+    pub fn registrar(
+        &mut self,
+        descripcion: &str,
+        fecha_hora: FechaHora,
+        movimientos: Vec<Movimiento>,
+    ) -> Result<u64, ErrorMoneda> {
+        let mut por_moneda: BTreeMap<Moneda, i64> = BTreeMap::new();
+        let mut nuevos_saldos: BTreeMap<(String, Moneda), i64> = BTreeMap::new();
+        for movimiento in &movimientos {
+            let moneda = movimiento.dinero.moneda;
+            let suma = por_moneda.entry(moneda).or_insert(0);
+            *suma = suma
+                .checked_add(movimiento.dinero.centavos)
+                .ok_or(ErrorMoneda::Desbordamiento)?;
+
+            let clave = (movimiento.cuenta.clone(), moneda);
+            let saldo_actual = self.saldos.get(&clave).copied().unwrap_or(0);
+            let saldo = nuevos_saldos.entry(clave).or_insert(saldo_actual);
+            *saldo = saldo
+                .checked_add(movimiento.dinero.centavos)
+                .ok_or(ErrorMoneda::Desbordamiento)?;
+        }
+
+        if let Some((moneda, _)) = por_moneda.iter().find(|(_, suma)| **suma != 0) {
+            return Err(ErrorMoneda::TransaccionDesbalanceada(*moneda));
+        }
+        let id = self.transacciones.len() as u64 + 1;
+        let transaccion = Transaccion {
+            id,
+            fecha_hora,
+            descripcion: descripcion.to_string(),
+            movimientos,
+        };
+        if !self.permitir_sobregiro {
+            for ((cuenta, moneda), saldo) in &nuevos_saldos {
+                if cuenta == CUENTA_BILLETERA {
+                    self.validar_sin_sobregiro(&transaccion, *moneda, *saldo)?;
+                }
+            }
+        }
+
+        self.saldos.extend(nuevos_saldos);
+        self.transacciones.push(transaccion);
+        Ok(id)
+    }
+
+    /// Recorre las transacciones en orden cronológico, con `nueva` en su lugar, y falla en
+    /// la primera que deja la billetera en negativo en `moneda`.
+    fn validar_sin_sobregiro(
+        &self,
+        nueva: &Transaccion,
+        moneda: Moneda,
+        saldo_final: i64,
+    ) -> Result<(), ErrorMoneda> {
+        if saldo_final < 0 {
+            let actual = self.saldo(moneda);
+            return Err(ErrorMoneda::SaldoInsuficiente {
+                moneda,
+                saldo: actual,
+                requerido: actual - saldo_final,
+            });
+        }
+
+        let mut ordenadas: Vec<&Transaccion> = self.transacciones.iter().collect();
+        ordenadas.push(nueva);
+        ordenadas.sort_by_key(|transaccion| (transaccion.fecha_hora, transaccion.id));
+        let mut saldo: i64 = 0;
+        for transaccion in ordenadas {
+            let neto = transaccion.neto(CUENTA_BILLETERA, moneda);
+            let siguiente = saldo.checked_add(neto).ok_or(ErrorMoneda::Desbordamiento)?;
+            if siguiente < 0 {
+                return Err(ErrorMoneda::SaldoInsuficiente {
+                    moneda,
+                    saldo,
+                    requerido: -neto,
+                });
+            }
+            saldo = siguiente;
+        }
+        Ok(())
+    }
+
+    /// Estado de cuenta del cliente en orden cronológico, con el saldo después de cada movimiento.
+    ///
+    /// Los movimientos llevan signo (`EstiloFormato::ConSigno`) y los saldos negativos de un
+    /// sobregiro van entre paréntesis (`EstiloFormato::Contable`).
+    ///
+    /// ```text
+    /// Estado de cuenta: Ana
+    /// 2024-01-02 09:00:00 | #1 Deposito | +$100.00 | saldo $100.00
+    /// Saldos finales: $100.00
+    /// ```
+    pub fn estado_de_cuenta(&self, locale: Locale) -> Result<String, ErrorMoneda> {
+        let mut ordenadas: Vec<&Transaccion> = self.transacciones.iter().collect();
+        ordenadas.sort_by_key(|transaccion| (transaccion.fecha_hora, transaccion.id));
+
+        let mut texto = String::new();
+        let mut saldos: BTreeMap<Moneda, i64> = BTreeMap::new();
+        // Escribir en un `String` no falla.
+        let _ = writeln!(texto, "Estado de cuenta: {}", self.titular);
+        for transaccion in ordenadas {
+            for moneda in Moneda::TODAS {
+                let neto = transaccion.neto(CUENTA_BILLETERA, moneda);
+                if neto == 0 {
+                    continue;
+                }
+                let saldo = saldos.entry(moneda).or_insert(0);
+                *saldo = saldo.checked_add(neto).ok_or(ErrorMoneda::Desbordamiento)?;
+                let _ = writeln!(
+                    texto,
+                    "{} | #{} {} | {} | saldo {}",
+                    transaccion.fecha_hora,
+                    transaccion.id,
+                    transaccion.descripcion,
+                    formatear_con_estilo(neto, moneda, locale, EstiloFormato::ConSigno)?,
+                    formatear_con_estilo(*saldo, moneda, locale, EstiloFormato::Contable)?
+                );
+            }
+        }
+
+        let finales = self
+            .saldos()
+            .into_iter()
+            .map(|dinero| {
+                formatear_con_estilo(
+                    dinero.centavos,
+                    dinero.moneda,
+                    locale,
+                    EstiloFormato::Contable,
+                )
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let _ = write!(texto, "Saldos finales: {}", finales.join(", "));
+        Ok(texto)
+    }
+}
+
+fn validar_positivo(dinero: Dinero) -> Result<(), ErrorMoneda> {
+    match dinero.centavos {
+        0 => Err(ErrorMoneda::CantidadCero),
+        centavos if centavos < 0 => Err(ErrorMoneda::ValorNegativo(centavos)),
+        _ => Ok(()),
+    }
+}
+
+fn negar(dinero: Dinero) -> Result<Dinero, ErrorMoneda> {
+    let centavos = dinero
+        .centavos
+        .checked_neg()
+        .ok_or(ErrorMoneda::Desbordamiento)?;
+    Ok(Dinero::new(centavos, dinero.moneda))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn momento(texto: &str) -> FechaHora {
+        let (fecha, hora) = texto.split_once(' ').unwrap();
+        let hora: Vec<u32> = hora
+            .split(':')
+            .map(|parte| parte.parse().unwrap())
+            .collect();
+        FechaHora::new(fecha.parse().unwrap(), hora[0], hora[1], hora[2]).unwrap()
+    }
+
+    fn usd(centavos: i64) -> Dinero {
+        Dinero::new(centavos, Moneda::USD)
+    }
+
+    fn eur(centavos: i64) -> Dinero {
+        Dinero::new(centavos, Moneda::EUR)
+    }
+
+    #[test]
+    fn test_depositar_retirar_y_cambiar() {
+        let mut billetera = Billetera::new("Ana");
+        billetera
+            .depositar(usd(10000), "Deposito", momento("2024-01-02 09:00:00"))
+            .unwrap();
+        billetera
+            .cambiar(
+                usd(5000),
+                eur(4500),
+                "Cambio",
+                momento("2024-01-03 10:00:00"),
+            )
+            .unwrap();
+        billetera
+            .retirar(eur(500), "Cajero", momento("2024-01-04 11:00:00"))
+            .unwrap();
+
+        assert_eq!(billetera.saldos(), vec![usd(5000), eur(4000)]);
+        assert_eq!(
+            billetera.saldo_de_cuenta(CUENTA_EXTERNA, Moneda::USD),
+            -10000
+        );
+        assert_eq!(billetera.saldo_de_cuenta(CUENTA_CAMBIO, Moneda::EUR), -4500);
+        assert_eq!(billetera.transacciones().len(), 3);
+    }
+
+    #[test]
+    fn test_rechaza_sobregiro() {
+        let mut billetera = Billetera::new("Ana");
+        billetera
+            .depositar(usd(1000), "Deposito", momento("2024-01-02 09:00:00"))
+            .unwrap();
+
+        assert_eq!(
+            billetera.retirar(usd(1500), "Retiro", momento("2024-01-02 10:00:00")),
+            Err(ErrorMoneda::SaldoInsuficiente {
+                moneda: Moneda::USD,
+                saldo: 1000,
+                requerido: 1500,
+            })
+        );
+        assert_eq!(billetera.saldo(Moneda::USD), 1000);
+        assert_eq!(billetera.transacciones().len(), 1);
+
+        // Al final alcanzaría, pero el 1 de enero todavía no había fondos.
+        assert_eq!(
+            billetera.retirar(usd(500), "Retiro", momento("2024-01-01 10:00:00")),
+            Err(ErrorMoneda::SaldoInsuficiente {
+                moneda: Moneda::USD,
+                saldo: 0,
+                requerido: 500,
+            })
+        );
+        assert_eq!(
+            billetera.depositar(usd(0), "Nada", momento("2024-01-02 11:00:00")),
+            Err(ErrorMoneda::CantidadCero)
+        );
+        assert_eq!(billetera.transacciones().len(), 1);
+    }
+
+    #[test]
+    fn test_sobregiro_permitido() {
+        let mut billetera = Billetera::new("Ana").con_sobregiro(true);
+        billetera
+            .retirar(usd(500), "Adelanto", momento("2024-01-02 09:00:00"))
+            .unwrap();
+        assert_eq!(billetera.saldo(Moneda::USD), -500);
+        assert_eq!(
+            billetera.estado_de_cuenta(Locale::EnUs),
+            Ok("Estado de cuenta: Ana\n\
+                2024-01-02 09:00:00 | #1 Adelanto | -$5.00 | saldo ($5.00)\n\
+                Saldos finales: ($5.00)"
+                .to_string())
+        );
+    }
+
+    #[test]
+    fn test_transaccion_desbalanceada() {
+        let mut billetera = Billetera::new("Ana");
+        let movimientos = vec![
+            Movimiento::new(CUENTA_BILLETERA, usd(100)),
+            Movimiento::new(CUENTA_EXTERNA, usd(-90)),
+        ];
+        assert_eq!(
+            billetera.registrar("Error", momento("2024-01-02 09:00:00"), movimientos),
+            Err(ErrorMoneda::TransaccionDesbalanceada(Moneda::USD))
+        );
+        assert!(billetera.saldos().is_empty());
+    }
+
+    #[test]
+    fn test_estado_de_cuenta_cronologico() {
+        let mut billetera = Billetera::new("Ana");
+        billetera
+            .depositar(usd(10000), "Deposito", momento("2024-01-02 09:00:00"))
+            .unwrap();
+        billetera
+            .cambiar(
+                usd(5000),
+                eur(4500),
+                "Cambio",
+                momento("2024-01-03 10:00:00"),
+            )
+            .unwrap();
+        // Registrada después, pero ocurrió antes del cambio.
+        billetera
+            .retirar(usd(1000), "Cajero", momento("2024-01-02 18:30:00"))
+            .unwrap();
+
+        assert_eq!(
+            billetera.estado_de_cuenta(Locale::EnUs),
+            Ok("Estado de cuenta: Ana\n\
+                2024-01-02 09:00:00 | #1 Deposito | +$100.00 | saldo $100.00\n\
+                2024-01-02 18:30:00 | #3 Cajero | -$10.00 | saldo $90.00\n\
+                2024-01-03 10:00:00 | #2 Cambio | -$50.00 | saldo $40.00\n\
+                2024-01-03 10:00:00 | #2 Cambio | +€45.00 | saldo €45.00\n\
+                Saldos finales: $40.00, €45.00"
+                .to_string())
+        );
+    }
+}
@@ -0,0 +1,482 @@
+// src/calculo.rs
+
+use std::fmt::Write;
+
+use crate::error::ErrorMoneda;
+use crate::formato;
+use crate::locale::Locale;
+use crate::moneda::Moneda;
+use crate::porcentaje::Porcentaje;
+use crate::redondeo::{ModoRedondeo, dividir_redondeando};
+
+/// Indica si los precios de las líneas ya incluyen los impuestos.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ModoPrecio {
+    /// Los precios son netos y los impuestos se suman al final (típico en EE. UU.).
+    #[default]
+    ImpuestosExcluidos,
+    /// Los precios ya incluyen los impuestos y se desglosan (típico con IVA).
+    ImpuestosIncluidos,
+}
+
+/// Un impuesto con nombre, por ejemplo "IVA" al 16%.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Impuesto {
+    pub nombre: String,
+    pub tasa: Porcentaje,
+}
+
+impl Impuesto {
+    pub fn new(nombre: &str, tasa: Porcentaje) -> Impuesto {
+        Impuesto {
+            nombre: nombre.to_string(),
+            tasa,
+        }
+    }
+}
+
+/// Un artículo de la compra.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Linea {
+    pub descripcion: String,
+    /// Precio de una unidad en centavos (neto o con impuestos, según `ModoPrecio`).
+    pub precio_unitario: i64,
+    pub cantidad: u32,
+    pub impuesto: Impuesto,
+}
+
+impl Linea {
+    pub fn new(
+        descripcion: &str,
+        precio_unitario: i64,
+        cantidad: u32,
+        impuesto: Impuesto,
+    ) -> Linea {
+        Linea {
+            descripcion: descripcion.to_string(),
+            precio_unitario,
+            cantidad,
+            impuesto,
+        }
+    }
+}
+
+/// Cómo se calcula un descuento.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TipoDescuento {
+    /// Un porcentaje de lo que queda por pagar después de los descuentos anteriores.
+    Porcentaje(Porcentaje),
+    /// Una cantidad fija en centavos; nunca deja el total por debajo de cero.
+    Fijo(i64),
+}
+
+/// Un descuento a nivel de pedido.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Descuento {
+    pub descripcion: String,
+    pub tipo: TipoDescuento,
+}
+
+impl Descuento {
+    pub fn porcentaje(descripcion: &str, porcentaje: Porcentaje) -> Descuento {
+        Descuento {
+            descripcion: descripcion.to_string(),
+            tipo: TipoDescuento::Porcentaje(porcentaje),
+        }
+    }
+
+    pub fn fijo(descripcion: &str, centavos: i64) -> Descuento {
+        Descuento {
+            descripcion: descripcion.to_string(),
+            tipo: TipoDescuento::Fijo(centavos),
+        }
+    }
+}
+
+/// Descuento ya calculado, en centavos.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DescuentoAplicado {
+    pub descripcion: String,
+    pub monto: i64,
+}
+
+/// Total de un impuesto para una tasa: la base gravable y el impuesto que le corresponde.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImpuestoAplicado {
+    pub impuesto: Impuesto,
+    pub base: i64,
+    pub monto: i64,
+}
+
+/// Desglose de la compra; todas las cantidades están en centavos de `moneda`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Desglose {
+    pub moneda: Moneda,
+    pub modo: ModoPrecio,
+    pub subtotal: i64,
+    pub descuentos: Vec<DescuentoAplicado>,
+    pub impuestos: Vec<ImpuestoAplicado>,
+    pub total: i64,
+}
+
+impl Desglose {
+    /// Suma de todos los descuentos aplicados.
+    pub fn total_descuentos(&self) -> i64 {
+        self.descuentos
+            .iter()
+            .map(|descuento| descuento.monto)
+            .sum()
+    }
+
+    /// Suma de todos los impuestos (incluidos o no en el subtotal).
+    pub fn total_impuestos(&self) -> i64 {
+        self.impuestos.iter().map(|impuesto| impuesto.monto).sum()
+    }
+
+    /// Formatea el desglose, una cantidad por línea, con las convenciones del locale.
+    ///
+    /// ```text
+    /// Subtotal: $100.00
+    /// Descuento 10%: -$10.00
+    /// IVA 16% (base $90.00): $14.40
+    /// Total: $104.40
+    /// ```
+    pub fn formatear(&self, locale: Locale) -> Result<String, ErrorMoneda> {
+        let monto = |centavos: i64| formato::formatear(centavos, self.moneda, locale);
+        let mut texto = String::new();
+
+        // Escribir en un `String` no falla.
+        let _ = writeln!(texto, "Subtotal: {}", monto(self.subtotal)?);
+        for descuento in &self.descuentos {
+            let _ = writeln!(
+                texto,
+                "{}: -{}",
+                descuento.descripcion,
+                monto(descuento.monto)?
+            );
+        }
+        for impuesto in &self.impuestos {
+            let incluido = match self.modo {
+                ModoPrecio::ImpuestosIncluidos => " incluido",
+                ModoPrecio::ImpuestosExcluidos => "",
+            };
+            let _ = writeln!(
+                texto,
+                "{} {}{} (base {}): {}",
+                impuesto.impuesto.nombre,
+                impuesto.impuesto.tasa,
+                incluido,
+                monto(impuesto.base)?,
+                monto(impuesto.monto)?
+            );
+        }
+        let _ = write!(texto, "Total: {}", monto(self.total)?);
+        Ok(texto)
+    }
+}
+
+/// Calcula subtotal, descuentos, impuestos por tasa y total de una compra.
+///
+/// Los descuentos se aplican en orden sobre lo que queda por pagar y se reparten entre las
+/// tasas de impuesto en proporción a su base, de modo que cada impuesto se calcula sobre el
+/// importe ya descontado.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Calculadora {
+    moneda: Moneda,
+    modo: ModoPrecio,
+    redondeo: ModoRedondeo,
+    lineas: Vec<Linea>,
+    descuentos: Vec<Descuento>,
+}
+
+impl Calculadora {
+    pub fn new(moneda: Moneda, modo: ModoPrecio) -> Calculadora {
+        Calculadora {
+            moneda,
+            modo,
+            redondeo: ModoRedondeo::default(),
+            lineas: Vec::new(),
+            descuentos: Vec::new(),
+        }
+    }
+
+    /// Modo de redondeo para descuentos porcentuales e impuestos (por defecto `MitadArriba`).
+    pub fn con_redondeo(mut self, redondeo: ModoRedondeo) -> Calculadora {
+        self.redondeo = redondeo;
+        self
+    }
+
+    pub fn con_linea(mut self, linea: Linea) -> Calculadora {
+        self.lineas.push(linea);
+        self
+    }
+
+    pub fn con_descuento(mut self, descuento: Descuento) -> Calculadora {
+        self.descuentos.push(descuento);
+        self
+    }
+
+    /// Calcula el desglose.
+    ///
+    /// # Retorno
+    /// Retorna `Err` si algún precio o descuento es negativo, si un descuento supera el 100%
+    /// o si alguna suma se desborda.
+    pub fn calcular(&self) -> Result<Desglose, ErrorMoneda> {
+        // Importe de cada impuesto antes de descuentos, en orden de aparición.
+        let mut por_impuesto: Vec<(Impuesto, i64)> = Vec::new();
+        for linea in &self.lineas {
+            if linea.precio_unitario < 0 {
+                return Err(ErrorMoneda::ValorNegativo(linea.precio_unitario));
+            }
+            let importe = linea
+                .precio_unitario
+                .checked_mul(i64::from(linea.cantidad))
+                .ok_or(ErrorMoneda::Desbordamiento)?;
+            match por_impuesto
+                .iter_mut()
+                .find(|(impuesto, _)| *impuesto == linea.impuesto)
+            {
+                Some((_, acumulado)) => {
+                    *acumulado = acumulado
+                        .checked_add(importe)
+                        .ok_or(ErrorMoneda::Desbordamiento)?;
+                }
+                None => por_impuesto.push((linea.impuesto.clone(), importe)),
+            }
+        }
+
+        let subtotal = sumar(por_impuesto.iter().map(|(_, importe)| *importe))?;
+        let mut restante = subtotal;
+        let mut descuentos = Vec::new();
+        for descuento in &self.descuentos {
+            let monto = self.monto_descuento(descuento, restante)?;
+            restante -= monto;
+            descuentos.push(DescuentoAplicado {
+                descripcion: descuento.descripcion.clone(),
+                monto,
+            });
+        }
+
+        let importes: Vec<i64> = por_impuesto.iter().map(|(_, importe)| *importe).collect();
+        let con_descuento = repartir_proporcional(restante, &importes);
+        let mut impuestos = Vec::new();
+        for ((impuesto, _), importe) in por_impuesto.into_iter().zip(con_descuento) {
+            let (numerador, denominador) = impuesto.tasa.como_fraccion();
+            let (base, monto) = match self.modo {
+                ModoPrecio::ImpuestosExcluidos => {
+                    let monto = self.aplicar_fraccion(importe, numerador, denominador)?;
+                    (importe, monto)
+                }
+                ModoPrecio::ImpuestosIncluidos => {
+                    // El importe ya incluye el impuesto: impuesto = importe * r / (1 + r).
+                    let monto =
+                        self.aplicar_fraccion(importe, numerador, denominador + numerador)?;
+                    (importe - monto, monto)
+                }
+            };
+            impuestos.push(ImpuestoAplicado {
+                impuesto,
+                base,
+                monto,
+            });
+        }
+
+        let total = match self.modo {
+            ModoPrecio::ImpuestosExcluidos => {
+                let total_impuestos = sumar(impuestos.iter().map(|impuesto| impuesto.monto))?;
+                restante
+                    .checked_add(total_impuestos)
+                    .ok_or(ErrorMoneda::Desbordamiento)?
+            }
+            ModoPrecio::ImpuestosIncluidos => restante,
+        };
+
+        Ok(Desglose {
+            moneda: self.moneda,
+            modo: self.modo,
+            subtotal,
+            descuentos,
+            impuestos,
+            total,
+        })
+    }
+
+    fn monto_descuento(&self, descuento: &Descuento, restante: i64) -> Result<i64, ErrorMoneda> {
+        match descuento.tipo {
+            TipoDescuento::Porcentaje(porcentaje) => {
+                let (numerador, denominador) = porcentaje.como_fraccion();
+                if numerador > denominador {
+                    return Err(ErrorMoneda::PorcentajeInvalido(porcentaje.to_string()));
+                }
+                self.aplicar_fraccion(restante, numerador, denominador)
+            }
+            TipoDescuento::Fijo(centavos) if centavos < 0 => {
+                Err(ErrorMoneda::ValorNegativo(centavos))
+            }
+            TipoDescuento::Fijo(centavos) => Ok(centavos.min(restante)),
+        }
+    }
+
+    fn aplicar_fraccion(
+        &self,
+        centavos: i64,
+        numerador: i128,
+        denominador: i128,
+    ) -> Result<i64, ErrorMoneda> {
+        let producto = i128::from(centavos)
+            .checked_mul(numerador)
+            .ok_or(ErrorMoneda::Desbordamiento)?;
+        i64::try_from(dividir_redondeando(producto, denominador, self.redondeo))
+            .map_err(|_| ErrorMoneda::Desbordamiento)
+    }
+}
+
+fn sumar(mut montos: impl Iterator<Item = i64>) -> Result<i64, ErrorMoneda> {
+    montos.try_fold(0_i64, |total, monto| {
+        total.checked_add(monto).ok_or(ErrorMoneda::Desbordamiento)
+    })
+}
+
+/// Reparte `total` en proporción a `pesos` sin perder centavos (método del mayor residuo).
+fn repartir_proporcional(total: i64, pesos: &[i64]) -> Vec<i64> {
+    let suma: i128 = pesos.iter().map(|peso| i128::from(*peso)).sum();
+    if suma == 0 {
+        return vec![0; pesos.len()];
+    }
+
+    let mut partes = Vec::with_capacity(pesos.len());
+    let mut residuos = Vec::with_capacity(pesos.len());
+    for (i, peso) in pesos.iter().enumerate() {
+        let producto = i128::from(total) * i128::from(*peso);
+        // Cabe en i64: es a lo sumo `total`.
+        partes.push((producto / suma) as i64);
+        residuos.push((producto % suma, i));
+    }
+
+    let sobrante = total - partes.iter().sum::<i64>();
+    residuos.sort_by(|a, b| b.0.cmp(&a.0).then(a.1.cmp(&b.1)));
+    for (_, i) in residuos.into_iter().take(sobrante as usize) {
+        partes[i] += 1;
+    }
+    partes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn iva(tasa: &str) -> Impuesto {
+        Impuesto::new("IVA", tasa.parse().unwrap())
+    }
+
+    #[test]
+    fn test_impuestos_excluidos_con_descuentos() {
+        let desglose = Calculadora::new(Moneda::USD, ModoPrecio::ImpuestosExcluidos)
+            .con_linea(Linea::new("Camiseta", 2500, 2, iva("16")))
+            .con_linea(Linea::new("Libro", 5000, 1, iva("0")))
+            .con_descuento(Descuento::porcentaje(
+                "Descuento 10%",
+                "10".parse().unwrap(),
+            ))
+            .con_descuento(Descuento::fijo("Cupon", 1000))
+            .calcular()
+            .unwrap();
+
+        assert_eq!(desglose.subtotal, 10000);
+        assert_eq!(desglose.total_descuentos(), 2000);
+        // Quedan 8000, repartidos 4000 / 4000 entre las dos tasas.
+        assert_eq!(
+            desglose.impuestos,
+            vec![
+                ImpuestoAplicado {
+                    impuesto: iva("16"),
+                    base: 4000,
+                    monto: 640
+                },
+                ImpuestoAplicado {
+                    impuesto: iva("0"),
+                    base: 4000,
+                    monto: 0
+                },
+            ]
+        );
+        assert_eq!(desglose.total, 8640);
+    }
+
+    #[test]
+    fn test_impuestos_incluidos() {
+        let desglose = Calculadora::new(Moneda::EUR, ModoPrecio::ImpuestosIncluidos)
+            .con_linea(Linea::new("Cafe", 121, 10, iva("21")))
+            .calcular()
+            .unwrap();
+
+        assert_eq!(desglose.subtotal, 1210);
+        assert_eq!(
+            desglose.impuestos,
+            vec![ImpuestoAplicado {
+                impuesto: iva("21"),
+                base: 1000,
+                monto: 210
+            }]
+        );
+        assert_eq!(desglose.total, 1210);
+    }
+
+    #[test]
+    fn test_descuento_fijo_no_deja_total_negativo() {
+        let desglose = Calculadora::new(Moneda::USD, ModoPrecio::ImpuestosExcluidos)
+            .con_linea(Linea::new("Taza", 500, 1, iva("16")))
+            .con_descuento(Descuento::fijo("Regalo", 2000))
+            .calcular()
+            .unwrap();
+
+        assert_eq!(desglose.total_descuentos(), 500);
+        assert_eq!(desglose.total, 0);
+    }
+
+    #[test]
+    fn test_errores() {
+        let negativo = Calculadora::new(Moneda::USD, ModoPrecio::ImpuestosExcluidos)
+            .con_linea(Linea::new("Taza", -500, 1, iva("16")));
+        assert_eq!(negativo.calcular(), Err(ErrorMoneda::ValorNegativo(-500)));
+
+        let excesivo = Calculadora::new(Moneda::USD, ModoPrecio::ImpuestosExcluidos)
+            .con_linea(Linea::new("Taza", 500, 1, iva("16")))
+            .con_descuento(Descuento::porcentaje(
+                "Mas que gratis",
+                "150".parse().unwrap(),
+            ));
+        assert!(matches!(
+            excesivo.calcular(),
+            Err(ErrorMoneda::PorcentajeInvalido(_))
+        ));
+    }
+
+    #[test]
+    fn test_formatear_desglose() {
+        let desglose = Calculadora::new(Moneda::USD, ModoPrecio::ImpuestosExcluidos)
+            .con_linea(Linea::new("Camiseta", 10000, 1, iva("16")))
+            .con_descuento(Descuento::porcentaje(
+                "Descuento 10%",
+                "10".parse().unwrap(),
+            ))
+            .calcular()
+            .unwrap();
+
+        assert_eq!(
+            desglose.formatear(Locale::EnUs),
+            Ok("Subtotal: $100.00\n\
+                Descuento 10%: -$10.00\n\
+                IVA 16% (base $90.00): $14.40\n\
+                Total: $104.40"
+                .to_string())
+        );
+    }
+
+    #[test]
+    fn test_repartir_proporcional() {
+        assert_eq!(repartir_proporcional(100, &[1, 1, 1]), vec![34, 33, 33]);
+        assert_eq!(repartir_proporcional(10, &[0, 5]), vec![0, 10]);
+        assert_eq!(repartir_proporcional(7, &[0, 0]), vec![0, 0]);
+    }
+}
@@ -0,0 +1,607 @@
+// src/cli.rs
+
+use std::fs;
+use std::io::{BufRead, Write};
+use std::path::PathBuf;
+
+use serde_json::{Value, json};
+
+use convercion_monedas::{
+    Dinero, ErrorMoneda, EstiloFormato, Fecha, HistorialTasas, Locale, ModoRedondeo, Moneda,
+    convertir_con_redondeo, formatear, formatear_con_estilo, intentar_convertir_cents_a_moneda,
+    parsear_moneda, parsear_moneda_con_locale,
+};
+
+/// Todo salió bien.
+pub const SALIDA_OK: i32 = 0;
+/// Alguna entrada (cantidad, moneda, archivo de tasas...) no es válida.
+pub const SALIDA_ENTRADA_INVALIDA: i32 = 1;
+/// Los argumentos de la línea de comandos no son válidos.
+pub const SALIDA_USO: i32 = 2;
+
+pub const USO: &str = "Uso:
+  convercion_monedas format <centavos> <moneda> [--locale es-MX]
+                            [--style standard|accounting|compact|sign]
+  convercion_monedas convert <centavos> <origen> <destino> --rates <tasas.csv|tasas.json>
+                             [--date AAAA-MM-DD] [--rounding half-up|half-even|floor|ceiling|truncate]
+  convercion_monedas parse <texto> [--locale es-ES]
+
+Sin argumentos posicionales (o con \"-\") lee un registro por línea de la entrada estándar;
+con --input <archivo.csv> los lee de un CSV. Cada línea tiene los mismos campos que los
+argumentos, separados por comas (usa comillas para textos con coma: \"€0,50\").
+
+Opciones comunes:
+  --output text|json   formato de salida (por defecto text)
+  --input <archivo>    lee los registros de un archivo CSV";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Comando {
+    Format,
+    Convert,
+    Parse,
+}
+
+impl Comando {
+    /// Cantidad de campos de cada registro.
+    fn campos(&self) -> usize {
+        match self {
+            Comando::Format => 2,
+            Comando::Convert => 3,
+            Comando::Parse => 1,
+        }
+    }
+
+    /// Encabezado que se omite si aparece en la primera línea de un CSV.
+    fn encabezado(&self) -> &'static str {
+        match self {
+            Comando::Format => "centavos,moneda",
+            Comando::Convert => "centavos,origen,destino",
+            Comando::Parse => "texto",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Salida {
+    Texto,
+    Json,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Opciones {
+    pub comando: Comando,
+    pub argumentos: Vec<String>,
+    pub locale: Option<Locale>,
+    pub tasas: Option<PathBuf>,
+    pub fecha: Option<Fecha>,
+    pub redondeo: ModoRedondeo,
+    pub estilo: EstiloFormato,
+    pub salida: Salida,
+    pub entrada: Option<PathBuf>,
+}
+
+impl Opciones {
+    /// Sin argumentos posicionales (o con "-") los registros se leen de la entrada o de --input.
+    pub fn en_lote(&self) -> bool {
+        self.argumentos.is_empty() || self.argumentos == ["-"]
+    }
+}
+
+/// Interpreta los argumentos (sin el nombre del programa).
+///
+/// # Retorno
+/// Retorna las opciones o un mensaje de error de uso.
+pub fn parsear_argumentos(args: &[String]) -> Result<Opciones, String> {
+    let mut args = args.iter();
+    let comando = match args.next().map(String::as_str) {
+        Some("format") => Comando::Format,
+        Some("convert") => Comando::Convert,
+        Some("parse") => Comando::Parse,
+        Some(otro) => return Err(format!("comando desconocido: {}", otro)),
+        None => return Err("falta el comando".to_string()),
+    };
+
+    let mut opciones = Opciones {
+        comando,
+        argumentos: Vec::new(),
+        locale: None,
+        tasas: None,
+        fecha: None,
+        redondeo: ModoRedondeo::default(),
+        estilo: EstiloFormato::default(),
+        salida: Salida::Texto,
+        entrada: None,
+    };
+
+    while let Some(arg) = args.next() {
+        let mut valor = || {
+            args.next()
+                .ok_or_else(|| format!("falta el valor de {}", arg))
+        };
+        match arg.as_str() {
+            "--locale" => {
+                opciones.locale = Some(valor()?.parse().map_err(|e: ErrorMoneda| e.to_string())?)
+            }
+            "--rates" => opciones.tasas = Some(PathBuf::from(valor()?)),
+            "--date" => {
+                opciones.fecha = Some(valor()?.parse().map_err(|e: ErrorMoneda| e.to_string())?)
+            }
+            "--rounding" => opciones.redondeo = parsear_redondeo(valor()?)?,
+            "--style" => opciones.estilo = parsear_estilo(valor()?)?,
+            "--output" => {
+                opciones.salida = match valor()?.as_str() {
+                    "text" => Salida::Texto,
+                    "json" => Salida::Json,
+                    otro => return Err(format!("formato de salida desconocido: {}", otro)),
+                }
+            }
+            "--input" => opciones.entrada = Some(PathBuf::from(valor()?)),
+            opcion if opcion.starts_with("--") => {
+                return Err(format!("opcion desconocida: {}", opcion));
+            }
+            _ => opciones.argumentos.push(arg.clone()),
+        }
+    }
+
+    let en_lote = opciones.en_lote();
+    if !en_lote && opciones.argumentos.len() != comando.campos() {
+        return Err(format!(
+            "{} espera {} argumentos, se recibieron {}",
+            comando.encabezado().replace(',', " "),
+            comando.campos(),
+            opciones.argumentos.len()
+        ));
+    }
+    if !en_lote && opciones.entrada.is_some() {
+        return Err("--input no se puede combinar con argumentos posicionales".to_string());
+    }
+    if comando == Comando::Convert && opciones.tasas.is_none() {
+        return Err("convert necesita --rates <archivo>".to_string());
+    }
+    Ok(opciones)
+}
+
+fn parsear_redondeo(valor: &str) -> Result<ModoRedondeo, String> {
+    match valor {
+        "half-up" => Ok(ModoRedondeo::MitadArriba),
+        "half-even" => Ok(ModoRedondeo::MitadPar),
+        "floor" => Ok(ModoRedondeo::Piso),
+        "ceiling" => Ok(ModoRedondeo::Techo),
+        "truncate" => Ok(ModoRedondeo::Truncar),
+        otro => Err(format!("modo de redondeo desconocido: {}", otro)),
+    }
+}
+
+fn parsear_estilo(valor: &str) -> Result<EstiloFormato, String> {
+    match valor {
+        "standard" => Ok(EstiloFormato::Estandar),
+        "accounting" => Ok(EstiloFormato::Contable),
+        "compact" => Ok(EstiloFormato::Compacto),
+        "sign" => Ok(EstiloFormato::ConSigno),
+        otro => Err(format!("estilo desconocido: {}", otro)),
+    }
+}
+
+/// Resultado de procesar un registro, en texto y en JSON.
+struct Registro {
+    texto: String,
+    json: Value,
+}
+
+/// Ejecuta el comando y retorna el código de salida del proceso.
+pub fn ejecutar(
+    opciones: &Opciones,
+    entrada: &mut dyn BufRead,
+    salida: &mut dyn Write,
+    errores: &mut dyn Write,
+) -> i32 {
+    let historial = match &opciones.tasas {
+        Some(ruta) => match HistorialTasas::cargar(ruta) {
+            Ok(historial) => Some(historial),
+            Err(error) => {
+                let _ = writeln!(errores, "Error: {}", error);
+                return SALIDA_ENTRADA_INVALIDA;
+            }
+        },
+        None => None,
+    };
+
+    let en_lote = opciones.en_lote();
+    let registros: Vec<(usize, Vec<String>)> = if en_lote {
+        let texto = match &opciones.entrada {
+            Some(ruta) => {
+                fs::read_to_string(ruta).map_err(|error| format!("{}: {}", ruta.display(), error))
+            }
+            None => {
+                let mut texto = String::new();
+                entrada
+                    .read_to_string(&mut texto)
+                    .map(|_| texto)
+                    .map_err(|error| error.to_string())
+            }
+        };
+        match texto {
+            Ok(texto) => leer_registros(&texto, opciones.comando),
+            Err(error) => {
+                let _ = writeln!(errores, "Error: {}", error);
+                return SALIDA_ENTRADA_INVALIDA;
+            }
+        }
+    } else {
+        vec![(1, opciones.argumentos.clone())]
+    };
+
+    let mut codigo = SALIDA_OK;
+    let mut resultados_json = Vec::new();
+    for (linea, campos) in &registros {
+        let resultado = procesar(opciones, historial.as_ref(), campos);
+        if resultado.is_err() {
+            codigo = SALIDA_ENTRADA_INVALIDA;
+        }
+        match (opciones.salida, resultado) {
+            (Salida::Texto, Ok(registro)) => {
+                let _ = writeln!(salida, "{}", registro.texto);
+            }
+            (Salida::Texto, Err(error)) if en_lote => {
+                let _ = writeln!(errores, "linea {}: {}", linea, error);
+            }
+            (Salida::Texto, Err(error)) => {
+                let _ = writeln!(errores, "Error: {}", error);
+            }
+            (Salida::Json, Ok(mut registro)) => {
+                if en_lote {
+                    registro.json["line"] = json!(linea);
+                }
+                resultados_json.push(registro.json);
+            }
+            (Salida::Json, Err(error)) => {
+                let mut json = json!({ "error": error.to_string() });
+                if en_lote {
+                    json["line"] = json!(linea);
+                }
+                resultados_json.push(json);
+            }
+        }
+    }
+
+    if opciones.salida == Salida::Json {
+        let json = if en_lote {
+            Value::Array(resultados_json)
+        } else {
+            resultados_json.pop().unwrap_or(Value::Null)
+        };
+        let _ = writeln!(salida, "{}", json);
+    }
+    codigo
+}
+
+/// Separa el texto en registros numerados, omitiendo líneas vacías y el encabezado.
+fn leer_registros(texto: &str, comando: Comando) -> Vec<(usize, Vec<String>)> {
+    texto
+        .lines()
+        .enumerate()
+        .filter(|(i, linea)| {
+            let linea = linea.trim();
+            let es_encabezado = *i == 0 && linea.replace(' ', "") == comando.encabezado();
+            !linea.is_empty() && !es_encabezado
+        })
+        .map(|(i, linea)| (i + 1, separar_campos(linea.trim(), comando.campos())))
+        .collect()
+}
+
+/// Separa una línea CSV en a lo sumo `maximo` campos; el último se queda con el resto de la línea.
+///
+/// Un campo entre comillas dobles puede contener comas, y `""` representa una comilla.
+fn separar_campos(linea: &str, maximo: usize) -> Vec<String> {
+    let mut campos = Vec::new();
+    let mut resto = linea;
+    while campos.len() + 1 < maximo {
+        let (campo, siguiente) = leer_campo(resto);
+        campos.push(campo);
+        match siguiente {
+            Some(siguiente) => resto = siguiente,
+            None => return campos,
+        }
+    }
+    let ultimo = resto.trim();
+    match ultimo.strip_prefix('"').and_then(|s| s.strip_suffix('"')) {
+        Some(entre_comillas) => campos.push(entre_comillas.replace("\"\"", "\"")),
+        None => campos.push(ultimo.to_string()),
+    }
+    campos
+}
+
+/// Lee un campo y retorna el resto de la línea después de la coma, si la hay.
+fn leer_campo(texto: &str) -> (String, Option<&str>) {
+    let texto = texto.trim_start();
+    let Some(contenido) = texto.strip_prefix('"') else {
+        return match texto.split_once(',') {
+            Some((campo, resto)) => (campo.trim().to_string(), Some(resto)),
+            None => (texto.trim().to_string(), None),
+        };
+    };
+
+    let mut campo = String::new();
+    let mut caracteres = contenido.char_indices().peekable();
+    while let Some((i, c)) = caracteres.next() {
+        if c != '"' {
+            campo.push(c);
+            continue;
+        }
+        if caracteres.peek().map(|(_, c)| *c) == Some('"') {
+            campo.push('"');
+            caracteres.next();
+            continue;
+        }
+        let resto = &contenido[i + 1..];
+        return match resto.split_once(',') {
+            Some((_, resto)) => (campo, Some(resto)),
+            None => (campo, None),
+        };
+    }
+    (campo, None)
+}
+
+fn procesar(
+    opciones: &Opciones,
+    historial: Option<&HistorialTasas>,
+    campos: &[String],
+) -> Result<Registro, ErrorMoneda> {
+    if campos.len() != opciones.comando.campos() {
+        return Err(ErrorMoneda::FormatoInvalido {
+            texto: campos.join(","),
+            motivo: format!(
+                "se esperaban {} campos ({})",
+                opciones.comando.campos(),
+                opciones.comando.encabezado()
+            ),
+        });
+    }
+
+    match opciones.comando {
+        Comando::Format => {
+            let centavos = parsear_centavos_argumento(&campos[0])?;
+            let texto = formatear_salida(opciones, centavos, &campos[1])?;
+            let json = json!({
+                "amount_cents": centavos,
+                "currency": campos[1],
+                "formatted": texto,
+            });
+            Ok(Registro { texto, json })
+        }
+        Comando::Convert => {
+            let centavos = parsear_centavos_argumento(&campos[0])?;
+            let origen = Dinero::desde_codigo(centavos, &campos[1])?;
+            let destino = Moneda::desde_codigo(&campos[2])?;
+            // `parsear_argumentos` lo exige, pero `Opciones` también se puede construir a mano.
+            let historial = historial.ok_or_else(|| ErrorMoneda::ArchivoTasas {
+                linea: None,
+                mensaje: "convert necesita --rates <archivo>".to_string(),
+            })?;
+            let convertido = match opciones.fecha {
+                Some(fecha) => convertir_con_redondeo(
+                    origen,
+                    destino,
+                    historial.en_fecha(fecha)?,
+                    opciones.redondeo,
+                ),
+                None => convertir_con_redondeo(origen, destino, historial, opciones.redondeo),
+            }?;
+            let texto = formatear_salida(opciones, convertido.centavos, destino.codigo())?;
+            let json = json!({
+                "amount_cents": convertido.centavos,
+                "currency": destino.codigo(),
+                "formatted": texto,
+                "from": { "amount_cents": origen.centavos, "currency": origen.moneda.codigo() },
+            });
+            Ok(Registro { texto, json })
+        }
+        Comando::Parse => {
+            let dinero = match opciones.locale {
+                Some(locale) => parsear_moneda_con_locale(&campos[0], locale),
+                None => parsear_moneda(&campos[0]),
+            }?;
+            let texto = format!("{} {}", dinero.centavos, dinero.moneda);
+            let json = json!({
+                "amount_cents": dinero.centavos,
+                "currency": dinero.moneda.codigo(),
+            });
+            Ok(Registro { texto, json })
+        }
+    }
+}
+
+fn parsear_centavos_argumento(texto: &str) -> Result<i64, ErrorMoneda> {
+    texto
+        .trim()
+        .parse()
+        .map_err(|_| ErrorMoneda::FormatoInvalido {
+            texto: texto.to_string(),
+            motivo: "se esperaba una cantidad entera de centavos".to_string(),
+        })
+}
+
+/// Con --locale usa las convenciones del locale; sin él, el formato de `convertir_cents_a_moneda`.
+///
+/// Un --style distinto de `standard` siempre usa un locale (en-US si no se indica).
+fn formatear_salida(
+    opciones: &Opciones,
+    centavos: i64,
+    codigo_moneda: &str,
+) -> Result<String, ErrorMoneda> {
+    if opciones.estilo != EstiloFormato::Estandar {
+        return formatear_con_estilo(
+            centavos,
+            Moneda::desde_codigo(codigo_moneda)?,
+            opciones.locale.unwrap_or(Locale::EnUs),
+            opciones.estilo,
+        );
+    }
+    match opciones.locale {
+        Some(locale) => formatear(centavos, Moneda::desde_codigo(codigo_moneda)?, locale),
+        None => intentar_convertir_cents_a_moneda(centavos, codigo_moneda),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(texto: &str) -> Vec<String> {
+        texto.split_whitespace().map(String::from).collect()
+    }
+
+    fn correr(argumentos: &[String], entrada: &str) -> (i32, String, String) {
+        let opciones = parsear_argumentos(argumentos).unwrap();
+        let mut salida = Vec::new();
+        let mut errores = Vec::new();
+        let codigo = ejecutar(
+            &opciones,
+            &mut entrada.as_bytes(),
+            &mut salida,
+            &mut errores,
+        );
+        (
+            codigo,
+            String::from_utf8(salida).unwrap(),
+            String::from_utf8(errores).unwrap(),
+        )
+    }
+
+    fn archivo_de_tasas(nombre: &str) -> PathBuf {
+        let ruta = std::env::temp_dir().join(format!(
+            "convercion_monedas_{}_{}.csv",
+            nombre,
+            std::process::id()
+        ));
+        fs::write(
+            &ruta,
+            "fecha,base,moneda,tasa\n2024-01-02,USD,EUR,0.8\n2024-01-02,USD,MXN,20\n\
+             2024-01-03,USD,EUR,0.9\n2024-01-03,USD,MXN,18\n",
+        )
+        .unwrap();
+        ruta
+    }
+
+    #[test]
+    fn test_format() {
+        assert_eq!(
+            correr(&args("format 123456 USD --locale es-MX"), ""),
+            (SALIDA_OK, "$1,234.56\n".to_string(), String::new())
+        );
+        assert_eq!(
+            correr(&args("format 1234 USD"), ""),
+            (SALIDA_OK, "$12.34\n".to_string(), String::new())
+        );
+    }
+
+    #[test]
+    fn test_format_con_estilo() {
+        assert_eq!(
+            correr(&args("format -1234 USD --style accounting"), "").1,
+            "($12.34)\n"
+        );
+        assert_eq!(
+            correr(&args("format 120000 EUR --style compact --locale es-ES"), "").1,
+            "1,2 mil €\n"
+        );
+        assert_eq!(correr(&args("format 50 GBP --style sign"), "").1, "+£0.50\n");
+        assert!(parsear_argumentos(&args("format 1 USD --style fancy")).is_err());
+    }
+
+    #[test]
+    fn test_format_invalido() {
+        let (codigo, salida, errores) = correr(&args("format 100 ABC"), "");
+        assert_eq!(codigo, SALIDA_ENTRADA_INVALIDA);
+        assert_eq!(salida, "");
+        assert_eq!(errores, "Error: codigo de moneda invalido: \"ABC\"\n");
+    }
+
+    #[test]
+    fn test_convert() {
+        let ruta = archivo_de_tasas("convert");
+        let mut argumentos = args("convert 1000 EUR MXN --rates");
+        argumentos.push(ruta.display().to_string());
+        assert_eq!(correr(&argumentos, "").1, "MX$200.00\n");
+
+        argumentos.extend(args("--date 2024-01-02 --output json"));
+        let (codigo, salida, _) = correr(&argumentos, "");
+        assert_eq!(codigo, SALIDA_OK);
+        let json: Value = serde_json::from_str(&salida).unwrap();
+        assert_eq!(json["amount_cents"], 25000);
+        assert_eq!(json["from"]["currency"], "EUR");
+        fs::remove_file(ruta).unwrap();
+
+        // Construidas a mano, sin --rates: error en lugar de pánico.
+        let mut opciones = parsear_argumentos(&args("format 1000 EUR")).unwrap();
+        opciones.comando = Comando::Convert;
+        opciones.argumentos = args("1000 EUR MXN");
+        let (mut salida, mut errores) = (Vec::new(), Vec::new());
+        let codigo = ejecutar(&opciones, &mut "".as_bytes(), &mut salida, &mut errores);
+        assert_eq!(codigo, SALIDA_ENTRADA_INVALIDA);
+        assert_eq!(
+            String::from_utf8(errores).unwrap(),
+            "Error: archivo de tasas: convert necesita --rates <archivo>\n"
+        );
+    }
+
+    #[test]
+    fn test_parse_con_comillas() {
+        assert_eq!(
+            correr(&["parse".to_string(), "€0,50".to_string()], "").1,
+            "50 EUR\n"
+        );
+    }
+
+    #[test]
+    fn test_lote_desde_stdin() {
+        let entrada = "centavos,moneda\n1234,USD\n\n50,\"EUR\"\n-1,GBP\n";
+        let (codigo, salida, errores) = correr(&args("format"), entrada);
+        assert_eq!(codigo, SALIDA_ENTRADA_INVALIDA);
+        assert_eq!(salida, "$12.34\n€0.50\n");
+        assert_eq!(
+            errores,
+            "linea 5: valor invalido: -1 centavos es negativo\n"
+        );
+    }
+
+    #[test]
+    fn test_lote_json() {
+        let entrada = "\"€0,50\"\nGBP 1,234.00\nnada\n";
+        let (codigo, salida, _) = correr(&args("parse - --output json"), entrada);
+        assert_eq!(codigo, SALIDA_ENTRADA_INVALIDA);
+        let json: Value = serde_json::from_str(&salida).unwrap();
+        assert_eq!(
+            json[0],
+            json!({ "amount_cents": 50, "currency": "EUR", "line": 1 })
+        );
+        assert_eq!(json[1]["amount_cents"], 123400);
+        assert_eq!(json[2]["line"], 3);
+        assert!(json[2]["error"].is_string());
+    }
+
+    #[test]
+    fn test_argumentos_invalidos() {
+        assert!(parsear_argumentos(&args("")).is_err());
+        assert!(parsear_argumentos(&args("sumar 1 2")).is_err());
+        assert!(parsear_argumentos(&args("format 1 USD extra")).is_err());
+        assert!(parsear_argumentos(&args("format 1 USD --locale xx")).is_err());
+        assert!(parsear_argumentos(&args("convert 1 USD EUR")).is_err());
+        assert!(parsear_argumentos(&args("format 1 USD --output xml")).is_err());
+    }
+
+    #[test]
+    fn test_separar_campos() {
+        assert_eq!(separar_campos("1234, USD", 2), vec!["1234", "USD"]);
+        assert_eq!(separar_campos("€0,50", 1), vec!["€0,50"]);
+        assert_eq!(
+            separar_campos("\"di \"\"hola\"\"\"", 1),
+            vec!["di \"hola\""]
+        );
+        assert_eq!(
+            separar_campos("\"1,000\",EUR,MXN", 3),
+            vec!["1,000", "EUR", "MXN"]
+        );
+        assert_eq!(separar_campos("1000", 3), vec!["1000"]);
+    }
+}
@@ -0,0 +1,108 @@
+// src/conversion.rs
+
+use crate::dinero::Dinero;
+use crate::error::ErrorMoneda;
+use crate::moneda::Moneda;
+use crate::redondeo::{self, ModoRedondeo};
+use crate::tasas::ProveedorTasas;
+
+/// Convierte una cantidad a otra moneda con las tasas del proveedor.
+///
+/// Redondea al centavo más cercano (`ModoRedondeo::MitadArriba`); para elegir otro modo
+/// usa `convertir_con_redondeo`.
+pub fn convertir(
+    dinero: Dinero,
+    moneda_destino: Moneda,
+    proveedor: &dyn ProveedorTasas,
+) -> Result<Dinero, ErrorMoneda> {
+    convertir_con_redondeo(dinero, moneda_destino, proveedor, ModoRedondeo::MitadArriba)
+}
+
+/// Convierte una cantidad a otra moneda redondeando los centavos con el modo indicado.
+///
+/// # Retorno
+/// Retorna el `Dinero` en la moneda destino, o `Err` si el proveedor no tiene la tasa o el
+/// resultado no cabe en un `i64`.
+pub fn convertir_con_redondeo(
+    dinero: Dinero,
+    moneda_destino: Moneda,
+    proveedor: &dyn ProveedorTasas,
+    modo: ModoRedondeo,
+) -> Result<Dinero, ErrorMoneda> {
+    let tasa = proveedor.tasa(dinero.moneda, moneda_destino)?;
+    let producto = i128::from(dinero.centavos)
+        .checked_mul(tasa.numerador())
+        .ok_or(ErrorMoneda::Desbordamiento)?;
+    let centavos = redondeo::dividir_redondeando(producto, tasa.denominador(), modo);
+    let centavos = i64::try_from(centavos).map_err(|_| ErrorMoneda::Desbordamiento)?;
+
+    Ok(Dinero::new(centavos, moneda_destino))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tasas::TablaTasas;
+
+    fn tabla() -> TablaTasas {
+        TablaTasas::new(Moneda::USD)
+            .con_tasa(Moneda::EUR, "0.9".parse().unwrap())
+            .con_tasa(Moneda::MXN, "17.05".parse().unwrap())
+    }
+
+    #[test]
+    fn test_convertir() {
+        let dinero = Dinero::new(1000, Moneda::USD);
+        assert_eq!(
+            convertir(dinero, Moneda::MXN, &tabla()),
+            Ok(Dinero::new(17050, Moneda::MXN))
+        );
+    }
+
+    #[test]
+    fn test_convertir_triangulando() {
+        // 1000 EUR-centavos * 17.05 / 0.9 = 18944.44...
+        let dinero = Dinero::new(1000, Moneda::EUR);
+        assert_eq!(
+            convertir(dinero, Moneda::MXN, &tabla()),
+            Ok(Dinero::new(18944, Moneda::MXN))
+        );
+        assert_eq!(
+            convertir_con_redondeo(dinero, Moneda::MXN, &tabla(), ModoRedondeo::Techo),
+            Ok(Dinero::new(18945, Moneda::MXN))
+        );
+    }
+
+    #[test]
+    fn test_convertir_modos_de_redondeo() {
+        // 5 USD-centavos * 0.9 = 4.5 centavos de euro.
+        let dinero = Dinero::new(5, Moneda::USD);
+        let casos = [
+            (ModoRedondeo::MitadArriba, 5),
+            (ModoRedondeo::MitadPar, 4),
+            (ModoRedondeo::Piso, 4),
+            (ModoRedondeo::Techo, 5),
+            (ModoRedondeo::Truncar, 4),
+        ];
+        for (modo, esperado) in casos {
+            assert_eq!(
+                convertir_con_redondeo(dinero, Moneda::EUR, &tabla(), modo),
+                Ok(Dinero::new(esperado, Moneda::EUR))
+            );
+        }
+    }
+
+    #[test]
+    fn test_convertir_errores() {
+        let dinero = Dinero::new(100, Moneda::GBP);
+        assert_eq!(
+            convertir(dinero, Moneda::USD, &tabla()),
+            Err(ErrorMoneda::TasaNoDisponible(Moneda::GBP, Moneda::USD))
+        );
+        let enorme = Dinero::new(i64::MAX, Moneda::USD);
+        assert_eq!(
+            convertir(enorme, Moneda::MXN, &tabla()),
+            Err(ErrorMoneda::Desbordamiento)
+        );
+    }
+}
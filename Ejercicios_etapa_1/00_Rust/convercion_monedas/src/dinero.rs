@@ -0,0 +1,298 @@
+// src/dinero.rs
+
+use std::fmt;
+use std::str::FromStr;
+
+use crate::error::ErrorMoneda;
+use crate::formato::{self, EstiloFormato};
+use crate::letras::{self, Idioma};
+use crate::locale::Locale;
+use crate::moneda::Moneda;
+use crate::porcentaje::Porcentaje;
+use crate::redondeo::{ModoRedondeo, dividir_redondeando};
+
+/// Una cantidad de dinero: centavos (unidades menores) más la moneda a la que pertenecen.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Dinero {
+    pub centavos: i64,
+    pub moneda: Moneda,
+}
+
+impl Dinero {
+    pub fn new(centavos: i64, moneda: Moneda) -> Dinero {
+        Dinero { centavos, moneda }
+    }
+
+    /// Crea un `Dinero` validando el código de moneda.
+    pub fn desde_codigo(centavos: i64, codigo_moneda: &str) -> Result<Dinero, ErrorMoneda> {
+        Ok(Dinero::new(centavos, Moneda::desde_codigo(codigo_moneda)?))
+    }
+
+    /// Monto en unidades con punto decimal y sin símbolo ("12.34", "-0.50").
+    ///
+    /// Es la representación canónica para APIs y bases de datos; no depende del locale.
+    pub fn monto_decimal(&self) -> String {
+        let signo = if self.centavos < 0 { "-" } else { "" };
+        let absoluto = self.centavos.unsigned_abs();
+        format!("{}{}.{:02}", signo, absoluto / 100, absoluto % 100)
+    }
+
+    /// Lee un monto escrito como lo produce `monto_decimal` ("12.34", "-0.5", "7").
+    pub fn desde_monto_decimal(monto: &str, moneda: Moneda) -> Result<Dinero, ErrorMoneda> {
+        let invalido = |motivo: &str| ErrorMoneda::FormatoInvalido {
+            texto: monto.to_string(),
+            motivo: motivo.to_string(),
+        };
+        let (negativo, sin_signo) = match monto.strip_prefix('-') {
+            Some(resto) => (true, resto),
+            None => (false, monto),
+        };
+        let (entero, decimales) = sin_signo.split_once('.').unwrap_or((sin_signo, "00"));
+        let solo_digitos =
+            |parte: &str| !parte.is_empty() && parte.bytes().all(|b| b.is_ascii_digit());
+        if !solo_digitos(entero) || !solo_digitos(decimales) {
+            return Err(invalido("se esperaba un monto como \"12.34\""));
+        }
+        if decimales.len() > 2 {
+            return Err(invalido("tiene mas de dos decimales"));
+        }
+
+        let texto = format!(
+            "{}{}{:0<2}",
+            if negativo { "-" } else { "" },
+            entero,
+            decimales
+        );
+        let centavos = texto.parse().map_err(|_| ErrorMoneda::Desbordamiento)?;
+        Ok(Dinero::new(centavos, moneda))
+    }
+
+    /// Formatea la cantidad según las convenciones del locale.
+    pub fn formatear(&self, locale: Locale) -> Result<String, ErrorMoneda> {
+        formato::formatear(self.centavos, self.moneda, locale)
+    }
+
+    /// Formatea la cantidad con un estilo (contable, compacto, con signo...).
+    pub fn formatear_con_estilo(
+        &self,
+        locale: Locale,
+        estilo: EstiloFormato,
+    ) -> Result<String, ErrorMoneda> {
+        formato::formatear_con_estilo(self.centavos, self.moneda, locale, estilo)
+    }
+
+    /// Escribe la cantidad en letras ("doce dólares con 34/100").
+    pub fn en_letras(&self, idioma: Idioma) -> Result<String, ErrorMoneda> {
+        letras::en_letras(self.centavos, self.moneda, idioma)
+    }
+
+    /// Calcula un porcentaje de la cantidad (por ejemplo el 16% de IVA).
+    ///
+    /// # Retorno
+    /// Retorna el resultado redondeado al centavo con `modo`, o `Err` si se desborda.
+    pub fn porcentaje(
+        &self,
+        porcentaje: Porcentaje,
+        modo: ModoRedondeo,
+    ) -> Result<Dinero, ErrorMoneda> {
+        let (numerador, denominador) = porcentaje.como_fraccion();
+        self.multiplicar_fraccion(numerador, denominador, modo)
+    }
+
+    /// Multiplica la cantidad por la razón `numerador / denominador` (por ejemplo 1/3 de la cuenta).
+    ///
+    /// # Retorno
+    /// Retorna el resultado redondeado al centavo con `modo`, o `Err` si el denominador es cero
+    /// o el resultado se desborda.
+    pub fn proporcion(
+        &self,
+        numerador: i64,
+        denominador: i64,
+        modo: ModoRedondeo,
+    ) -> Result<Dinero, ErrorMoneda> {
+        if denominador == 0 {
+            return Err(ErrorMoneda::DivisionEntreCero);
+        }
+        self.multiplicar_fraccion(i128::from(numerador), i128::from(denominador), modo)
+    }
+
+    fn multiplicar_fraccion(
+        &self,
+        numerador: i128,
+        denominador: i128,
+        modo: ModoRedondeo,
+    ) -> Result<Dinero, ErrorMoneda> {
+        let producto = i128::from(self.centavos)
+            .checked_mul(numerador)
+            .ok_or(ErrorMoneda::Desbordamiento)?;
+        let centavos = dividir_redondeando(producto, denominador, modo);
+        let centavos = i64::try_from(centavos).map_err(|_| ErrorMoneda::Desbordamiento)?;
+        Ok(Dinero::new(centavos, self.moneda))
+    }
+}
+
+impl fmt::Display for Dinero {
+    /// Forma canónica "12.34 USD"; para mostrar al usuario usa `formatear`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.monto_decimal(), self.moneda)
+    }
+}
+
+impl FromStr for Dinero {
+    type Err = ErrorMoneda;
+
+    /// Lee la forma canónica "12.34 USD".
+    fn from_str(texto: &str) -> Result<Self, Self::Err> {
+        let Some((monto, codigo)) = texto.trim().split_once(' ') else {
+            return Err(ErrorMoneda::FormatoInvalido {
+                texto: texto.to_string(),
+                motivo: "se esperaba \"<monto> <codigo>\", por ejemplo \"12.34 USD\"".to_string(),
+            });
+        };
+        Dinero::desde_monto_decimal(monto, Moneda::desde_codigo(codigo.trim())?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_desde_codigo() {
+        assert_eq!(
+            Dinero::desde_codigo(1234, "MXN"),
+            Ok(Dinero::new(1234, Moneda::MXN))
+        );
+        assert_eq!(
+            Dinero::desde_codigo(1234, "ABC"),
+            Err(ErrorMoneda::CodigoInvalido("ABC".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_formatear() {
+        let dinero = Dinero::new(123456, Moneda::EUR);
+        assert_eq!(dinero.formatear(Locale::EsEs), Ok("1.234,56 €".to_string()));
+    }
+
+    #[test]
+    fn test_monto_decimal() {
+        assert_eq!(Dinero::new(1234, Moneda::USD).monto_decimal(), "12.34");
+        assert_eq!(Dinero::new(-50, Moneda::USD).monto_decimal(), "-0.50");
+        assert_eq!(
+            Dinero::new(i64::MIN, Moneda::USD).monto_decimal(),
+            "-92233720368547758.08"
+        );
+        assert_eq!(
+            Dinero::desde_monto_decimal("-0.5", Moneda::EUR),
+            Ok(Dinero::new(-50, Moneda::EUR))
+        );
+        assert_eq!(
+            Dinero::desde_monto_decimal("7", Moneda::EUR),
+            Ok(Dinero::new(700, Moneda::EUR))
+        );
+        for monto in ["12.345", "1,234.00", "+1", "", ".5", "1."] {
+            assert!(
+                Dinero::desde_monto_decimal(monto, Moneda::EUR).is_err(),
+                "{}",
+                monto
+            );
+        }
+    }
+
+    #[test]
+    fn test_forma_canonica_ida_y_vuelta() {
+        let dinero = Dinero::new(123456, Moneda::GBP);
+        assert_eq!(dinero.to_string(), "1234.56 GBP");
+        assert_eq!("1234.56 GBP".parse(), Ok(dinero));
+        assert_eq!(
+            "1234.56 ABC".parse::<Dinero>(),
+            Err(ErrorMoneda::CodigoInvalido("ABC".to_string()))
+        );
+    }
+
+    /// Columnas: centavos, porcentaje, y el resultado esperado con cada modo en el orden
+    /// MitadArriba, MitadPar, Piso, Techo, Truncar.
+    const VECTORES_PORCENTAJE: [(i64, &str, [i64; 5]); 8] = [
+        // 16% de 1234 = 197.44
+        (1234, "16", [197, 197, 197, 198, 197]),
+        // 10% de 125 = 12.5 (empate, el anterior es par)
+        (125, "10", [13, 12, 12, 13, 12]),
+        // 10% de 135 = 13.5 (empate, el anterior es impar)
+        (135, "10", [14, 14, 13, 14, 13]),
+        // 50% de 1 = 0.5
+        (1, "50", [1, 0, 0, 1, 0]),
+        // 7.5% de 1999 = 149.925
+        (1999, "7.5", [150, 150, 149, 150, 149]),
+        // 10% de -125 = -12.5
+        (-125, "10", [-13, -12, -13, -12, -12]),
+        // 8.875% de -1001 = -88.83875
+        (-1001, "8.875", [-89, -89, -89, -88, -88]),
+        // 100% no redondea
+        (4321, "100", [4321, 4321, 4321, 4321, 4321]),
+    ];
+
+    const MODOS: [ModoRedondeo; 5] = [
+        ModoRedondeo::MitadArriba,
+        ModoRedondeo::MitadPar,
+        ModoRedondeo::Piso,
+        ModoRedondeo::Techo,
+        ModoRedondeo::Truncar,
+    ];
+
+    #[test]
+    fn test_porcentaje_vectores_por_modo() {
+        for (centavos, porcentaje, esperados) in VECTORES_PORCENTAJE {
+            let dinero = Dinero::new(centavos, Moneda::USD);
+            let porcentaje: Porcentaje = porcentaje.parse().unwrap();
+            for (modo, esperado) in MODOS.into_iter().zip(esperados) {
+                assert_eq!(
+                    dinero.porcentaje(porcentaje, modo),
+                    Ok(Dinero::new(esperado, Moneda::USD)),
+                    "{} de {} con {:?}",
+                    porcentaje,
+                    centavos,
+                    modo
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_proporcion_vectores_por_modo() {
+        // (centavos, numerador, denominador, esperados por modo)
+        let vectores: [(i64, i64, i64, [i64; 5]); 4] = [
+            // 1000 / 3 = 333.33
+            (1000, 1, 3, [333, 333, 333, 334, 333]),
+            // 1000 * 2/3 = 666.67
+            (1000, 2, 3, [667, 667, 666, 667, 666]),
+            // 5 / 2 = 2.5
+            (5, 1, 2, [3, 2, 2, 3, 2]),
+            // -1000 / 3 = -333.33
+            (-1000, 1, 3, [-333, -333, -334, -333, -333]),
+        ];
+        for (centavos, numerador, denominador, esperados) in vectores {
+            let dinero = Dinero::new(centavos, Moneda::EUR);
+            for (modo, esperado) in MODOS.into_iter().zip(esperados) {
+                assert_eq!(
+                    dinero.proporcion(numerador, denominador, modo),
+                    Ok(Dinero::new(esperado, Moneda::EUR))
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_proporcion_errores() {
+        let dinero = Dinero::new(100, Moneda::EUR);
+        assert_eq!(
+            dinero.proporcion(1, 0, ModoRedondeo::MitadArriba),
+            Err(ErrorMoneda::DivisionEntreCero)
+        );
+        let enorme = Dinero::new(i64::MAX, Moneda::EUR);
+        assert_eq!(
+            enorme.proporcion(3, 2, ModoRedondeo::MitadArriba),
+            Err(ErrorMoneda::Desbordamiento)
+        );
+    }
+}
@@ -0,0 +1,181 @@
+// src/error.rs
+
+use std::fmt;
+
+use crate::fecha::Fecha;
+use crate::moneda::Moneda;
+
+/// Errores que pueden ocurrir al trabajar con cantidades monetarias.
+///
+/// Sustituye a los mensajes "Valor invalido" y "Codigo de moneda invalido" que antes
+/// se devolvían dentro del mismo `String` que el resultado correcto.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ErrorMoneda {
+    /// La cantidad en centavos es negativa.
+    ValorNegativo(i64),
+    /// El código de moneda no está en la tabla de monedas soportadas.
+    CodigoInvalido(String),
+    /// Una cantidad u operación excede el rango de `i64`.
+    Desbordamiento,
+    /// El locale solicitado no está soportado.
+    LocaleNoSoportado(String),
+    /// Un porcentaje no es un número válido.
+    PorcentajeInvalido(String),
+    /// Se intentó dividir una cantidad entre cero.
+    DivisionEntreCero,
+    /// El proveedor no tiene una tasa de cambio para el par de monedas.
+    TasaNoDisponible(Moneda, Moneda),
+    /// Una tasa de cambio no es un número positivo válido.
+    TasaInvalida(String),
+    /// Una fecha no tiene el formato "AAAA-MM-DD" o no existe en el calendario.
+    FechaInvalida(String),
+    /// Un archivo de tasas tiene una línea mal formada.
+    ArchivoTasas {
+        linea: Option<usize>,
+        mensaje: String,
+    },
+    /// No hay tasas registradas para la fecha pedida ni para ninguna anterior.
+    SinTasasParaFecha(Fecha),
+    /// Un texto no se pudo interpretar como cantidad de dinero.
+    FormatoInvalido { texto: String, motivo: String },
+    /// La operación dejaría la billetera con saldo negativo y no se permite sobregiro.
+    SaldoInsuficiente {
+        moneda: Moneda,
+        saldo: i64,
+        requerido: i64,
+    },
+    /// Los movimientos de una transacción no suman cero en alguna moneda.
+    TransaccionDesbalanceada(Moneda),
+    /// Un depósito, retiro o cambio de cero; no movería nada.
+    CantidadCero,
+    /// Se combinaron cantidades de monedas distintas sin indicar cómo convertirlas.
+    MonedasMezcladas(Moneda, Moneda),
+    /// Se pidió un cálculo sobre una colección vacía.
+    SinValores,
+    /// No se pudo leer un archivo.
+    Io(String),
+}
+
+impl ErrorMoneda {
+    /// Retorna el mensaje que devolvía la versión original de `convertir_cents_a_moneda`,
+    /// o `None` si el error no existía en esa versión.
+    pub fn mensaje_legado(&self) -> Option<&'static str> {
+        match self {
+            ErrorMoneda::ValorNegativo(_) | ErrorMoneda::Desbordamiento => Some("Valor invalido"),
+            ErrorMoneda::CodigoInvalido(_) => Some("Codigo de moneda invalido"),
+            ErrorMoneda::LocaleNoSoportado(_) => Some("Locale invalido"),
+            ErrorMoneda::PorcentajeInvalido(_)
+            | ErrorMoneda::DivisionEntreCero
+            | ErrorMoneda::TasaNoDisponible(..)
+            | ErrorMoneda::TasaInvalida(_)
+            | ErrorMoneda::FechaInvalida(_)
+            | ErrorMoneda::ArchivoTasas { .. }
+            | ErrorMoneda::SinTasasParaFecha(_)
+            | ErrorMoneda::FormatoInvalido { .. }
+            | ErrorMoneda::SaldoInsuficiente { .. }
+            | ErrorMoneda::TransaccionDesbalanceada(_)
+            | ErrorMoneda::CantidadCero
+            | ErrorMoneda::MonedasMezcladas(..)
+            | ErrorMoneda::SinValores
+            | ErrorMoneda::Io(_) => None,
+        }
+    }
+}
+
+impl fmt::Display for ErrorMoneda {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ErrorMoneda::ValorNegativo(centavos) => {
+                write!(f, "valor invalido: {} centavos es negativo", centavos)
+            }
+            ErrorMoneda::CodigoInvalido(codigo) => {
+                write!(f, "codigo de moneda invalido: \"{}\"", codigo)
+            }
+            ErrorMoneda::Desbordamiento => write!(f, "la cantidad excede el rango soportado"),
+            ErrorMoneda::LocaleNoSoportado(locale) => {
+                write!(f, "locale no soportado: \"{}\"", locale)
+            }
+            ErrorMoneda::PorcentajeInvalido(porcentaje) => {
+                write!(f, "porcentaje invalido: \"{}\"", porcentaje)
+            }
+            ErrorMoneda::DivisionEntreCero => write!(f, "division entre cero"),
+            ErrorMoneda::TasaNoDisponible(origen, destino) => {
+                write!(f, "no hay tasa de cambio de {} a {}", origen, destino)
+            }
+            ErrorMoneda::TasaInvalida(tasa) => write!(f, "tasa de cambio invalida: \"{}\"", tasa),
+            ErrorMoneda::FechaInvalida(fecha) => write!(f, "fecha invalida: \"{}\"", fecha),
+            ErrorMoneda::ArchivoTasas {
+                linea: Some(linea),
+                mensaje,
+            } => {
+                write!(f, "archivo de tasas, linea {}: {}", linea, mensaje)
+            }
+            ErrorMoneda::ArchivoTasas {
+                linea: None,
+                mensaje,
+            } => {
+                write!(f, "archivo de tasas: {}", mensaje)
+            }
+            ErrorMoneda::SinTasasParaFecha(fecha) => {
+                write!(
+                    f,
+                    "no hay tasas de cambio para el {} ni fechas anteriores",
+                    fecha
+                )
+            }
+            ErrorMoneda::FormatoInvalido { texto, motivo } => {
+                write!(f, "no se puede interpretar \"{}\": {}", texto, motivo)
+            }
+            ErrorMoneda::SaldoInsuficiente {
+                moneda,
+                saldo,
+                requerido,
+            } => write!(
+                f,
+                "saldo insuficiente en {}: hay {} centavos, se requieren {}",
+                moneda, saldo, requerido
+            ),
+            ErrorMoneda::TransaccionDesbalanceada(moneda) => {
+                write!(f, "los movimientos en {} no suman cero", moneda)
+            }
+            ErrorMoneda::CantidadCero => write!(f, "la cantidad debe ser mayor que cero"),
+            ErrorMoneda::MonedasMezcladas(primera, otra) => write!(
+                f,
+                "monedas mezcladas: {} y {} (se necesita un proveedor de tasas)",
+                primera, otra
+            ),
+            ErrorMoneda::SinValores => write!(f, "no hay valores"),
+            ErrorMoneda::Io(mensaje) => write!(f, "error de lectura: {}", mensaje),
+        }
+    }
+}
+
+impl std::error::Error for ErrorMoneda {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mensajes_legados() {
+        assert_eq!(
+            ErrorMoneda::ValorNegativo(-1).mensaje_legado(),
+            Some("Valor invalido")
+        );
+        assert_eq!(
+            ErrorMoneda::Desbordamiento.mensaje_legado(),
+            Some("Valor invalido")
+        );
+        assert_eq!(
+            ErrorMoneda::CodigoInvalido("ABC".to_string()).mensaje_legado(),
+            Some("Codigo de moneda invalido")
+        );
+        assert_eq!(ErrorMoneda::Io("disco".to_string()).mensaje_legado(), None);
+    }
+
+    #[test]
+    fn test_display() {
+        let error = ErrorMoneda::CodigoInvalido("ABC".to_string());
+        assert_eq!(error.to_string(), "codigo de moneda invalido: \"ABC\"");
+    }
+}
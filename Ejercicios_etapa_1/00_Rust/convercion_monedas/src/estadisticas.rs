@@ -0,0 +1,272 @@
+// src/estadisticas.rs
+
+use crate::conversion;
+use crate::dinero::Dinero;
+use crate::error::ErrorMoneda;
+use crate::formato::EstiloFormato;
+use crate::locale::Locale;
+use crate::moneda::Moneda;
+use crate::redondeo::{ModoRedondeo, dividir_redondeando};
+use crate::tasas::ProveedorTasas;
+
+/// Estadísticas de una lista de precios, calculadas en centavos enteros.
+///
+/// Todas las cantidades deben estar en la misma moneda; para mezclar monedas se usa
+/// `Estadisticas::convertidas`, que lleva cada valor a una moneda común.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Estadisticas {
+    moneda: Moneda,
+    ordenados: Vec<i64>,
+    suma: i64,
+}
+
+/// Un intervalo del histograma: cuenta los valores entre `desde` y `hasta`, ambos incluidos.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cubeta {
+    pub desde: Dinero,
+    pub hasta: Dinero,
+    pub cantidad: usize,
+}
+
+impl Estadisticas {
+    /// Calcula las estadísticas de cantidades que están todas en la misma moneda.
+    ///
+    /// # Retorno
+    /// Retorna `Err(SinValores)` si la lista está vacía, `Err(MonedasMezcladas)` si hay
+    /// más de una moneda y `Err(Desbordamiento)` si la suma no cabe en un `i64`.
+    pub fn calcular(valores: &[Dinero]) -> Result<Estadisticas, ErrorMoneda> {
+        let primero = valores.first().ok_or(ErrorMoneda::SinValores)?;
+        if let Some(otro) = valores.iter().find(|d| d.moneda != primero.moneda) {
+            return Err(ErrorMoneda::MonedasMezcladas(primero.moneda, otro.moneda));
+        }
+
+        let suma = valores.iter().try_fold(0_i64, |suma, dinero| {
+            suma.checked_add(dinero.centavos)
+                .ok_or(ErrorMoneda::Desbordamiento)
+        })?;
+        let mut ordenados: Vec<i64> = valores.iter().map(|d| d.centavos).collect();
+        ordenados.sort_unstable();
+
+        Ok(Estadisticas {
+            moneda: primero.moneda,
+            ordenados,
+            suma,
+        })
+    }
+
+    /// Convierte cada cantidad a `moneda` con el proveedor y calcula las estadísticas.
+    ///
+    /// Cada conversión redondea al centavo con `ModoRedondeo::MitadArriba`, como `convertir`.
+    pub fn convertidas(
+        valores: &[Dinero],
+        moneda: Moneda,
+        proveedor: &dyn ProveedorTasas,
+    ) -> Result<Estadisticas, ErrorMoneda> {
+        if valores.is_empty() {
+            return Err(ErrorMoneda::SinValores);
+        }
+        let convertidos = valores
+            .iter()
+            .map(|dinero| conversion::convertir(*dinero, moneda, proveedor))
+            .collect::<Result<Vec<_>, _>>()?;
+        Estadisticas::calcular(&convertidos)
+    }
+
+    pub fn moneda(&self) -> Moneda {
+        self.moneda
+    }
+
+    pub fn cantidad(&self) -> usize {
+        self.ordenados.len()
+    }
+
+    pub fn suma(&self) -> Dinero {
+        self.dinero(self.suma)
+    }
+
+    pub fn minimo(&self) -> Dinero {
+        self.dinero(self.ordenados[0])
+    }
+
+    pub fn maximo(&self) -> Dinero {
+        self.dinero(self.ordenados[self.ordenados.len() - 1])
+    }
+
+    /// Promedio redondeado al centavo con `ModoRedondeo::MitadArriba`.
+    pub fn media(&self) -> Dinero {
+        let n = self.ordenados.len() as i128;
+        self.dinero_redondeado(i128::from(self.suma), n)
+    }
+
+    /// Valor central; con una cantidad par de valores, el promedio de los dos centrales.
+    pub fn mediana(&self) -> Dinero {
+        let n = self.ordenados.len();
+        if n % 2 == 1 {
+            return self.dinero(self.ordenados[n / 2]);
+        }
+        let suma = i128::from(self.ordenados[n / 2 - 1]) + i128::from(self.ordenados[n / 2]);
+        self.dinero_redondeado(suma, 2)
+    }
+
+    /// Percentil por rango más cercano: el menor valor que deja al menos `p`% de los valores
+    /// por debajo o igual.
+    ///
+    /// # Parámetros
+    /// - `p`: percentil entre 0 y 100; el 0 es el mínimo y el 100 el máximo.
+    pub fn percentil(&self, p: u8) -> Result<Dinero, ErrorMoneda> {
+        if p > 100 {
+            return Err(ErrorMoneda::PorcentajeInvalido(p.to_string()));
+        }
+        let n = self.ordenados.len();
+        let rango = (usize::from(p) * n).div_ceil(100).max(1);
+        Ok(self.dinero(self.ordenados[rango - 1]))
+    }
+
+    /// Reparte los valores en intervalos de igual ancho entre el mínimo y el máximo.
+    ///
+    /// El ancho se redondea hacia arriba al centavo, así que pueden salir menos cubetas
+    /// que las pedidas cuando el rango es pequeño (por ejemplo, si todos los valores son
+    /// iguales sale una sola).
+    pub fn histograma(&self, cubetas: usize) -> Result<Vec<Cubeta>, ErrorMoneda> {
+        if cubetas == 0 {
+            return Err(ErrorMoneda::DivisionEntreCero);
+        }
+        let minimo = i128::from(self.minimo().centavos);
+        let maximo = i128::from(self.maximo().centavos);
+        let ancho = dividir_redondeando(maximo - minimo + 1, cubetas as i128, ModoRedondeo::Techo);
+
+        let mut resultado: Vec<Cubeta> = Vec::new();
+        let mut desde = minimo;
+        while desde <= maximo {
+            let hasta = (desde + ancho - 1).min(maximo);
+            let inicio = self.ordenados.partition_point(|&c| i128::from(c) < desde);
+            let fin = self.ordenados.partition_point(|&c| i128::from(c) <= hasta);
+            resultado.push(Cubeta {
+                desde: self.dinero(desde as i64),
+                hasta: self.dinero(hasta as i64),
+                cantidad: fin - inicio,
+            });
+            desde = hasta + 1;
+        }
+        Ok(resultado)
+    }
+
+    /// Resumen en texto, una estadística por línea, con el formateador del locale.
+    ///
+    /// Los negativos se muestran entre paréntesis (`EstiloFormato::Contable`).
+    pub fn formatear(&self, locale: Locale) -> Result<String, ErrorMoneda> {
+        let formato = |dinero: Dinero| dinero.formatear_con_estilo(locale, EstiloFormato::Contable);
+        Ok([
+            format!("Cantidad: {}", self.cantidad()),
+            format!("Suma: {}", formato(self.suma())?),
+            format!("Minimo: {}", formato(self.minimo())?),
+            format!("Maximo: {}", formato(self.maximo())?),
+            format!("Media: {}", formato(self.media())?),
+            format!("Mediana: {}", formato(self.mediana())?),
+        ]
+        .join("\n"))
+    }
+
+    fn dinero(&self, centavos: i64) -> Dinero {
+        Dinero::new(centavos, self.moneda)
+    }
+
+    /// El cociente está entre el mínimo y el máximo, así que siempre cabe en un `i64`.
+    fn dinero_redondeado(&self, numerador: i128, denominador: i128) -> Dinero {
+        let centavos = dividir_redondeando(numerador, denominador, ModoRedondeo::MitadArriba);
+        self.dinero(centavos as i64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tasas::TablaTasas;
+
+    fn usd(centavos: &[i64]) -> Vec<Dinero> {
+        centavos
+            .iter()
+            .map(|&c| Dinero::new(c, Moneda::USD))
+            .collect()
+    }
+
+    #[test]
+    fn test_estadisticas_basicas() {
+        let estadisticas = Estadisticas::calcular(&usd(&[1999, 500, 2500, 1000])).unwrap();
+        assert_eq!(estadisticas.cantidad(), 4);
+        assert_eq!(estadisticas.suma(), Dinero::new(5999, Moneda::USD));
+        assert_eq!(estadisticas.minimo(), Dinero::new(500, Moneda::USD));
+        assert_eq!(estadisticas.maximo(), Dinero::new(2500, Moneda::USD));
+        // 5999 / 4 = 1499.75 y (1000 + 1999) / 2 = 1499.5; ambos redondean hacia arriba.
+        assert_eq!(estadisticas.media(), Dinero::new(1500, Moneda::USD));
+        assert_eq!(estadisticas.mediana(), Dinero::new(1500, Moneda::USD));
+    }
+
+    #[test]
+    fn test_percentiles() {
+        let valores: Vec<i64> = (1..=10).map(|n| n * 100).collect();
+        let estadisticas = Estadisticas::calcular(&usd(&valores)).unwrap();
+        let percentil = |p| estadisticas.percentil(p).unwrap().centavos;
+        assert_eq!(percentil(0), 100);
+        assert_eq!(percentil(25), 300);
+        assert_eq!(percentil(50), 500);
+        assert_eq!(percentil(90), 900);
+        assert_eq!(percentil(100), 1000);
+        assert_eq!(
+            estadisticas.percentil(101),
+            Err(ErrorMoneda::PorcentajeInvalido("101".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_histograma() {
+        let estadisticas = Estadisticas::calcular(&usd(&[0, 100, 250, 299, 300, 599])).unwrap();
+        let cubetas = estadisticas.histograma(2).unwrap();
+        assert_eq!(cubetas.len(), 2);
+        assert_eq!(cubetas[0].desde.centavos, 0);
+        assert_eq!(cubetas[0].hasta.centavos, 299);
+        assert_eq!(cubetas[0].cantidad, 4);
+        assert_eq!(cubetas[1].hasta.centavos, 599);
+        assert_eq!(cubetas[1].cantidad, 2);
+
+        let iguales = Estadisticas::calcular(&usd(&[700, 700])).unwrap();
+        assert_eq!(iguales.histograma(5).unwrap().len(), 1);
+        assert_eq!(iguales.histograma(0), Err(ErrorMoneda::DivisionEntreCero));
+    }
+
+    #[test]
+    fn test_errores() {
+        assert_eq!(Estadisticas::calcular(&[]), Err(ErrorMoneda::SinValores));
+        let mezcladas = [Dinero::new(100, Moneda::USD), Dinero::new(100, Moneda::EUR)];
+        assert_eq!(
+            Estadisticas::calcular(&mezcladas),
+            Err(ErrorMoneda::MonedasMezcladas(Moneda::USD, Moneda::EUR))
+        );
+        assert_eq!(
+            Estadisticas::calcular(&usd(&[i64::MAX, 1])),
+            Err(ErrorMoneda::Desbordamiento)
+        );
+    }
+
+    #[test]
+    fn test_convertidas() {
+        let tabla = TablaTasas::new(Moneda::USD).con_tasa(Moneda::EUR, "0.5".parse().unwrap());
+        let mezcladas = [
+            Dinero::new(1000, Moneda::USD),
+            Dinero::new(1000, Moneda::EUR),
+        ];
+        let estadisticas = Estadisticas::convertidas(&mezcladas, Moneda::USD, &tabla).unwrap();
+        assert_eq!(estadisticas.suma(), Dinero::new(3000, Moneda::USD));
+        assert_eq!(estadisticas.maximo(), Dinero::new(2000, Moneda::USD));
+    }
+
+    #[test]
+    fn test_formatear() {
+        let estadisticas = Estadisticas::calcular(&usd(&[-500, 123456])).unwrap();
+        assert_eq!(
+            estadisticas.formatear(Locale::EnUs).unwrap(),
+            "Cantidad: 2\nSuma: $1,229.56\nMinimo: ($5.00)\nMaximo: $1,234.56\n\
+             Media: $614.78\nMediana: $614.78"
+        );
+    }
+}
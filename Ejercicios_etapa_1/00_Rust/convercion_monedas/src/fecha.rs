@@ -0,0 +1,192 @@
+// src/fecha.rs
+
+use std::fmt;
+use std::str::FromStr;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::error::ErrorMoneda;
+
+/// Fecha de calendario ("AAAA-MM-DD") sin zona horaria.
+///
+/// El orden de los campos hace que `Ord` sea el orden cronológico.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Fecha {
+    pub anio: i32,
+    pub mes: u32,
+    pub dia: u32,
+}
+
+impl Fecha {
+    /// Crea una fecha validando que exista en el calendario gregoriano.
+    pub fn new(anio: i32, mes: u32, dia: u32) -> Result<Fecha, ErrorMoneda> {
+        if !(1..=12).contains(&mes) || dia == 0 || dia > dias_del_mes(anio, mes) {
+            return Err(ErrorMoneda::FechaInvalida(format!(
+                "{:04}-{:02}-{:02}",
+                anio, mes, dia
+            )));
+        }
+        Ok(Fecha { anio, mes, dia })
+    }
+}
+
+fn es_bisiesto(anio: i32) -> bool {
+    (anio % 4 == 0 && anio % 100 != 0) || anio % 400 == 0
+}
+
+fn dias_del_mes(anio: i32, mes: u32) -> u32 {
+    match mes {
+        2 if es_bisiesto(anio) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+impl FromStr for Fecha {
+    type Err = ErrorMoneda;
+
+    fn from_str(texto: &str) -> Result<Self, Self::Err> {
+        let invalida = || ErrorMoneda::FechaInvalida(texto.to_string());
+        let partes: Vec<&str> = texto.trim().split('-').collect();
+        if partes.len() != 3 || partes[0].len() != 4 || partes[1].len() != 2 || partes[2].len() != 2
+        {
+            return Err(invalida());
+        }
+
+        let anio = partes[0].parse().map_err(|_| invalida())?;
+        let mes = partes[1].parse().map_err(|_| invalida())?;
+        let dia = partes[2].parse().map_err(|_| invalida())?;
+        Fecha::new(anio, mes, dia).map_err(|_| invalida())
+    }
+}
+
+impl fmt::Display for Fecha {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.anio, self.mes, self.dia)
+    }
+}
+
+/// Fecha y hora UTC con precisión de segundos, para registrar cuándo ocurrió algo.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct FechaHora {
+    pub fecha: Fecha,
+    pub hora: u32,
+    pub minuto: u32,
+    pub segundo: u32,
+}
+
+impl FechaHora {
+    pub fn new(
+        fecha: Fecha,
+        hora: u32,
+        minuto: u32,
+        segundo: u32,
+    ) -> Result<FechaHora, ErrorMoneda> {
+        if hora > 23 || minuto > 59 || segundo > 59 {
+            return Err(ErrorMoneda::FechaInvalida(format!(
+                "{} {:02}:{:02}:{:02}",
+                fecha, hora, minuto, segundo
+            )));
+        }
+        Ok(FechaHora {
+            fecha,
+            hora,
+            minuto,
+            segundo,
+        })
+    }
+
+    /// Convierte segundos desde 1970-01-01 00:00:00 UTC.
+    pub fn desde_unix(segundos: i64) -> FechaHora {
+        let dias = segundos.div_euclid(86_400);
+        let del_dia = segundos.rem_euclid(86_400) as u32;
+
+        // Algoritmo "civil_from_days" de Howard Hinnant.
+        let z = dias + 719_468;
+        let era = z.div_euclid(146_097);
+        let dia_de_era = z.rem_euclid(146_097);
+        let anio_de_era =
+            (dia_de_era - dia_de_era / 1460 + dia_de_era / 36_524 - dia_de_era / 146_096) / 365;
+        let dia_del_anio = dia_de_era - (365 * anio_de_era + anio_de_era / 4 - anio_de_era / 100);
+        let mes_desde_marzo = (5 * dia_del_anio + 2) / 153;
+        let dia = (dia_del_anio - (153 * mes_desde_marzo + 2) / 5 + 1) as u32;
+        let mes = if mes_desde_marzo < 10 {
+            mes_desde_marzo + 3
+        } else {
+            mes_desde_marzo - 9
+        } as u32;
+        let anio = (anio_de_era + era * 400 + i64::from(mes <= 2)) as i32;
+
+        FechaHora {
+            fecha: Fecha { anio, mes, dia },
+            hora: del_dia / 3600,
+            minuto: del_dia % 3600 / 60,
+            segundo: del_dia % 60,
+        }
+    }
+
+    /// Fecha y hora actuales según el reloj del sistema.
+    pub fn ahora() -> FechaHora {
+        let segundos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duracion| duracion.as_secs() as i64)
+            .unwrap_or(0);
+        FechaHora::desde_unix(segundos)
+    }
+}
+
+impl fmt::Display for FechaHora {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} {:02}:{:02}:{:02}",
+            self.fecha, self.hora, self.minuto, self.segundo
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parsear_fecha() {
+        let fecha: Fecha = "2024-02-29".parse().unwrap();
+        assert_eq!(
+            fecha,
+            Fecha {
+                anio: 2024,
+                mes: 2,
+                dia: 29
+            }
+        );
+        assert_eq!(fecha.to_string(), "2024-02-29");
+    }
+
+    #[test]
+    fn test_fechas_invalidas() {
+        for texto in ["2023-02-29", "2024-13-01", "2024-1-01", "ayer"] {
+            assert_eq!(
+                texto.parse::<Fecha>(),
+                Err(ErrorMoneda::FechaInvalida(texto.to_string()))
+            );
+        }
+    }
+
+    #[test]
+    fn test_orden_cronologico() {
+        let a: Fecha = "2023-12-31".parse().unwrap();
+        let b: Fecha = "2024-01-01".parse().unwrap();
+        assert!(a < b);
+    }
+
+    #[test]
+    fn test_fecha_hora_desde_unix() {
+        assert_eq!(FechaHora::desde_unix(0).to_string(), "1970-01-01 00:00:00");
+        assert_eq!(
+            FechaHora::desde_unix(1_709_210_096).to_string(),
+            "2024-02-29 12:34:56"
+        );
+        assert_eq!(FechaHora::desde_unix(-1).to_string(), "1969-12-31 23:59:59");
+    }
+}
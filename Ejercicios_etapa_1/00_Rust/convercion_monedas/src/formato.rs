@@ -0,0 +1,341 @@
+// src/formato.rs
+
+use crate::error::ErrorMoneda;
+use crate::locale::Locale;
+use crate::moneda::Moneda;
+use crate::redondeo::{ModoRedondeo, dividir_redondeando};
+
+/// Estilos de presentación de `formatear_con_estilo`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum EstiloFormato {
+    /// "$1,234.56"; rechaza negativos igual que `formatear`.
+    #[default]
+    Estandar,
+    /// Negativos entre paréntesis, como en los reportes financieros: "($12.34)".
+    Contable,
+    /// Abreviado para tableros: "$1.2K", "$3.4M", "1,2 mil €".
+    Compacto,
+    /// Siempre con signo: "+$12.34", "-$12.34" (el cero no lleva signo).
+    ConSigno,
+}
+
+/// Convierte una cantidad en centavos a un formato monetario, reportando los errores con `ErrorMoneda`.
+///
+/// # Parámetros
+/// - `centavos`: número entero que representa la cantidad en centavos.
+/// - `codigo_moneda`: string que representa el código de la moneda ("USD", "EUR", "GBP").
+///
+/// # Retorno
+/// Retorna `Ok` con el símbolo seguido del valor con dos decimales (por ejemplo "$12.34"),
+/// o `Err` si la cantidad es negativa o el código no existe.
+pub fn intentar_convertir_cents_a_moneda(
+    centavos: i64,
+    codigo_moneda: &str,
+) -> Result<String, ErrorMoneda> {
+    validar_no_negativo(centavos)?;
+    let moneda = Moneda::desde_codigo(codigo_moneda)?;
+    let (entero, decimales) = partes(centavos);
+
+    Ok(format!("{}{}.{}", moneda.simbolo(), entero, decimales))
+}
+
+/// Formatea una cantidad en centavos según las convenciones de un locale.
+///
+/// # Parámetros
+/// - `centavos`: número entero que representa la cantidad en centavos.
+/// - `codigo_moneda`: string con el código de la moneda ("USD", "EUR", "GBP").
+/// - `locale`: etiqueta del locale ("en-US", "es-MX", "es-ES", ...).
+///
+/// # Retorno
+/// Retorna el valor con separador de miles, separador decimal y posición del símbolo
+/// del locale (por ejemplo "$1,234.56" en "es-MX" o "1.234,56 €" en "es-ES").
+pub fn formatear_moneda(
+    centavos: i64,
+    codigo_moneda: &str,
+    locale: &str,
+) -> Result<String, ErrorMoneda> {
+    validar_no_negativo(centavos)?;
+    let moneda = Moneda::desde_codigo(codigo_moneda)?;
+    let locale = Locale::desde_etiqueta(locale)?;
+
+    formatear(centavos, moneda, locale)
+}
+
+/// Versión tipada de `formatear_moneda`.
+pub fn formatear(centavos: i64, moneda: Moneda, locale: Locale) -> Result<String, ErrorMoneda> {
+    validar_no_negativo(centavos)?;
+    let (entero, decimales) = partes(centavos);
+    let numero = format!(
+        "{}{}{}",
+        agrupar_miles(&entero, locale.separador_miles()),
+        locale.separador_decimal(),
+        decimales
+    );
+
+    Ok(con_simbolo(&numero, moneda, locale))
+}
+
+/// Igual que `formatear_moneda`, con un estilo de presentación.
+///
+/// Solo `EstiloFormato::Estandar` rechaza los negativos; los demás estilos los aceptan.
+pub fn formatear_moneda_con_estilo(
+    centavos: i64,
+    codigo_moneda: &str,
+    locale: &str,
+    estilo: EstiloFormato,
+) -> Result<String, ErrorMoneda> {
+    if estilo == EstiloFormato::Estandar {
+        validar_no_negativo(centavos)?;
+    }
+    let moneda = Moneda::desde_codigo(codigo_moneda)?;
+    let locale = Locale::desde_etiqueta(locale)?;
+
+    formatear_con_estilo(centavos, moneda, locale, estilo)
+}
+
+/// Versión tipada de `formatear_moneda_con_estilo`.
+pub fn formatear_con_estilo(
+    centavos: i64,
+    moneda: Moneda,
+    locale: Locale,
+    estilo: EstiloFormato,
+) -> Result<String, ErrorMoneda> {
+    if estilo == EstiloFormato::Estandar {
+        return formatear(centavos, moneda, locale);
+    }
+
+    let absoluto = centavos.checked_abs().ok_or(ErrorMoneda::Desbordamiento)?;
+    match estilo {
+        EstiloFormato::Estandar => unreachable!("se resolvio arriba"),
+        EstiloFormato::Contable if centavos < 0 => {
+            Ok(format!("({})", formatear(absoluto, moneda, locale)?))
+        }
+        EstiloFormato::Contable => formatear(absoluto, moneda, locale),
+        EstiloFormato::ConSigno => {
+            let signo = match centavos {
+                ..0 => "-",
+                0 => "",
+                _ => "+",
+            };
+            Ok(format!("{}{}", signo, formatear(absoluto, moneda, locale)?))
+        }
+        EstiloFormato::Compacto => {
+            let signo = if centavos < 0 { "-" } else { "" };
+            Ok(format!(
+                "{}{}",
+                signo,
+                formatear_compacto(absoluto, moneda, locale)?
+            ))
+        }
+    }
+}
+
+/// Abrevia miles, millones y miles de millones con un decimal (redondeo `MitadArriba`).
+///
+/// Las cantidades menores que mil se muestran completas.
+fn formatear_compacto(
+    centavos: i64,
+    moneda: Moneda,
+    locale: Locale,
+) -> Result<String, ErrorMoneda> {
+    let [miles, millones, miles_de_millones] = locale.sufijos_compactos();
+    let escalas = [
+        (1_000_000_000, miles_de_millones),
+        (1_000_000, millones),
+        (1_000, miles),
+    ];
+    let unidades = i128::from(centavos) / 100;
+    let Some(mut indice) = escalas.iter().position(|(escala, _)| unidades >= *escala) else {
+        return formatear(centavos, moneda, locale);
+    };
+
+    let decimas_en = |escala: i128| {
+        dividir_redondeando(
+            i128::from(centavos) * 10,
+            100 * escala,
+            ModoRedondeo::MitadArriba,
+        )
+    };
+    let mut decimas = decimas_en(escalas[indice].0);
+    // 999 950 se redondea a "1000K"; se muestra como "1M".
+    if decimas >= 10_000 && indice > 0 {
+        indice -= 1;
+        decimas = decimas_en(escalas[indice].0);
+    }
+
+    let mut numero = agrupar_miles(&(decimas / 10).to_string(), locale.separador_miles());
+    if decimas % 10 != 0 {
+        numero.push(locale.separador_decimal());
+        numero.push_str(&(decimas % 10).to_string());
+    }
+    numero.push_str(escalas[indice].1);
+    Ok(con_simbolo(&numero, moneda, locale))
+}
+
+fn con_simbolo(numero: &str, moneda: Moneda, locale: Locale) -> String {
+    if locale.simbolo_antes() {
+        format!("{}{}", moneda.simbolo(), numero)
+    } else {
+        format!("{} {}", numero, moneda.simbolo())
+    }
+}
+
+fn validar_no_negativo(centavos: i64) -> Result<(), ErrorMoneda> {
+    if centavos < 0 {
+        return Err(ErrorMoneda::ValorNegativo(centavos));
+    }
+    Ok(())
+}
+
+/// Separa la cantidad en parte entera y dos decimales, como lo hacía `format!("{:.2}", valor)`.
+///
+/// Se hace con enteros: dividir entre 100.0 como `f64` pierde centavos en cantidades grandes.
+fn partes(centavos: i64) -> (String, String) {
+    (
+        format!("{}", centavos / 100),
+        format!("{:02}", centavos % 100),
+    )
+}
+
+/// Inserta el separador de miles cada tres dígitos, de derecha a izquierda.
+fn agrupar_miles(entero: &str, separador: char) -> String {
+    let mut resultado = String::with_capacity(entero.len() + entero.len() / 3);
+    for (i, digito) in entero.chars().enumerate() {
+        if i > 0 && (entero.len() - i).is_multiple_of(3) {
+            resultado.push(separador);
+        }
+        resultado.push(digito);
+    }
+    resultado
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_intentar_convertir_ok() {
+        assert_eq!(
+            intentar_convertir_cents_a_moneda(1234, "USD"),
+            Ok("$12.34".to_string())
+        );
+        assert_eq!(
+            intentar_convertir_cents_a_moneda(123456, "GBP"),
+            Ok("£1234.56".to_string())
+        );
+    }
+
+    #[test]
+    fn test_intentar_convertir_errores() {
+        assert_eq!(
+            intentar_convertir_cents_a_moneda(-100, "ABC"),
+            Err(ErrorMoneda::ValorNegativo(-100))
+        );
+        assert_eq!(
+            intentar_convertir_cents_a_moneda(200, "ABC"),
+            Err(ErrorMoneda::CodigoInvalido("ABC".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_cantidades_grandes_sin_perder_centavos() {
+        assert_eq!(
+            intentar_convertir_cents_a_moneda(7730265897051335, "USD"),
+            Ok("$77302658970513.35".to_string())
+        );
+        assert_eq!(
+            intentar_convertir_cents_a_moneda(i64::MAX, "USD"),
+            Ok("$92233720368547758.07".to_string())
+        );
+    }
+
+    #[test]
+    fn test_formatear_moneda_por_locale() {
+        assert_eq!(
+            formatear_moneda(123456, "USD", "es-MX"),
+            Ok("$1,234.56".to_string())
+        );
+        assert_eq!(
+            formatear_moneda(123456, "EUR", "es-ES"),
+            Ok("1.234,56 €".to_string())
+        );
+        assert_eq!(
+            formatear_moneda(50, "EUR", "de-DE"),
+            Ok("0,50 €".to_string())
+        );
+        assert_eq!(
+            formatear_moneda(123456789, "EUR", "fr-FR"),
+            Ok("1\u{202f}234\u{202f}567,89 €".to_string())
+        );
+    }
+
+    #[test]
+    fn test_formatear_moneda_locale_no_soportado() {
+        assert_eq!(
+            formatear_moneda(100, "USD", "xx-YY"),
+            Err(ErrorMoneda::LocaleNoSoportado("xx-YY".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_estilo_contable() {
+        let contable = |centavos, locale| {
+            formatear_moneda_con_estilo(centavos, "USD", locale, EstiloFormato::Contable)
+        };
+        assert_eq!(contable(-1234, "en-US"), Ok("($12.34)".to_string()));
+        assert_eq!(contable(1234, "en-US"), Ok("$12.34".to_string()));
+        assert_eq!(contable(-123456, "es-ES"), Ok("(1.234,56 $)".to_string()));
+    }
+
+    #[test]
+    fn test_estilo_con_signo() {
+        let con_signo = |centavos| {
+            formatear_moneda_con_estilo(centavos, "EUR", "en-GB", EstiloFormato::ConSigno)
+        };
+        assert_eq!(con_signo(1234), Ok("+€12.34".to_string()));
+        assert_eq!(con_signo(-1234), Ok("-€12.34".to_string()));
+        assert_eq!(con_signo(0), Ok("€0.00".to_string()));
+    }
+
+    #[test]
+    fn test_estilo_compacto() {
+        let compacto = |centavos, locale| {
+            formatear_moneda_con_estilo(centavos, "USD", locale, EstiloFormato::Compacto)
+        };
+        assert_eq!(compacto(1234, "en-US"), Ok("$12.34".to_string()));
+        assert_eq!(compacto(120_000, "en-US"), Ok("$1.2K".to_string()));
+        assert_eq!(compacto(100_000, "en-US"), Ok("$1K".to_string()));
+        assert_eq!(compacto(340_000_000, "en-US"), Ok("$3.4M".to_string()));
+        assert_eq!(compacto(99_995_000, "en-US"), Ok("$1M".to_string()));
+        assert_eq!(
+            compacto(-560_000_000_000, "en-US"),
+            Ok("-$5.6B".to_string())
+        );
+        assert_eq!(compacto(120_000, "es-MX"), Ok("$1.2 mil".to_string()));
+        assert_eq!(compacto(340_000_000, "es-ES"), Ok("3,4 M $".to_string()));
+        assert_eq!(
+            compacto(250_000_000_000, "de-DE"),
+            Ok("2,5 Mrd. $".to_string())
+        );
+    }
+
+    #[test]
+    fn test_estilo_estandar_sigue_validando() {
+        assert_eq!(
+            formatear_moneda_con_estilo(-1, "USD", "en-US", EstiloFormato::Estandar),
+            Err(ErrorMoneda::ValorNegativo(-1))
+        );
+        assert_eq!(
+            formatear_con_estilo(i64::MIN, Moneda::USD, Locale::EnUs, EstiloFormato::Contable),
+            Err(ErrorMoneda::Desbordamiento)
+        );
+    }
+
+    #[test]
+    fn test_agrupar_miles() {
+        assert_eq!(agrupar_miles("1", ','), "1");
+        assert_eq!(agrupar_miles("123", ','), "123");
+        assert_eq!(agrupar_miles("1234", ','), "1,234");
+        assert_eq!(agrupar_miles("1234567", ','), "1,234,567");
+    }
+}
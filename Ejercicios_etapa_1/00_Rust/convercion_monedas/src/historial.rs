@@ -0,0 +1,303 @@
+// src/historial.rs
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+use serde_json::Value;
+
+use crate::error::ErrorMoneda;
+use crate::fecha::Fecha;
+use crate::moneda::Moneda;
+use crate::tasas::{ProveedorTasas, TablaTasas, Tasa};
+
+/// Encabezado obligatorio de los archivos CSV de tasas.
+pub const ENCABEZADO_CSV: &str = "fecha,base,moneda,tasa";
+
+/// Tasas de cambio diarias, una `TablaTasas` por fecha.
+///
+/// Como proveedor usa la fecha más reciente; para tasas históricas usa `en_fecha`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct HistorialTasas {
+    tablas: BTreeMap<Fecha, TablaTasas>,
+}
+
+impl HistorialTasas {
+    pub fn new() -> HistorialTasas {
+        HistorialTasas::default()
+    }
+
+    pub fn insertar(&mut self, fecha: Fecha, tabla: TablaTasas) {
+        self.tablas.insert(fecha, tabla);
+    }
+
+    /// Tabla vigente en `fecha`: la de ese día o, si no hay (fines de semana, feriados),
+    /// la del último día anterior con tasas.
+    pub fn en_fecha(&self, fecha: Fecha) -> Result<&TablaTasas, ErrorMoneda> {
+        self.tablas
+            .range(..=fecha)
+            .next_back()
+            .map(|(_, tabla)| tabla)
+            .ok_or(ErrorMoneda::SinTasasParaFecha(fecha))
+    }
+
+    /// Fecha y tabla más recientes.
+    pub fn ultima(&self) -> Option<(Fecha, &TablaTasas)> {
+        self.tablas
+            .iter()
+            .next_back()
+            .map(|(fecha, tabla)| (*fecha, tabla))
+    }
+
+    /// Carga un historial desde un archivo `.csv` o `.json`, según su extensión.
+    pub fn cargar(ruta: impl AsRef<Path>) -> Result<HistorialTasas, ErrorMoneda> {
+        let ruta = ruta.as_ref();
+        let texto = fs::read_to_string(ruta)
+            .map_err(|error| ErrorMoneda::Io(format!("{}: {}", ruta.display(), error)))?;
+
+        match ruta.extension().and_then(|extension| extension.to_str()) {
+            Some("csv") => HistorialTasas::desde_csv(&texto),
+            Some("json") => HistorialTasas::desde_json(&texto),
+            _ => Err(ErrorMoneda::Io(format!(
+                "{}: se esperaba un archivo .csv o .json",
+                ruta.display()
+            ))),
+        }
+    }
+
+    /// Lee un CSV con el encabezado `fecha,base,moneda,tasa`.
+    ///
+    /// Cada fila indica cuántas unidades de `moneda` vale una unidad de `base` en esa fecha:
+    ///
+    /// ```text
+    /// fecha,base,moneda,tasa
+    /// 2024-01-02,USD,EUR,0.9123
+    /// 2024-01-02,USD,MXN,17.05
+    /// ```
+    pub fn desde_csv(texto: &str) -> Result<HistorialTasas, ErrorMoneda> {
+        let mut lineas = texto
+            .lines()
+            .enumerate()
+            .map(|(i, linea)| (i + 1, linea.trim()));
+        match lineas.next() {
+            Some((_, encabezado)) if encabezado.replace(' ', "") == ENCABEZADO_CSV => {}
+            _ => {
+                return Err(ErrorMoneda::ArchivoTasas {
+                    linea: Some(1),
+                    mensaje: format!("se esperaba el encabezado \"{}\"", ENCABEZADO_CSV),
+                });
+            }
+        }
+
+        let mut historial = HistorialTasas::new();
+        for (numero, linea) in lineas.filter(|(_, linea)| !linea.is_empty()) {
+            let error_en_linea = |error: ErrorMoneda| ErrorMoneda::ArchivoTasas {
+                linea: Some(numero),
+                mensaje: error.to_string(),
+            };
+
+            let columnas: Vec<&str> = linea.split(',').map(str::trim).collect();
+            let [fecha, base, moneda, tasa] = columnas[..] else {
+                return Err(ErrorMoneda::ArchivoTasas {
+                    linea: Some(numero),
+                    mensaje: format!("se esperaban 4 columnas, hay {}", columnas.len()),
+                });
+            };
+
+            let fecha = fecha.parse().map_err(error_en_linea)?;
+            let base = Moneda::desde_codigo(base).map_err(error_en_linea)?;
+            let moneda = Moneda::desde_codigo(moneda).map_err(error_en_linea)?;
+            let tasa = tasa.parse().map_err(error_en_linea)?;
+            historial
+                .registrar(fecha, base, moneda, tasa)
+                .map_err(|mensaje| ErrorMoneda::ArchivoTasas {
+                    linea: Some(numero),
+                    mensaje,
+                })?;
+        }
+        Ok(historial)
+    }
+
+    /// Lee un JSON con una base y las tasas de cada fecha:
+    ///
+    /// ```text
+    /// { "base": "USD", "tasas": { "2024-01-02": { "EUR": 0.9123, "MXN": "17.05" } } }
+    /// ```
+    pub fn desde_json(texto: &str) -> Result<HistorialTasas, ErrorMoneda> {
+        let error = |mensaje: String| ErrorMoneda::ArchivoTasas {
+            linea: None,
+            mensaje,
+        };
+        let json: Value = serde_json::from_str(texto).map_err(|e| ErrorMoneda::ArchivoTasas {
+            linea: Some(e.line()),
+            mensaje: e.to_string(),
+        })?;
+
+        let base = json
+            .get("base")
+            .and_then(Value::as_str)
+            .ok_or_else(|| error("falta el campo \"base\"".to_string()))?;
+        let base = Moneda::desde_codigo(base).map_err(|e| error(e.to_string()))?;
+        let fechas = json
+            .get("tasas")
+            .and_then(Value::as_object)
+            .ok_or_else(|| error("falta el objeto \"tasas\"".to_string()))?;
+
+        let mut historial = HistorialTasas::new();
+        for (fecha_texto, tasas) in fechas {
+            let fecha = fecha_texto
+                .parse()
+                .map_err(|e: ErrorMoneda| error(e.to_string()))?;
+            let tasas = tasas.as_object().ok_or_else(|| {
+                error(format!("las tasas de {} deben ser un objeto", fecha_texto))
+            })?;
+
+            // Registra la fecha aunque no tenga tasas: la base siempre se puede convertir a sí misma.
+            historial
+                .tablas
+                .entry(fecha)
+                .or_insert_with(|| TablaTasas::new(base));
+            for (codigo, valor) in tasas {
+                let moneda = Moneda::desde_codigo(codigo).map_err(|e| error(e.to_string()))?;
+                let tasa: Tasa = match valor {
+                    Value::Number(numero) => numero.to_string().parse(),
+                    Value::String(texto) => texto.parse(),
+                    otro => Err(ErrorMoneda::TasaInvalida(otro.to_string())),
+                }
+                .map_err(|e| error(format!("{} {}: {}", fecha_texto, codigo, e)))?;
+                historial
+                    .registrar(fecha, base, moneda, tasa)
+                    .map_err(error)?;
+            }
+        }
+        Ok(historial)
+    }
+
+    fn registrar(
+        &mut self,
+        fecha: Fecha,
+        base: Moneda,
+        moneda: Moneda,
+        tasa: Tasa,
+    ) -> Result<(), String> {
+        let tabla = self
+            .tablas
+            .entry(fecha)
+            .or_insert_with(|| TablaTasas::new(base));
+        if tabla.base() != base {
+            return Err(format!(
+                "la fecha {} ya usa {} como base, no {}",
+                fecha,
+                tabla.base(),
+                base
+            ));
+        }
+        tabla.insertar_tasa(moneda, tasa);
+        Ok(())
+    }
+}
+
+impl ProveedorTasas for HistorialTasas {
+    /// Usa las tasas de la fecha más reciente del historial.
+    fn tasa(&self, origen: Moneda, destino: Moneda) -> Result<Tasa, ErrorMoneda> {
+        match self.ultima() {
+            Some((_, tabla)) => tabla.tasa(origen, destino),
+            None => Err(ErrorMoneda::TasaNoDisponible(origen, destino)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CSV: &str = "fecha,base,moneda,tasa
+2024-01-02,USD,EUR,0.90
+2024-01-02,USD,MXN,17.00
+
+2024-01-05,USD,EUR,0.92
+2024-01-05,USD,MXN,17.25
+";
+
+    fn fecha(texto: &str) -> Fecha {
+        texto.parse().unwrap()
+    }
+
+    #[test]
+    fn test_desde_csv_y_fechas() {
+        let historial = HistorialTasas::desde_csv(CSV).unwrap();
+
+        let tabla = historial.en_fecha(fecha("2024-01-03")).unwrap();
+        assert_eq!(
+            tabla.tasa(Moneda::USD, Moneda::EUR),
+            Ok("0.90".parse().unwrap())
+        );
+        assert_eq!(
+            historial.tasa(Moneda::USD, Moneda::EUR),
+            Ok("0.92".parse().unwrap())
+        );
+        assert_eq!(
+            historial.en_fecha(fecha("2024-01-01")),
+            Err(ErrorMoneda::SinTasasParaFecha(fecha("2024-01-01")))
+        );
+    }
+
+    #[test]
+    fn test_desde_csv_reporta_linea() {
+        let csv = "fecha,base,moneda,tasa\n2024-01-02,USD,EUR,0.9\n2024-01-02,USD,XXX,1.0\n";
+        assert_eq!(
+            HistorialTasas::desde_csv(csv),
+            Err(ErrorMoneda::ArchivoTasas {
+                linea: Some(3),
+                mensaje: "codigo de moneda invalido: \"XXX\"".to_string(),
+            })
+        );
+        assert!(matches!(
+            HistorialTasas::desde_csv("2024-01-02,USD,EUR,0.9"),
+            Err(ErrorMoneda::ArchivoTasas { linea: Some(1), .. })
+        ));
+    }
+
+    #[test]
+    fn test_desde_csv_base_inconsistente() {
+        let csv = "fecha,base,moneda,tasa\n2024-01-02,USD,EUR,0.9\n2024-01-02,EUR,MXN,19\n";
+        assert!(matches!(
+            HistorialTasas::desde_csv(csv),
+            Err(ErrorMoneda::ArchivoTasas { linea: Some(3), .. })
+        ));
+    }
+
+    #[test]
+    fn test_desde_json() {
+        let json = r#"{
+            "base": "USD",
+            "tasas": {
+                "2024-01-02": { "EUR": 0.9, "MXN": "17.00" },
+                "2024-01-05": { "EUR": 0.92 }
+            }
+        }"#;
+        let historial = HistorialTasas::desde_json(json).unwrap();
+
+        let tabla = historial.en_fecha(fecha("2024-01-02")).unwrap();
+        assert_eq!(
+            tabla.tasa(Moneda::EUR, Moneda::MXN),
+            Ok(Tasa::new(170, 9).unwrap())
+        );
+        assert_eq!(
+            historial.ultima().map(|(fecha, _)| fecha),
+            Some(fecha("2024-01-05"))
+        );
+    }
+
+    #[test]
+    fn test_desde_json_invalido() {
+        assert!(matches!(
+            HistorialTasas::desde_json("{\"base\": \"USD\"}"),
+            Err(ErrorMoneda::ArchivoTasas { linea: None, .. })
+        ));
+        assert!(matches!(
+            HistorialTasas::desde_json("{\n\"base\": }"),
+            Err(ErrorMoneda::ArchivoTasas { linea: Some(2), .. })
+        ));
+    }
+}
@@ -0,0 +1,393 @@
+// src/letras.rs
+
+use crate::error::ErrorMoneda;
+use crate::moneda::Moneda;
+
+/// Mayor cantidad de unidades (sin centavos) que se puede escribir en letras: 999 999 999 999.
+pub const MAX_UNIDADES_EN_LETRAS: i64 = 999_999_999_999;
+
+/// Idiomas en los que se puede escribir una cantidad.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Idioma {
+    Espanol,
+    Ingles,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Genero {
+    Masculino,
+    Femenino,
+}
+
+/// Escribe una cantidad en letras, como se pide en las facturas.
+///
+/// # Parámetros
+/// - `centavos`: cantidad en centavos.
+/// - `codigo_moneda`: código de la moneda ("USD", "EUR", "GBP", "MXN", "CAD").
+/// - `idioma`: idioma del texto.
+///
+/// # Retorno
+/// Retorna por ejemplo "doce dólares con 34/100" o "twelve dollars and 34/100", o `Err` si
+/// la cantidad es negativa, supera `MAX_UNIDADES_EN_LETRAS` o el código no existe.
+pub fn cantidad_en_letras(
+    centavos: i64,
+    codigo_moneda: &str,
+    idioma: Idioma,
+) -> Result<String, ErrorMoneda> {
+    if centavos < 0 {
+        return Err(ErrorMoneda::ValorNegativo(centavos));
+    }
+    let moneda = Moneda::desde_codigo(codigo_moneda)?;
+    en_letras(centavos, moneda, idioma)
+}
+
+/// Versión tipada de `cantidad_en_letras`.
+pub fn en_letras(centavos: i64, moneda: Moneda, idioma: Idioma) -> Result<String, ErrorMoneda> {
+    if centavos < 0 {
+        return Err(ErrorMoneda::ValorNegativo(centavos));
+    }
+    let unidades = centavos / 100;
+    let fraccion = centavos % 100;
+    if unidades > MAX_UNIDADES_EN_LETRAS {
+        return Err(ErrorMoneda::Desbordamiento);
+    }
+
+    let (singular, plural, genero) = nombres(moneda, idioma);
+    let nombre = if unidades == 1 { singular } else { plural };
+    let texto = match idioma {
+        Idioma::Espanol => {
+            let numero = numero_es(unidades, genero);
+            // "un millón de dólares", pero "un millón cien dólares".
+            let de = if unidades >= 1_000_000 && unidades % 1_000_000 == 0 {
+                " de"
+            } else {
+                ""
+            };
+            format!("{}{} {} con {:02}/100", numero, de, nombre, fraccion)
+        }
+        Idioma::Ingles => format!("{} {} and {:02}/100", numero_en(unidades), nombre, fraccion),
+    };
+    Ok(texto)
+}
+
+/// Nombre singular y plural de la moneda, y su género gramatical en español.
+fn nombres(moneda: Moneda, idioma: Idioma) -> (&'static str, &'static str, Genero) {
+    use Genero::*;
+    match (idioma, moneda) {
+        (Idioma::Espanol, Moneda::USD) => ("dólar", "dólares", Masculino),
+        (Idioma::Espanol, Moneda::EUR) => ("euro", "euros", Masculino),
+        (Idioma::Espanol, Moneda::GBP) => ("libra esterlina", "libras esterlinas", Femenino),
+        (Idioma::Espanol, Moneda::MXN) => ("peso", "pesos", Masculino),
+        (Idioma::Espanol, Moneda::CAD) => ("dólar canadiense", "dólares canadienses", Masculino),
+        (Idioma::Ingles, Moneda::USD) => ("dollar", "dollars", Masculino),
+        (Idioma::Ingles, Moneda::EUR) => ("euro", "euros", Masculino),
+        (Idioma::Ingles, Moneda::GBP) => ("pound", "pounds", Masculino),
+        (Idioma::Ingles, Moneda::MXN) => ("Mexican peso", "Mexican pesos", Masculino),
+        (Idioma::Ingles, Moneda::CAD) => ("Canadian dollar", "Canadian dollars", Masculino),
+    }
+}
+
+const UNIDADES_ES: [&str; 30] = [
+    "cero",
+    "uno",
+    "dos",
+    "tres",
+    "cuatro",
+    "cinco",
+    "seis",
+    "siete",
+    "ocho",
+    "nueve",
+    "diez",
+    "once",
+    "doce",
+    "trece",
+    "catorce",
+    "quince",
+    "dieciséis",
+    "diecisiete",
+    "dieciocho",
+    "diecinueve",
+    "veinte",
+    "veintiuno",
+    "veintidós",
+    "veintitrés",
+    "veinticuatro",
+    "veinticinco",
+    "veintiséis",
+    "veintisiete",
+    "veintiocho",
+    "veintinueve",
+];
+
+const DECENAS_ES: [&str; 10] = [
+    "",
+    "",
+    "",
+    "treinta",
+    "cuarenta",
+    "cincuenta",
+    "sesenta",
+    "setenta",
+    "ochenta",
+    "noventa",
+];
+
+/// Escribe `n` (menor que 10^12) en español, concordando con un sustantivo del género dado.
+///
+/// "uno" se apocopa ("un", "veintiún") o se feminiza ("una", "veintiuna") porque siempre le
+/// sigue el nombre de la moneda, "mil" o "millones".
+fn numero_es(n: i64, genero: Genero) -> String {
+    if n == 0 {
+        return UNIDADES_ES[0].to_string();
+    }
+
+    let millones = n / 1_000_000;
+    let resto = n % 1_000_000;
+    let mut partes = Vec::new();
+    match millones {
+        0 => {}
+        1 => partes.push("un millón".to_string()),
+        // "millón" es masculino aunque la moneda no lo sea: "doscientos millones de libras".
+        _ => partes.push(format!(
+            "{} millones",
+            menor_que_millon_es(millones, Genero::Masculino)
+        )),
+    }
+    if resto > 0 {
+        partes.push(menor_que_millon_es(resto, genero));
+    }
+    partes.join(" ")
+}
+
+fn menor_que_millon_es(n: i64, genero: Genero) -> String {
+    let miles = n / 1000;
+    let resto = n % 1000;
+    let mut partes = Vec::new();
+    match miles {
+        0 => {}
+        1 => partes.push("mil".to_string()),
+        _ => partes.push(format!("{} mil", menor_que_mil_es(miles, genero))),
+    }
+    if resto > 0 {
+        partes.push(menor_que_mil_es(resto, genero));
+    }
+    partes.join(" ")
+}
+
+fn menor_que_mil_es(n: i64, genero: Genero) -> String {
+    if n == 100 {
+        return "cien".to_string();
+    }
+
+    let centenas = n / 100;
+    let resto = n % 100;
+    let raiz = match centenas {
+        0 => "",
+        1 => "ciento",
+        2 => "doscient",
+        3 => "trescient",
+        4 => "cuatrocient",
+        5 => "quinient",
+        6 => "seiscient",
+        7 => "setecient",
+        8 => "ochocient",
+        _ => "novecient",
+    };
+    let terminacion = match (centenas, genero) {
+        (0 | 1, _) => "",
+        (_, Genero::Masculino) => "os",
+        (_, Genero::Femenino) => "as",
+    };
+
+    let mut partes = Vec::new();
+    if centenas > 0 {
+        partes.push(format!("{}{}", raiz, terminacion));
+    }
+    if resto > 0 {
+        partes.push(menor_que_cien_es(resto, genero));
+    }
+    partes.join(" ")
+}
+
+fn menor_que_cien_es(n: i64, genero: Genero) -> String {
+    let uno = match genero {
+        Genero::Masculino => "un",
+        Genero::Femenino => "una",
+    };
+    match n {
+        1 => uno.to_string(),
+        21 => match genero {
+            Genero::Masculino => "veintiún".to_string(),
+            Genero::Femenino => "veintiuna".to_string(),
+        },
+        0..30 => UNIDADES_ES[n as usize].to_string(),
+        _ if n % 10 == 0 => DECENAS_ES[(n / 10) as usize].to_string(),
+        _ if n % 10 == 1 => format!("{} y {}", DECENAS_ES[(n / 10) as usize], uno),
+        _ => format!(
+            "{} y {}",
+            DECENAS_ES[(n / 10) as usize],
+            UNIDADES_ES[(n % 10) as usize]
+        ),
+    }
+}
+
+const UNIDADES_EN: [&str; 20] = [
+    "zero",
+    "one",
+    "two",
+    "three",
+    "four",
+    "five",
+    "six",
+    "seven",
+    "eight",
+    "nine",
+    "ten",
+    "eleven",
+    "twelve",
+    "thirteen",
+    "fourteen",
+    "fifteen",
+    "sixteen",
+    "seventeen",
+    "eighteen",
+    "nineteen",
+];
+
+const DECENAS_EN: [&str; 10] = [
+    "", "", "twenty", "thirty", "forty", "fifty", "sixty", "seventy", "eighty", "ninety",
+];
+
+/// Escribe `n` (menor que 10^12) en inglés, con la escala corta ("billion" = 10^9).
+fn numero_en(n: i64) -> String {
+    if n == 0 {
+        return UNIDADES_EN[0].to_string();
+    }
+
+    let escalas = [
+        (1_000_000_000, "billion"),
+        (1_000_000, "million"),
+        (1000, "thousand"),
+    ];
+    let mut resto = n;
+    let mut partes = Vec::new();
+    for (valor, nombre) in escalas {
+        if resto >= valor {
+            partes.push(format!("{} {}", menor_que_mil_en(resto / valor), nombre));
+            resto %= valor;
+        }
+    }
+    if resto > 0 {
+        partes.push(menor_que_mil_en(resto));
+    }
+    partes.join(" ")
+}
+
+fn menor_que_mil_en(n: i64) -> String {
+    let centenas = n / 100;
+    let resto = n % 100;
+    let mut partes = Vec::new();
+    if centenas > 0 {
+        partes.push(format!("{} hundred", UNIDADES_EN[centenas as usize]));
+    }
+    match resto {
+        0 => {}
+        1..20 => partes.push(UNIDADES_EN[resto as usize].to_string()),
+        _ if resto % 10 == 0 => partes.push(DECENAS_EN[(resto / 10) as usize].to_string()),
+        _ => partes.push(format!(
+            "{}-{}",
+            DECENAS_EN[(resto / 10) as usize],
+            UNIDADES_EN[(resto % 10) as usize]
+        )),
+    }
+    partes.join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn es(centavos: i64, codigo: &str) -> String {
+        cantidad_en_letras(centavos, codigo, Idioma::Espanol).unwrap()
+    }
+
+    fn en(centavos: i64, codigo: &str) -> String {
+        cantidad_en_letras(centavos, codigo, Idioma::Ingles).unwrap()
+    }
+
+    #[test]
+    fn test_espanol() {
+        assert_eq!(es(1234, "USD"), "doce dólares con 34/100");
+        assert_eq!(es(0, "MXN"), "cero pesos con 00/100");
+        assert_eq!(es(100, "EUR"), "un euro con 00/100");
+        assert_eq!(es(2150, "MXN"), "veintiún pesos con 50/100");
+        assert_eq!(es(10_100, "USD"), "ciento un dólares con 00/100");
+        assert_eq!(es(100_000, "USD"), "mil dólares con 00/100");
+        assert_eq!(
+            es(199_999, "EUR"),
+            "mil novecientos noventa y nueve euros con 99/100"
+        );
+    }
+
+    #[test]
+    fn test_espanol_genero_femenino() {
+        assert_eq!(es(100, "GBP"), "una libra esterlina con 00/100");
+        assert_eq!(es(2100, "GBP"), "veintiuna libras esterlinas con 00/100");
+        assert_eq!(
+            es(20_000_000, "GBP"),
+            "doscientas mil libras esterlinas con 00/100"
+        );
+        assert_eq!(
+            es(20_000_000_000, "GBP"),
+            "doscientos millones de libras esterlinas con 00/100"
+        );
+    }
+
+    #[test]
+    fn test_espanol_millones() {
+        assert_eq!(es(100_000_000, "MXN"), "un millón de pesos con 00/100");
+        assert_eq!(es(100_010_000, "MXN"), "un millón cien pesos con 00/100");
+        assert_eq!(
+            es(210_000_000_000, "USD"),
+            "dos mil cien millones de dólares con 00/100"
+        );
+        assert_eq!(
+            es(MAX_UNIDADES_EN_LETRAS * 100 + 99, "USD"),
+            "novecientos noventa y nueve mil novecientos noventa y nueve millones \
+             novecientos noventa y nueve mil novecientos noventa y nueve dólares con 99/100"
+        );
+    }
+
+    #[test]
+    fn test_ingles() {
+        assert_eq!(en(1234, "USD"), "twelve dollars and 34/100");
+        assert_eq!(en(0, "EUR"), "zero euros and 00/100");
+        assert_eq!(en(100, "GBP"), "one pound and 00/100");
+        assert_eq!(
+            en(42_000, "CAD"),
+            "four hundred twenty Canadian dollars and 00/100"
+        );
+        assert_eq!(
+            en(123_456_789_123, "USD"),
+            "one billion two hundred thirty-four million five hundred sixty-seven thousand \
+             eight hundred ninety-one dollars and 23/100"
+        );
+    }
+
+    #[test]
+    fn test_errores() {
+        assert_eq!(
+            cantidad_en_letras(-1, "USD", Idioma::Espanol),
+            Err(ErrorMoneda::ValorNegativo(-1))
+        );
+        assert_eq!(
+            cantidad_en_letras(100, "ABC", Idioma::Ingles),
+            Err(ErrorMoneda::CodigoInvalido("ABC".to_string()))
+        );
+        assert_eq!(
+            cantidad_en_letras((MAX_UNIDADES_EN_LETRAS + 1) * 100, "USD", Idioma::Ingles),
+            Err(ErrorMoneda::Desbordamiento)
+        );
+    }
+}
@@ -0,0 +1,124 @@
+// lib.rs
+// EJERCICIO 5: CONVERSIÓN DE CENTAVOS A FORMATO MONETARIO
+//
+// La función original `convertir_cents_a_moneda` devuelve los errores como texto dentro del
+// mismo `String` que el resultado. La librería expone además una API basada en `Result`:
+//
+//   intentar_convertir_cents_a_moneda(1234, "USD")  => Ok("$12.34")
+//   intentar_convertir_cents_a_moneda(200, "ABC")   => Err(ErrorMoneda::CodigoInvalido("ABC"))
+//   formatear_moneda(123456, "EUR", "es-ES")         => Ok("1.234,56 €")
+//
+// Para convertir entre monedas se usa `convertir` con un `ProveedorTasas`:
+//
+//   let tabla = TablaTasas::new(Moneda::USD).con_tasa(Moneda::MXN, "17.05".parse()?);
+//   convertir(Dinero::new(1000, Moneda::USD), Moneda::MXN, &tabla)  => Ok(Dinero { 17050, MXN })
+//
+// Y para leer de vuelta un texto formateado, `parsear_moneda`:
+//
+//   parsear_moneda("GBP 1,234.00")  => Ok(Dinero { 123400, GBP })
+//
+// Los porcentajes y proporciones redondean con un `ModoRedondeo` explícito:
+//
+//   Dinero::new(125, Moneda::USD).porcentaje("10".parse()?, ModoRedondeo::MitadPar)  => 12 centavos
+//
+// Las estadísticas de una lista de precios se calculan en centavos enteros:
+//
+//   Estadisticas::calcular(&precios)?.mediana()  => Dinero { .., USD }
+
+mod billetera;
+mod calculo;
+mod conversion;
+mod dinero;
+mod error;
+mod estadisticas;
+mod fecha;
+mod formato;
+mod historial;
+mod letras;
+mod locale;
+mod moneda;
+mod parseo;
+mod porcentaje;
+mod redondeo;
+#[cfg(feature = "serde")]
+pub mod serde_dinero;
+mod tasas;
+
+pub use billetera::{
+    Billetera, CUENTA_BILLETERA, CUENTA_CAMBIO, CUENTA_EXTERNA, Movimiento, Transaccion,
+};
+pub use calculo::{
+    Calculadora, Descuento, DescuentoAplicado, Desglose, Impuesto, ImpuestoAplicado, Linea,
+    ModoPrecio, TipoDescuento,
+};
+pub use conversion::{convertir, convertir_con_redondeo};
+pub use dinero::Dinero;
+pub use error::ErrorMoneda;
+pub use estadisticas::{Cubeta, Estadisticas};
+pub use fecha::{Fecha, FechaHora};
+pub use formato::{
+    EstiloFormato, formatear, formatear_con_estilo, formatear_moneda, formatear_moneda_con_estilo,
+    intentar_convertir_cents_a_moneda,
+};
+pub use historial::{ENCABEZADO_CSV, HistorialTasas};
+pub use letras::{Idioma, MAX_UNIDADES_EN_LETRAS, cantidad_en_letras, en_letras};
+pub use locale::Locale;
+pub use moneda::Moneda;
+pub use parseo::{parsear_centavos, parsear_moneda, parsear_moneda_con_locale};
+pub use porcentaje::Porcentaje;
+pub use redondeo::{ModoRedondeo, dividir_redondeando};
+pub use tasas::{ProveedorTasas, TablaTasas, Tasa};
+
+/// Convierte una cantidad en centavos a un formato monetario.
+///
+/// # Parámetros
+/// - `centavos`: número entero que representa la cantidad en centavos.
+/// - `codigo_moneda`: string que representa el código de la moneda ("USD", "EUR", "GBP").
+///
+/// # Retorno
+/// Retorna un `String` con el valor formateado en la moneda correspondiente o un mensaje de error si los parámetros no son válidos.
+/// Para distinguir los errores sin comparar strings usa `intentar_convertir_cents_a_moneda`.
+pub fn convertir_cents_a_moneda(centavos: i32, codigo_moneda: &str) -> String {
+    match intentar_convertir_cents_a_moneda(i64::from(centavos), codigo_moneda) {
+        Ok(valor) => valor,
+        // `intentar_convertir_cents_a_moneda` solo falla con errores que ya existían.
+        Err(error) => error
+            .mensaje_legado()
+            .map_or_else(|| error.to_string(), String::from),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_valor_invalido() {
+        let resultado = convertir_cents_a_moneda(-100, "USD");
+        assert_eq!(resultado, "Valor invalido");
+    }
+
+    #[test]
+    fn test_codigo_moneda_invalido() {
+        let resultado = convertir_cents_a_moneda(200, "ABC");
+        assert_eq!(resultado, "Codigo de moneda invalido");
+    }
+
+    #[test]
+    fn test_conversion_usd() {
+        let resultado = convertir_cents_a_moneda(1234, "USD");
+        assert_eq!(resultado, "$12.34");
+    }
+
+    #[test]
+    fn test_conversion_eur() {
+        let resultado = convertir_cents_a_moneda(50, "EUR");
+        assert_eq!(resultado, "€0.50");
+    }
+
+    #[test]
+    fn test_conversion_otro_ejemplo() {
+        let resultado = convertir_cents_a_moneda(2000, "USD");
+        assert_eq!(resultado, "$20.00");
+    }
+}
@@ -0,0 +1,117 @@
+// src/locale.rs
+
+use std::str::FromStr;
+
+use crate::error::ErrorMoneda;
+
+/// Locales soportados para formatear cantidades.
+///
+/// Cada locale define el separador decimal, el separador de miles y la posición del símbolo.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Locale {
+    EnUs,
+    EnGb,
+    EsMx,
+    EsEs,
+    FrFr,
+    DeDe,
+}
+
+impl Locale {
+    /// Todos los locales soportados.
+    pub const TODOS: [Locale; 6] = [
+        Locale::EnUs,
+        Locale::EnGb,
+        Locale::EsMx,
+        Locale::EsEs,
+        Locale::FrFr,
+        Locale::DeDe,
+    ];
+
+    /// Busca un locale a partir de su etiqueta BCP 47 ("es-MX", "en_US", ...).
+    pub fn desde_etiqueta(etiqueta: &str) -> Result<Locale, ErrorMoneda> {
+        match etiqueta.replace('_', "-").to_ascii_lowercase().as_str() {
+            "en-us" | "en" => Ok(Locale::EnUs),
+            "en-gb" => Ok(Locale::EnGb),
+            "es-mx" => Ok(Locale::EsMx),
+            "es-es" | "es" => Ok(Locale::EsEs),
+            "fr-fr" | "fr" => Ok(Locale::FrFr),
+            "de-de" | "de" => Ok(Locale::DeDe),
+            _ => Err(ErrorMoneda::LocaleNoSoportado(etiqueta.to_string())),
+        }
+    }
+
+    /// Etiqueta BCP 47 del locale.
+    pub fn etiqueta(&self) -> &'static str {
+        match self {
+            Locale::EnUs => "en-US",
+            Locale::EnGb => "en-GB",
+            Locale::EsMx => "es-MX",
+            Locale::EsEs => "es-ES",
+            Locale::FrFr => "fr-FR",
+            Locale::DeDe => "de-DE",
+        }
+    }
+
+    /// Separador entre la parte entera y los decimales.
+    pub fn separador_decimal(&self) -> char {
+        match self {
+            Locale::EnUs | Locale::EnGb | Locale::EsMx => '.',
+            Locale::EsEs | Locale::FrFr | Locale::DeDe => ',',
+        }
+    }
+
+    /// Separador de grupos de miles.
+    pub fn separador_miles(&self) -> char {
+        match self {
+            Locale::EnUs | Locale::EnGb | Locale::EsMx => ',',
+            Locale::EsEs | Locale::DeDe => '.',
+            Locale::FrFr => '\u{202f}',
+        }
+    }
+
+    /// Sufijos del formato compacto para miles, millones y miles de millones.
+    pub fn sufijos_compactos(&self) -> [&'static str; 3] {
+        match self {
+            Locale::EnUs | Locale::EnGb => ["K", "M", "B"],
+            Locale::EsMx | Locale::EsEs => [" mil", " M", " mil M"],
+            Locale::FrFr => [" k", " M", " Md"],
+            Locale::DeDe => [" Tsd.", " Mio.", " Mrd."],
+        }
+    }
+
+    /// Indica si el símbolo va antes del número ("$12.34") o después ("12,34 €").
+    pub fn simbolo_antes(&self) -> bool {
+        matches!(self, Locale::EnUs | Locale::EnGb | Locale::EsMx)
+    }
+}
+
+impl FromStr for Locale {
+    type Err = ErrorMoneda;
+
+    fn from_str(etiqueta: &str) -> Result<Self, Self::Err> {
+        Locale::desde_etiqueta(etiqueta)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_desde_etiqueta() {
+        assert_eq!(Locale::desde_etiqueta("es-MX"), Ok(Locale::EsMx));
+        assert_eq!(Locale::desde_etiqueta("de_DE"), Ok(Locale::DeDe));
+        assert_eq!(
+            Locale::desde_etiqueta("xx-YY"),
+            Err(ErrorMoneda::LocaleNoSoportado("xx-YY".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_etiqueta_ida_y_vuelta() {
+        for locale in Locale::TODOS {
+            assert_eq!(locale.etiqueta().parse::<Locale>(), Ok(locale));
+        }
+    }
+}
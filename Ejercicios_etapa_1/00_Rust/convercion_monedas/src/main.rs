@@ -18,6 +18,18 @@
 //   convertir_cents_a_moneda(50, "EUR")  => "€0.50"
 //   convertir_cents_a_moneda(-100, "GBP") => "Valor invalido"
 //   convertir_cents_a_moneda(200, "ABC")  => "Codigo de moneda invalido"
+//
+// La librería (lib.rs) expone además una API basada en `Result` (`intentar_convertir_cents_a_moneda`,
+// `formatear_moneda`, ...). Este binario es una herramienta de línea de comandos que la usa:
+//
+//   convercion_monedas format 1234 USD --locale es-MX
+//   convercion_monedas convert 1000 EUR MXN --rates tasas.csv
+//   convercion_monedas parse "€0,50"
+
+mod cli;
+
+use std::io;
+use std::process;
 
 /// Convierte una cantidad en centavos a un formato monetario.
 /// 
@@ -43,14 +55,27 @@ fn convertir_cents_a_moneda(centavos: i32, codigo_moneda: &str) -> String {
 }
 
 fn main() {
-    // Ejemplos de uso:
-    let conversion = convertir_cents_a_moneda(-4000, "GBP");
-    let conversion_dos = convertir_cents_a_moneda(1000, "EURR");
-    let conversion_tres = convertir_cents_a_moneda(2000, "USD");
-
-    println!("{}", conversion);        // Debe imprimir "Valor invalido"
-    println!("{}", conversion_dos);     // Debe imprimir "Codigo de moneda invalido"
-    println!("{}", conversion_tres);    // Debe imprimir "$20.00"
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args.iter().any(|arg| arg == "--help" || arg == "-h") {
+        println!("{}", cli::USO);
+        return;
+    }
+
+    let opciones = match cli::parsear_argumentos(&args) {
+        Ok(opciones) => opciones,
+        Err(error) => {
+            eprintln!("Error: {}\n\n{}", error, cli::USO);
+            process::exit(cli::SALIDA_USO);
+        }
+    };
+
+    let codigo = cli::ejecutar(
+        &opciones,
+        &mut io::stdin().lock(),
+        &mut io::stdout().lock(),
+        &mut io::stderr().lock(),
+    );
+    process::exit(codigo);
 }
 
 #[cfg(test)]
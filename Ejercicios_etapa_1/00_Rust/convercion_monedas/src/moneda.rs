@@ -0,0 +1,100 @@
+// src/moneda.rs
+
+use std::fmt;
+use std::str::FromStr;
+
+use crate::error::ErrorMoneda;
+
+/// Monedas soportadas por la librería.
+///
+/// Es la tabla que antes vivía en el `match` de `convertir_cents_a_moneda`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Moneda {
+    USD,
+    EUR,
+    GBP,
+    MXN,
+    CAD,
+}
+
+impl Moneda {
+    /// Todas las monedas de la tabla, en orden.
+    pub const TODAS: [Moneda; 5] = [
+        Moneda::USD,
+        Moneda::EUR,
+        Moneda::GBP,
+        Moneda::MXN,
+        Moneda::CAD,
+    ];
+
+    /// Busca una moneda a partir de su código ISO 4217 ("USD", "EUR", "GBP", "MXN", "CAD").
+    pub fn desde_codigo(codigo: &str) -> Result<Moneda, ErrorMoneda> {
+        match codigo {
+            "USD" => Ok(Moneda::USD),
+            "EUR" => Ok(Moneda::EUR),
+            "GBP" => Ok(Moneda::GBP),
+            "MXN" => Ok(Moneda::MXN),
+            "CAD" => Ok(Moneda::CAD),
+            _ => Err(ErrorMoneda::CodigoInvalido(codigo.to_string())),
+        }
+    }
+
+    /// Código ISO 4217 de la moneda.
+    pub fn codigo(&self) -> &'static str {
+        match self {
+            Moneda::USD => "USD",
+            Moneda::EUR => "EUR",
+            Moneda::GBP => "GBP",
+            Moneda::MXN => "MXN",
+            Moneda::CAD => "CAD",
+        }
+    }
+
+    /// Símbolo que se antepone (o pospone, según el locale) al valor formateado.
+    ///
+    /// El peso mexicano y el dólar canadiense usan el prefijo del país para no confundirse con "$".
+    pub fn simbolo(&self) -> &'static str {
+        match self {
+            Moneda::USD => "$",
+            Moneda::EUR => "€",
+            Moneda::GBP => "£",
+            Moneda::MXN => "MX$",
+            Moneda::CAD => "CA$",
+        }
+    }
+}
+
+impl FromStr for Moneda {
+    type Err = ErrorMoneda;
+
+    fn from_str(codigo: &str) -> Result<Self, Self::Err> {
+        Moneda::desde_codigo(codigo)
+    }
+}
+
+impl fmt::Display for Moneda {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.codigo())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_desde_codigo() {
+        assert_eq!(Moneda::desde_codigo("EUR"), Ok(Moneda::EUR));
+        assert_eq!(
+            Moneda::desde_codigo("EURR"),
+            Err(ErrorMoneda::CodigoInvalido("EURR".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_codigo_ida_y_vuelta() {
+        for moneda in Moneda::TODAS {
+            assert_eq!(moneda.codigo().parse::<Moneda>(), Ok(moneda));
+        }
+    }
+}
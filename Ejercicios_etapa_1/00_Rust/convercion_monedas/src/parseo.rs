@@ -0,0 +1,301 @@
+// src/parseo.rs
+
+use crate::dinero::Dinero;
+use crate::error::ErrorMoneda;
+use crate::locale::Locale;
+use crate::moneda::Moneda;
+
+/// Caracteres que se aceptan como separador de miles además de '.' y ','.
+const ESPACIOS_DE_MILES: [char; 3] = [' ', '\u{a0}', '\u{202f}'];
+
+/// Interpreta un texto con símbolo o código de moneda y lo convierte a `Dinero`.
+///
+/// Es la operación inversa de `convertir_cents_a_moneda` y `formatear_moneda`: acepta
+/// "$12.34", "€0,50", "GBP 1,234.00" o "1.234,56 €". El separador decimal se deduce del texto;
+/// si no se puede deducir (por ejemplo "$1,234") se retorna `ErrorMoneda::FormatoInvalido`.
+/// Para resolver esos casos con las convenciones de un locale usa `parsear_moneda_con_locale`.
+pub fn parsear_moneda(texto: &str) -> Result<Dinero, ErrorMoneda> {
+    parsear(texto, None)
+}
+
+/// Igual que `parsear_moneda`, pero usa el separador decimal del locale.
+pub fn parsear_moneda_con_locale(texto: &str, locale: Locale) -> Result<Dinero, ErrorMoneda> {
+    parsear(texto, Some(locale.separador_decimal()))
+}
+
+/// Interpreta una cantidad sin moneda ("1,234.56", "0,50") y la retorna en centavos.
+pub fn parsear_centavos(texto: &str) -> Result<i64, ErrorMoneda> {
+    parsear_numero(texto, texto.trim(), None)
+}
+
+fn parsear(texto: &str, separador_decimal: Option<char>) -> Result<Dinero, ErrorMoneda> {
+    let (moneda, cantidad) = separar_moneda(texto)?;
+    let centavos = parsear_numero(texto, &cantidad, separador_decimal)?;
+    Ok(Dinero::new(centavos, moneda))
+}
+
+fn formato_invalido(texto: &str, motivo: impl Into<String>) -> ErrorMoneda {
+    ErrorMoneda::FormatoInvalido {
+        texto: texto.to_string(),
+        motivo: motivo.into(),
+    }
+}
+
+/// Tokens que identifican cada moneda, del más largo al más corto para que "MX$" gane a "$".
+fn tokens_de_moneda() -> Vec<(&'static str, Moneda)> {
+    let mut tokens: Vec<(&'static str, Moneda)> = Moneda::TODAS
+        .iter()
+        .flat_map(|moneda| [(moneda.codigo(), *moneda), (moneda.simbolo(), *moneda)])
+        .collect();
+    tokens.sort_by_key(|(token, _)| std::cmp::Reverse(token.chars().count()));
+    tokens
+}
+
+/// Separa el símbolo o código de moneda (al inicio o al final) del resto del texto.
+///
+/// Un signo '-' antes de la moneda ("-$12.34") se conserva en la cantidad.
+fn separar_moneda(texto: &str) -> Result<(Moneda, String), ErrorMoneda> {
+    let limpio = texto.trim();
+    let (signo, limpio) = match limpio.strip_prefix('-') {
+        Some(resto) => ("-", resto.trim_start()),
+        None => ("", limpio),
+    };
+
+    let tokens = tokens_de_moneda();
+    let prefijo = tokens
+        .iter()
+        .find_map(|(token, moneda)| quitar_prefijo(limpio, token).map(|resto| (*moneda, resto)));
+    let sin_prefijo = prefijo.map_or(limpio, |(_, resto)| resto);
+    let sufijo = tokens.iter().find_map(|(token, moneda)| {
+        quitar_sufijo(sin_prefijo, token).map(|resto| (*moneda, resto))
+    });
+
+    let (moneda, cantidad) = match (prefijo, sufijo) {
+        (Some((antes, _)), Some((despues, _))) if antes != despues => {
+            return Err(formato_invalido(
+                texto,
+                format!("indica dos monedas distintas ({} y {})", antes, despues),
+            ));
+        }
+        (_, Some((moneda, resto))) => (moneda, resto),
+        (Some((moneda, resto)), None) => (moneda, resto),
+        (None, None) => {
+            return Err(formato_invalido(
+                texto,
+                "no tiene un simbolo ni un codigo de moneda conocido",
+            ));
+        }
+    };
+
+    Ok((moneda, format!("{}{}", signo, cantidad.trim())))
+}
+
+fn quitar_prefijo<'a>(texto: &'a str, token: &str) -> Option<&'a str> {
+    let inicio = texto.get(..token.len())?;
+    inicio
+        .eq_ignore_ascii_case(token)
+        .then(|| &texto[token.len()..])
+}
+
+fn quitar_sufijo<'a>(texto: &'a str, token: &str) -> Option<&'a str> {
+    let corte = texto.len().checked_sub(token.len())?;
+    let fin = texto.get(corte..)?;
+    fin.eq_ignore_ascii_case(token).then(|| &texto[..corte])
+}
+
+/// Convierte la parte numérica a centavos.
+///
+/// `original` es el texto completo, solo para los mensajes de error.
+fn parsear_numero(
+    original: &str,
+    cantidad: &str,
+    separador_decimal: Option<char>,
+) -> Result<i64, ErrorMoneda> {
+    let error = |motivo: &str| formato_invalido(original, motivo);
+
+    let (negativo, cantidad) = match cantidad.strip_prefix('-') {
+        Some(resto) => (true, resto.trim_start()),
+        None => (false, cantidad),
+    };
+    if !cantidad.starts_with(|c: char| c.is_ascii_digit()) {
+        return Err(error("la cantidad debe empezar con un digito"));
+    }
+    if let Some(c) = cantidad
+        .chars()
+        .find(|c| !c.is_ascii_digit() && *c != '.' && *c != ',' && !ESPACIOS_DE_MILES.contains(c))
+    {
+        return Err(error(&format!("caracter inesperado '{}'", c)));
+    }
+
+    let separador_decimal = match separador_decimal {
+        Some(separador) => Some(separador),
+        None => deducir_separador_decimal(cantidad).map_err(|motivo| error(&motivo))?,
+    };
+
+    let (entero, decimales) = match separador_decimal.and_then(|s| cantidad.rsplit_once(s)) {
+        Some((entero, decimales)) => (entero, decimales),
+        None => (cantidad, ""),
+    };
+    if separador_decimal.is_some_and(|s| entero.contains(s)) {
+        return Err(error("el separador decimal aparece mas de una vez"));
+    }
+    let decimales = match decimales.len() {
+        0 if cantidad.len() > entero.len() => return Err(error("faltan los decimales")),
+        0 => "00".to_string(),
+        1 => format!("{}0", decimales),
+        2 => decimales.to_string(),
+        _ => return Err(error("tiene mas de dos decimales")),
+    };
+    if !decimales.chars().all(|c| c.is_ascii_digit()) {
+        return Err(error("los decimales solo pueden ser digitos"));
+    }
+
+    let entero = quitar_separadores_de_miles(entero).map_err(|motivo| error(&motivo))?;
+    let centavos = format!("{}{}", entero, decimales)
+        .parse::<i64>()
+        .map_err(|_| ErrorMoneda::Desbordamiento)?;
+
+    Ok(if negativo { -centavos } else { centavos })
+}
+
+/// Deduce qué carácter es el separador decimal cuando no se conoce el locale.
+///
+/// - Si aparecen '.' y ',', el último es el decimal ("1,234.56", "1.234,56").
+/// - Si uno solo aparece varias veces, es de miles ("1,234,567").
+/// - Si aparece una vez seguido de 1 o 2 dígitos, es decimal ("12.34", "0,5").
+/// - Si aparece una vez seguido de 3 dígitos ("1,234") es ambiguo.
+fn deducir_separador_decimal(cantidad: &str) -> Result<Option<char>, String> {
+    let ultimo_punto = cantidad.rfind('.');
+    let ultima_coma = cantidad.rfind(',');
+
+    let candidato = match (ultimo_punto, ultima_coma) {
+        (None, None) => return Ok(None),
+        (Some(punto), Some(coma)) => return Ok(Some(if punto > coma { '.' } else { ',' })),
+        (Some(_), None) => '.',
+        (None, Some(_)) => ',',
+    };
+
+    if cantidad.matches(candidato).count() > 1 {
+        return Ok(None);
+    }
+    let (_, despues) = cantidad.rsplit_once(candidato).unwrap_or_default();
+    if despues.len() == 3 {
+        return Err(format!(
+            "es ambiguo: '{}' seguido de tres digitos puede ser separador decimal o de miles",
+            candidato
+        ));
+    }
+    Ok(Some(candidato))
+}
+
+/// Quita los separadores de miles validando que agrupen de tres en tres.
+fn quitar_separadores_de_miles(entero: &str) -> Result<String, String> {
+    let es_separador = |c: char| !c.is_ascii_digit();
+    let separadores: Vec<char> = entero.chars().filter(|c| es_separador(*c)).collect();
+    if separadores.is_empty() {
+        return Ok(entero.to_string());
+    }
+    if separadores.iter().any(|c| *c != separadores[0]) {
+        return Err("mezcla distintos separadores de miles".to_string());
+    }
+
+    let grupos: Vec<&str> = entero.split(es_separador).collect();
+    let primero_valido = (1..=3).contains(&grupos[0].len());
+    if !primero_valido || grupos[1..].iter().any(|grupo| grupo.len() != 3) {
+        return Err("los separadores de miles deben agrupar de tres en tres digitos".to_string());
+    }
+    Ok(grupos.concat())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{convertir_cents_a_moneda, formatear};
+    use proptest::prelude::*;
+
+    fn dinero(centavos: i64, moneda: Moneda) -> Result<Dinero, ErrorMoneda> {
+        Ok(Dinero::new(centavos, moneda))
+    }
+
+    #[test]
+    fn test_ejemplos_de_hojas_de_calculo() {
+        assert_eq!(parsear_moneda("$12.34"), dinero(1234, Moneda::USD));
+        assert_eq!(parsear_moneda("€0,50"), dinero(50, Moneda::EUR));
+        assert_eq!(parsear_moneda("GBP 1,234.00"), dinero(123400, Moneda::GBP));
+        assert_eq!(parsear_moneda("1.234,56 €"), dinero(123456, Moneda::EUR));
+        assert_eq!(parsear_moneda("MX$ 1,000.5"), dinero(100050, Moneda::MXN));
+        assert_eq!(parsear_moneda("12 usd"), dinero(1200, Moneda::USD));
+        assert_eq!(parsear_moneda("-$3.10"), dinero(-310, Moneda::USD));
+    }
+
+    #[test]
+    fn test_entrada_ambigua() {
+        let error = parsear_moneda("$1,234").unwrap_err();
+        assert!(
+            matches!(&error, ErrorMoneda::FormatoInvalido { motivo, .. } if motivo.contains("ambiguo"))
+        );
+        assert_eq!(
+            parsear_moneda_con_locale("$1,234", Locale::EsMx),
+            dinero(123400, Moneda::USD)
+        );
+        assert_eq!(
+            parsear_moneda_con_locale("1.234 €", Locale::EsEs),
+            dinero(123400, Moneda::EUR)
+        );
+    }
+
+    #[test]
+    fn test_entradas_invalidas() {
+        for texto in [
+            "12.34",
+            "$",
+            "$12.345",
+            "$1,23,456.00",
+            "$1.234.56",
+            "$12.",
+            "$1,234 567.00",
+            "$12.34 EUR",
+            "$12a",
+        ] {
+            assert!(
+                matches!(
+                    parsear_moneda(texto),
+                    Err(ErrorMoneda::FormatoInvalido { .. })
+                ),
+                "{} deberia ser invalido",
+                texto
+            );
+        }
+        assert_eq!(
+            parsear_moneda("$99999999999999999999"),
+            Err(ErrorMoneda::Desbordamiento)
+        );
+    }
+
+    #[test]
+    fn test_parsear_centavos() {
+        assert_eq!(parsear_centavos("1,234,567"), Ok(123456700));
+        assert_eq!(parsear_centavos("0.5"), Ok(50));
+    }
+
+    proptest! {
+        #[test]
+        fn prop_ida_y_vuelta_legado(centavos in 0..i32::MAX, indice in 0..3usize) {
+            let codigo = ["USD", "EUR", "GBP"][indice];
+            let texto = convertir_cents_a_moneda(centavos, codigo);
+            let esperado = Dinero::new(i64::from(centavos), Moneda::desde_codigo(codigo).unwrap());
+            prop_assert_eq!(parsear_moneda(&texto), Ok(esperado));
+        }
+
+        #[test]
+        fn prop_ida_y_vuelta_por_locale(
+            centavos in 0..i64::MAX,
+            moneda in prop::sample::select(Moneda::TODAS.to_vec()),
+            locale in prop::sample::select(Locale::TODOS.to_vec()),
+        ) {
+            let texto = formatear(centavos, moneda, locale).unwrap();
+            prop_assert_eq!(parsear_moneda(&texto), Ok(Dinero::new(centavos, moneda)));
+            prop_assert_eq!(parsear_moneda_con_locale(&texto, locale), Ok(Dinero::new(centavos, moneda)));
+        }
+    }
+}
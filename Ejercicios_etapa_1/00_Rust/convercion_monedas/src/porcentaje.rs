@@ -0,0 +1,92 @@
+// src/porcentaje.rs
+
+use std::fmt;
+use std::str::FromStr;
+
+use crate::error::ErrorMoneda;
+use crate::tasas::{mcd, parsear_decimal};
+
+/// Porcentaje exacto (16%, 8.875%), guardado como fracción para no redondear antes de tiempo.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Porcentaje {
+    numerador: i128,
+    denominador: i128,
+}
+
+impl Porcentaje {
+    pub const CERO: Porcentaje = Porcentaje {
+        numerador: 0,
+        denominador: 1,
+    };
+    pub const CIEN: Porcentaje = Porcentaje {
+        numerador: 100,
+        denominador: 1,
+    };
+
+    /// Crea el porcentaje `numerador / denominador` % (por ejemplo `new(35, 2)` es 17.5%).
+    pub fn new(numerador: i128, denominador: i128) -> Result<Porcentaje, ErrorMoneda> {
+        if numerador < 0 || denominador <= 0 {
+            return Err(ErrorMoneda::PorcentajeInvalido(format!(
+                "{}/{}",
+                numerador, denominador
+            )));
+        }
+        let divisor = mcd(numerador, denominador).max(1);
+        Ok(Porcentaje {
+            numerador: numerador / divisor,
+            denominador: denominador / divisor,
+        })
+    }
+
+    /// Crea un porcentaje a partir de puntos básicos (1600 = 16%).
+    pub fn desde_puntos_basicos(puntos: u32) -> Porcentaje {
+        Porcentaje::new(i128::from(puntos), 100).expect("los puntos basicos no son negativos")
+    }
+
+    /// El porcentaje como fracción de la unidad: 16% es `(16, 100)`.
+    pub fn como_fraccion(&self) -> (i128, i128) {
+        (self.numerador, self.denominador * 100)
+    }
+}
+
+impl FromStr for Porcentaje {
+    type Err = ErrorMoneda;
+
+    /// Lee un porcentaje escrito en decimal, con o sin '%' ("16", "8.875%").
+    fn from_str(texto: &str) -> Result<Self, Self::Err> {
+        let invalido = || ErrorMoneda::PorcentajeInvalido(texto.to_string());
+        let sin_signo = texto.trim().trim_end_matches('%');
+        let (numerador, escala) = parsear_decimal(sin_signo).ok_or_else(invalido)?;
+        Porcentaje::new(numerador, escala).map_err(|_| invalido())
+    }
+}
+
+impl fmt::Display for Porcentaje {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}%", self.numerador as f64 / self.denominador as f64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parsear_porcentaje() {
+        assert_eq!("16".parse(), Ok(Porcentaje::desde_puntos_basicos(1600)));
+        assert_eq!("8.875%".parse(), Porcentaje::new(71, 8));
+        assert_eq!("0".parse(), Ok(Porcentaje::CERO));
+        assert_eq!(
+            "-5".parse::<Porcentaje>(),
+            Err(ErrorMoneda::PorcentajeInvalido("-5".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_como_fraccion_y_display() {
+        let iva: Porcentaje = "16".parse().unwrap();
+        assert_eq!(iva.como_fraccion(), (16, 100));
+        assert_eq!(iva.to_string(), "16%");
+        assert_eq!("7.5".parse::<Porcentaje>().unwrap().to_string(), "7.5%");
+    }
+}
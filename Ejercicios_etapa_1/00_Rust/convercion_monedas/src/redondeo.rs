@@ -0,0 +1,89 @@
+// src/redondeo.rs
+
+/// Modos de redondeo para las operaciones que no dan un número exacto de centavos.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum ModoRedondeo {
+    /// Redondea al más cercano; los empates se alejan del cero (2.5 => 3, -2.5 => -3).
+    #[default]
+    MitadArriba,
+    /// Redondeo bancario: al más cercano; los empates van al número par (2.5 => 2, 3.5 => 4).
+    MitadPar,
+    /// Hacia menos infinito (2.7 => 2, -2.1 => -3).
+    Piso,
+    /// Hacia más infinito (2.1 => 3, -2.7 => -2).
+    Techo,
+    /// Descarta los decimales, hacia el cero (2.7 => 2, -2.7 => -2).
+    Truncar,
+}
+
+/// Divide `numerador / denominador` redondeando el resultado con el modo indicado.
+///
+/// # Panics
+/// Si `denominador` es cero.
+pub fn dividir_redondeando(numerador: i128, denominador: i128, modo: ModoRedondeo) -> i128 {
+    assert!(denominador != 0, "division entre cero");
+
+    let (numerador, denominador) = if denominador < 0 {
+        (-numerador, -denominador)
+    } else {
+        (numerador, denominador)
+    };
+    let cociente = numerador / denominador;
+    let resto = numerador % denominador;
+    if resto == 0 {
+        return cociente;
+    }
+
+    // El cociente ya está truncado hacia el cero; `paso` lo aleja del cero.
+    let paso = if numerador < 0 { -1 } else { 1 };
+    // Compara el resto con la mitad del denominador sin multiplicar (evita desbordes).
+    let resto = resto.abs();
+    let falta = denominador - resto;
+
+    match modo {
+        ModoRedondeo::Truncar => cociente,
+        ModoRedondeo::Piso if numerador < 0 => cociente - 1,
+        ModoRedondeo::Piso => cociente,
+        ModoRedondeo::Techo if numerador > 0 => cociente + 1,
+        ModoRedondeo::Techo => cociente,
+        ModoRedondeo::MitadArriba if resto >= falta => cociente + paso,
+        ModoRedondeo::MitadArriba => cociente,
+        ModoRedondeo::MitadPar if resto > falta || (resto == falta && cociente % 2 != 0) => {
+            cociente + paso
+        }
+        ModoRedondeo::MitadPar => cociente,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_division_exacta() {
+        assert_eq!(dividir_redondeando(10, 2, ModoRedondeo::Techo), 5);
+        assert_eq!(dividir_redondeando(-10, 2, ModoRedondeo::Piso), -5);
+    }
+
+    #[test]
+    fn test_mitad_arriba_y_mitad_par() {
+        assert_eq!(dividir_redondeando(25, 10, ModoRedondeo::MitadArriba), 3);
+        assert_eq!(dividir_redondeando(-25, 10, ModoRedondeo::MitadArriba), -3);
+        assert_eq!(dividir_redondeando(25, 10, ModoRedondeo::MitadPar), 2);
+        assert_eq!(dividir_redondeando(35, 10, ModoRedondeo::MitadPar), 4);
+        assert_eq!(dividir_redondeando(26, 10, ModoRedondeo::MitadPar), 3);
+    }
+
+    #[test]
+    fn test_piso_techo_truncar() {
+        assert_eq!(dividir_redondeando(-21, 10, ModoRedondeo::Piso), -3);
+        assert_eq!(dividir_redondeando(21, 10, ModoRedondeo::Techo), 3);
+        assert_eq!(dividir_redondeando(-27, 10, ModoRedondeo::Techo), -2);
+        assert_eq!(dividir_redondeando(-27, 10, ModoRedondeo::Truncar), -2);
+    }
+
+    #[test]
+    fn test_denominador_negativo() {
+        assert_eq!(dividir_redondeando(7, -2, ModoRedondeo::Piso), -4);
+    }
+}
@@ -0,0 +1,194 @@
+// src/serde_dinero.rs
+
+//! Representaciones serde de `Dinero` y `Moneda` (feature `serde`).
+//!
+//! Por defecto `Dinero` se serializa con el monto en decimal, como texto para no perder
+//! precisión en JSON:
+//!
+//! ```text
+//! {"amount":"12.34","currency":"USD"}
+//! ```
+//!
+//! Para guardar unidades menores usa `#[serde(with = "convercion_monedas::serde_dinero::centavos")]`:
+//!
+//! ```text
+//! {"amount_cents":1234,"currency":"USD"}
+//! ```
+//!
+//! Al deserializar se acepta cualquiera de las dos formas (no ambas a la vez ni campos
+//! desconocidos) y el código de moneda se valida contra la misma tabla que usa
+//! `convertir_cents_a_moneda`.
+
+use serde::de::Error as _;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::dinero::Dinero;
+use crate::moneda::Moneda;
+
+#[derive(Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct DineroDecimal {
+    amount: String,
+    currency: Moneda,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct DineroCentavos {
+    amount_cents: i64,
+    currency: Moneda,
+}
+
+/// Las dos formas en una sola estructura: con `#[serde(untagged)]` cualquier error se
+/// reportaría como "did not match any variant" y se perdería el motivo real.
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct CualquierDinero {
+    amount: Option<String>,
+    amount_cents: Option<i64>,
+    currency: Moneda,
+}
+
+impl Serialize for Moneda {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.codigo())
+    }
+}
+
+impl<'de> Deserialize<'de> for Moneda {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let codigo = String::deserialize(deserializer)?;
+        Moneda::desde_codigo(&codigo).map_err(D::Error::custom)
+    }
+}
+
+impl Serialize for Dinero {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        decimal::serialize(self, serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Dinero {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let dinero = CualquierDinero::deserialize(deserializer)?;
+        match (dinero.amount, dinero.amount_cents) {
+            (Some(monto), None) => {
+                Dinero::desde_monto_decimal(&monto, dinero.currency).map_err(D::Error::custom)
+            }
+            (None, Some(centavos)) => Ok(Dinero::new(centavos, dinero.currency)),
+            (Some(_), Some(_)) => Err(D::Error::custom(
+                "se esperaba `amount` o `amount_cents`, no ambos",
+            )),
+            (None, None) => Err(D::Error::missing_field("amount")),
+        }
+    }
+}
+
+/// `{"amount":"12.34","currency":"USD"}`; es la forma por defecto.
+pub mod decimal {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(dinero: &Dinero, serializer: S) -> Result<S::Ok, S::Error> {
+        DineroDecimal {
+            amount: dinero.monto_decimal(),
+            currency: dinero.moneda,
+        }
+        .serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Dinero, D::Error> {
+        let dinero = DineroDecimal::deserialize(deserializer)?;
+        Dinero::desde_monto_decimal(&dinero.amount, dinero.currency).map_err(D::Error::custom)
+    }
+}
+
+/// `{"amount_cents":1234,"currency":"USD"}`, para columnas enteras en bases de datos.
+pub mod centavos {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(dinero: &Dinero, serializer: S) -> Result<S::Ok, S::Error> {
+        DineroCentavos {
+            amount_cents: dinero.centavos,
+            currency: dinero.moneda,
+        }
+        .serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Dinero, D::Error> {
+        let dinero = DineroCentavos::deserialize(deserializer)?;
+        Ok(Dinero::new(dinero.amount_cents, dinero.currency))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Pedido {
+        total: Dinero,
+        #[serde(with = "centavos")]
+        envio: Dinero,
+    }
+
+    #[test]
+    fn test_serializar() {
+        let pedido = Pedido {
+            total: Dinero::new(1234, Moneda::USD),
+            envio: Dinero::new(-50, Moneda::EUR),
+        };
+        let json = serde_json::to_value(&pedido).unwrap();
+        assert_eq!(
+            json,
+            json!({
+                "total": { "amount": "12.34", "currency": "USD" },
+                "envio": { "amount_cents": -50, "currency": "EUR" },
+            })
+        );
+        assert_eq!(serde_json::from_value::<Pedido>(json).unwrap(), pedido);
+    }
+
+    #[test]
+    fn test_deserializar_acepta_ambas_formas() {
+        let decimal: Dinero = serde_json::from_str(r#"{"amount":"0.5","currency":"GBP"}"#).unwrap();
+        let centavos: Dinero =
+            serde_json::from_str(r#"{"amount_cents":50,"currency":"GBP"}"#).unwrap();
+        assert_eq!(decimal, Dinero::new(50, Moneda::GBP));
+        assert_eq!(centavos, decimal);
+    }
+
+    #[test]
+    fn test_deserializar_valida() {
+        let codigo = serde_json::from_str::<Moneda>(r#""ABC""#).unwrap_err();
+        assert_eq!(codigo.to_string(), "codigo de moneda invalido: \"ABC\"");
+
+        for (texto, mensaje) in [
+            (
+                r#"{"amount":"12.345","currency":"USD"}"#,
+                "no se puede interpretar",
+            ),
+            (
+                r#"{"amount":12.34,"currency":"USD"}"#,
+                "invalid type: floating point `12.34`, expected a string",
+            ),
+            (
+                r#"{"amount":"12.34","currency":"ABC"}"#,
+                "codigo de moneda invalido: \"ABC\"",
+            ),
+            (r#"{"amount_cents":1234}"#, "missing field `currency`"),
+            (r#"{"currency":"USD"}"#, "missing field `amount`"),
+            (
+                r#"{"amount":"1.00","amount_cents":100,"currency":"USD"}"#,
+                "se esperaba `amount` o `amount_cents`, no ambos",
+            ),
+            (
+                r#"{"amount":"1.00","currency":"USD","nota":"x"}"#,
+                "unknown field `nota`",
+            ),
+        ] {
+            let error = serde_json::from_str::<Dinero>(texto).unwrap_err();
+            assert!(error.to_string().contains(mensaje), "{}: {}", texto, error);
+        }
+    }
+}
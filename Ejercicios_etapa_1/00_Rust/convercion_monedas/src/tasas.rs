@@ -0,0 +1,256 @@
+// src/tasas.rs
+
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::error::ErrorMoneda;
+use crate::moneda::Moneda;
+
+/// Máximo de decimales aceptados al leer una tasa o un porcentaje escrito en decimal.
+const MAX_DECIMALES: usize = 18;
+
+/// Tasa de cambio exacta, guardada como fracción positiva `numerador / denominador`.
+///
+/// Se evita `f64` para que convertir y triangular no acumule errores de redondeo:
+/// "0.9123" se guarda como 9123/10000.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Tasa {
+    numerador: i128,
+    denominador: i128,
+}
+
+impl Tasa {
+    /// La tasa de una moneda contra sí misma.
+    pub const UNO: Tasa = Tasa {
+        numerador: 1,
+        denominador: 1,
+    };
+
+    /// Crea una tasa a partir de una fracción; ambos términos deben ser positivos.
+    pub fn new(numerador: i128, denominador: i128) -> Result<Tasa, ErrorMoneda> {
+        if numerador <= 0 || denominador <= 0 {
+            return Err(ErrorMoneda::TasaInvalida(format!(
+                "{}/{}",
+                numerador, denominador
+            )));
+        }
+        let divisor = mcd(numerador, denominador);
+        Ok(Tasa {
+            numerador: numerador / divisor,
+            denominador: denominador / divisor,
+        })
+    }
+
+    pub fn numerador(&self) -> i128 {
+        self.numerador
+    }
+
+    pub fn denominador(&self) -> i128 {
+        self.denominador
+    }
+
+    /// La tasa en sentido contrario (de destino a origen).
+    pub fn inversa(&self) -> Tasa {
+        Tasa {
+            numerador: self.denominador,
+            denominador: self.numerador,
+        }
+    }
+
+    /// Encadena dos tasas: (A→B).por(B→C) es A→C.
+    pub fn por(&self, otra: Tasa) -> Result<Tasa, ErrorMoneda> {
+        // Simplifica en cruz antes de multiplicar para retrasar el desbordamiento.
+        let a = mcd(self.numerador, otra.denominador);
+        let b = mcd(otra.numerador, self.denominador);
+        let numerador = (self.numerador / a).checked_mul(otra.numerador / b);
+        let denominador = (self.denominador / b).checked_mul(otra.denominador / a);
+        match (numerador, denominador) {
+            (Some(numerador), Some(denominador)) => Tasa::new(numerador, denominador),
+            _ => Err(ErrorMoneda::Desbordamiento),
+        }
+    }
+
+    /// Valor aproximado de la tasa, solo para mostrarla.
+    pub fn a_f64(&self) -> f64 {
+        self.numerador as f64 / self.denominador as f64
+    }
+}
+
+pub(crate) fn mcd(mut a: i128, mut b: i128) -> i128 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a.abs()
+}
+
+impl FromStr for Tasa {
+    type Err = ErrorMoneda;
+
+    /// Lee una tasa escrita en decimal ("17.05", "0.9123", "1").
+    fn from_str(texto: &str) -> Result<Self, Self::Err> {
+        let invalida = || ErrorMoneda::TasaInvalida(texto.to_string());
+        let (numerador, escala) = parsear_decimal(texto).ok_or_else(invalida)?;
+        Tasa::new(numerador, escala).map_err(|_| invalida())
+    }
+}
+
+/// Lee un decimal sin signo ("17.05") como fracción `(1705, 100)`.
+pub(crate) fn parsear_decimal(texto: &str) -> Option<(i128, i128)> {
+    let texto = texto.trim();
+    let (entero, decimales) = texto.split_once('.').unwrap_or((texto, ""));
+
+    let solo_digitos = |parte: &str| parte.chars().all(|c| c.is_ascii_digit());
+    if entero.is_empty()
+        || !solo_digitos(entero)
+        || !solo_digitos(decimales)
+        || decimales.len() > MAX_DECIMALES
+    {
+        return None;
+    }
+
+    let escala = 10_i128.pow(decimales.len() as u32);
+    let numerador = format!("{}{}", entero, decimales).parse::<i128>().ok()?;
+    Some((numerador, escala))
+}
+
+impl fmt::Display for Tasa {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.a_f64())
+    }
+}
+
+/// Fuente de tasas de cambio para `convertir`.
+pub trait ProveedorTasas {
+    /// Retorna cuántas unidades de `destino` vale una unidad de `origen`.
+    fn tasa(&self, origen: Moneda, destino: Moneda) -> Result<Tasa, ErrorMoneda>;
+}
+
+/// Tabla estática de tasas expresadas contra una moneda base.
+///
+/// Los pares que no se registran directamente se calculan triangulando a través de la base:
+/// si la base es USD, EUR→MXN = (USD→MXN) / (USD→EUR).
+#[derive(Debug, Clone, PartialEq)]
+pub struct TablaTasas {
+    base: Moneda,
+    tasas: HashMap<Moneda, Tasa>,
+    pares: HashMap<(Moneda, Moneda), Tasa>,
+}
+
+impl TablaTasas {
+    pub fn new(base: Moneda) -> TablaTasas {
+        TablaTasas {
+            base,
+            tasas: HashMap::new(),
+            pares: HashMap::new(),
+        }
+    }
+
+    pub fn base(&self) -> Moneda {
+        self.base
+    }
+
+    /// Registra cuántas unidades de `moneda` vale una unidad de la base.
+    pub fn con_tasa(mut self, moneda: Moneda, tasa: Tasa) -> TablaTasas {
+        self.insertar_tasa(moneda, tasa);
+        self
+    }
+
+    /// Registra una tasa directa entre dos monedas; tiene prioridad sobre la triangulación.
+    pub fn con_par(mut self, origen: Moneda, destino: Moneda, tasa: Tasa) -> TablaTasas {
+        self.pares.insert((origen, destino), tasa);
+        self
+    }
+
+    pub fn insertar_tasa(&mut self, moneda: Moneda, tasa: Tasa) {
+        self.tasas.insert(moneda, tasa);
+    }
+
+    /// Monedas con tasa registrada contra la base (sin incluir la base).
+    pub fn monedas(&self) -> impl Iterator<Item = (Moneda, Tasa)> + '_ {
+        self.tasas.iter().map(|(moneda, tasa)| (*moneda, *tasa))
+    }
+
+    fn tasa_desde_base(&self, moneda: Moneda) -> Option<Tasa> {
+        if moneda == self.base {
+            return Some(Tasa::UNO);
+        }
+        self.tasas.get(&moneda).copied()
+    }
+}
+
+impl ProveedorTasas for TablaTasas {
+    fn tasa(&self, origen: Moneda, destino: Moneda) -> Result<Tasa, ErrorMoneda> {
+        if origen == destino {
+            return Ok(Tasa::UNO);
+        }
+        if let Some(tasa) = self.pares.get(&(origen, destino)) {
+            return Ok(*tasa);
+        }
+        if let Some(tasa) = self.pares.get(&(destino, origen)) {
+            return Ok(tasa.inversa());
+        }
+
+        let no_disponible = || ErrorMoneda::TasaNoDisponible(origen, destino);
+        let base_a_origen = self.tasa_desde_base(origen).ok_or_else(no_disponible)?;
+        let base_a_destino = self.tasa_desde_base(destino).ok_or_else(no_disponible)?;
+        base_a_origen.inversa().por(base_a_destino)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tasa(texto: &str) -> Tasa {
+        texto.parse().unwrap()
+    }
+
+    #[test]
+    fn test_parsear_tasa() {
+        assert_eq!(tasa("0.9123"), Tasa::new(9123, 10000).unwrap());
+        assert_eq!(tasa("17.50"), Tasa::new(35, 2).unwrap());
+        assert_eq!(tasa("2"), Tasa::new(2, 1).unwrap());
+        for texto in ["", "-1", "0", "1,5", "abc", ".5"] {
+            assert_eq!(
+                texto.parse::<Tasa>(),
+                Err(ErrorMoneda::TasaInvalida(texto.to_string()))
+            );
+        }
+    }
+
+    #[test]
+    fn test_triangulacion_por_base() {
+        let tabla = TablaTasas::new(Moneda::USD)
+            .con_tasa(Moneda::EUR, tasa("0.8"))
+            .con_tasa(Moneda::MXN, tasa("20"));
+
+        assert_eq!(tabla.tasa(Moneda::USD, Moneda::MXN), Ok(tasa("20")));
+        assert_eq!(tabla.tasa(Moneda::MXN, Moneda::USD), Ok(tasa("0.05")));
+        assert_eq!(tabla.tasa(Moneda::EUR, Moneda::MXN), Ok(tasa("25")));
+        assert_eq!(tabla.tasa(Moneda::GBP, Moneda::GBP), Ok(Tasa::UNO));
+    }
+
+    #[test]
+    fn test_par_directo_tiene_prioridad() {
+        let tabla = TablaTasas::new(Moneda::USD)
+            .con_tasa(Moneda::EUR, tasa("0.8"))
+            .con_tasa(Moneda::MXN, tasa("20"))
+            .con_par(Moneda::EUR, Moneda::MXN, tasa("24.5"));
+
+        assert_eq!(tabla.tasa(Moneda::EUR, Moneda::MXN), Ok(tasa("24.5")));
+        assert_eq!(
+            tabla.tasa(Moneda::MXN, Moneda::EUR),
+            Ok(tasa("24.5").inversa())
+        );
+    }
+
+    #[test]
+    fn test_tasa_no_disponible() {
+        let tabla = TablaTasas::new(Moneda::USD).con_tasa(Moneda::EUR, tasa("0.8"));
+        assert_eq!(
+            tabla.tasa(Moneda::EUR, Moneda::GBP),
+            Err(ErrorMoneda::TasaNoDisponible(Moneda::EUR, Moneda::GBP))
+        );
+    }
+}
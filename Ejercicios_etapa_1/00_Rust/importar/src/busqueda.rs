@@ -0,0 +1,453 @@
+// src/busqueda.rs
+
+use std::collections::{BTreeMap, HashMap};
+use std::ops::Bound;
+
+use crate::Product;
+use crate::catalogo::Catalogo;
+use crate::error::ErrorImportacion;
+use crate::handle::slugify;
+
+/// Peso de cada campo del producto al calcular la relevancia.
+const PESO_TITULO: f64 = 3.0;
+const PESO_ETIQUETA: f64 = 2.0;
+const PESO_HANDLE: f64 = 2.0;
+const PESO_DESCRIPCION: f64 = 1.0;
+
+/// Palabras demasiado comunes para servir en una búsqueda, en español e inglés.
+const PALABRAS_VACIAS: [&str; 20] = [
+    "a", "al", "con", "de", "del", "el", "en", "la", "las", "los", "para", "por", "un", "una", "y",
+    "and", "for", "of", "the", "with",
+];
+
+/// Un producto encontrado por `IndiceBusqueda::buscar`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Coincidencia {
+    pub id: u32,
+    /// Cuántas palabras de la búsqueda encontraron el producto.
+    pub palabras: usize,
+    pub puntaje: f64,
+}
+
+/// Índice invertido en memoria sobre título, descripción, etiquetas y handle.
+///
+/// Las palabras se normalizan quitando acentos y mayúsculas (igual que `slugify`) y se
+/// reducen a una raíz aproximada para que "camisas" encuentre "camisa". Si una palabra
+/// no aparece completa ni como prefijo, tolera errores de escritura: una letra de
+/// diferencia en palabras de 4 a 7 letras y dos en palabras más largas.
+///
+/// El índice no se entera solo de los cambios del catálogo: después de modificar un
+/// producto hay que volver a llamar a `indexar` (o a `quitar` si se eliminó), o usar
+/// `CatalogoIndexado`, que mantiene los dos al día.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct IndiceBusqueda {
+    /// Raíz -> (id del producto -> peso acumulado de la raíz en el producto). Ordenado para
+    /// encontrar los prefijos con un rango.
+    terminos: BTreeMap<String, HashMap<u32, f64>>,
+    /// Raíces de cada producto, para poder quitarlo sin recorrer todo el índice.
+    por_producto: HashMap<u32, Vec<String>>,
+}
+
+impl IndiceBusqueda {
+    pub fn new() -> IndiceBusqueda {
+        IndiceBusqueda::default()
+    }
+
+    pub fn desde_catalogo(catalogo: &Catalogo) -> IndiceBusqueda {
+        let mut indice = IndiceBusqueda::new();
+        for producto in catalogo {
+            indice.indexar(producto);
+        }
+        indice
+    }
+
+    /// Cantidad de productos indexados.
+    pub fn len(&self) -> usize {
+        self.por_producto.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.por_producto.is_empty()
+    }
+
+    /// Agrega el producto al índice, o lo reemplaza si ya estaba.
+    pub fn indexar(&mut self, producto: &Product) {
+        self.quitar(producto.id);
+
+        let mut pesos: HashMap<String, f64> = HashMap::new();
+        let mut agregar = |texto: &str, peso: f64| {
+            for raiz in raices(texto) {
+                *pesos.entry(raiz).or_default() += peso;
+            }
+        };
+        agregar(&producto.titulo, PESO_TITULO);
+        agregar(&producto.handle, PESO_HANDLE);
+        agregar(&quitar_html(&producto.descripcion), PESO_DESCRIPCION);
+        for etiqueta in &producto.etiquetas {
+            agregar(etiqueta, PESO_ETIQUETA);
+        }
+
+        for (raiz, peso) in &pesos {
+            self.terminos
+                .entry(raiz.clone())
+                .or_default()
+                .insert(producto.id, *peso);
+        }
+        self.por_producto
+            .insert(producto.id, pesos.into_keys().collect());
+    }
+
+    /// Quita el producto del índice; no hace nada si no estaba.
+    pub fn quitar(&mut self, id: u32) {
+        let Some(raices) = self.por_producto.remove(&id) else {
+            return;
+        };
+        for raiz in raices {
+            if let Some(productos) = self.terminos.get_mut(&raiz) {
+                productos.remove(&id);
+                if productos.is_empty() {
+                    self.terminos.remove(&raiz);
+                }
+            }
+        }
+    }
+
+    /// Busca los productos más relevantes para el texto.
+    ///
+    /// Primero van los productos que coinciden con más palabras de la búsqueda; entre ellos,
+    /// los de mayor puntaje. Las coincidencias exactas pesan más que las de prefijo (para
+    /// búsquedas a medio escribir) y estas más que las aproximadas. Las palabras raras en el
+    /// catálogo pesan más que las comunes.
+    pub fn buscar(&self, texto: &str, limite: usize) -> Vec<Coincidencia> {
+        let total = self.por_producto.len() as f64;
+        let mut resultados: HashMap<u32, Coincidencia> = HashMap::new();
+
+        for raiz_buscada in raices(texto) {
+            // Mejor puntaje de esta palabra en cada producto.
+            let mut mejores: HashMap<u32, f64> = HashMap::new();
+            let mut sumar = |productos: &HashMap<u32, f64>, factor: f64| {
+                let rareza = (1.0 + total / productos.len() as f64).ln();
+                for (&id, &peso) in productos {
+                    let puntaje = peso * rareza * factor;
+                    let mejor = mejores.entry(id).or_default();
+                    *mejor = mejor.max(puntaje);
+                }
+            };
+
+            let mut encontrada = false;
+            for (raiz, productos) in self.similares(&raiz_buscada) {
+                encontrada = true;
+                let factor = if *raiz == raiz_buscada { 1.0 } else { 0.5 };
+                sumar(productos, factor);
+            }
+            // Recorrer todo el vocabulario solo si no hubo coincidencia exacta ni de prefijo.
+            if !encontrada {
+                for (raiz, productos) in &self.terminos {
+                    if let Some(factor) = similitud_aproximada(&raiz_buscada, raiz) {
+                        sumar(productos, factor);
+                    }
+                }
+            }
+            for (id, puntaje) in mejores {
+                let coincidencia = resultados.entry(id).or_insert(Coincidencia {
+                    id,
+                    palabras: 0,
+                    puntaje: 0.0,
+                });
+                coincidencia.palabras += 1;
+                coincidencia.puntaje += puntaje;
+            }
+        }
+
+        let mut resultados: Vec<Coincidencia> = resultados.into_values().collect();
+        resultados.sort_by(|a, b| {
+            b.palabras
+                .cmp(&a.palabras)
+                .then(b.puntaje.total_cmp(&a.puntaje))
+                .then(a.id.cmp(&b.id))
+        });
+        resultados.truncate(limite);
+        resultados
+    }
+
+    /// La raíz exacta y las que la tienen como prefijo (si tiene al menos 3 letras).
+    fn similares<'a>(
+        &'a self,
+        buscada: &'a str,
+    ) -> impl Iterator<Item = (&'a String, &'a HashMap<u32, f64>)> {
+        let exacta = self.terminos.get_key_value(buscada);
+        let prefijos = self
+            .terminos
+            .range::<str, _>((Bound::Excluded(buscada), Bound::Unbounded))
+            .take_while(move |(raiz, _)| buscada.len() >= 3 && raiz.starts_with(buscada));
+        exacta.into_iter().chain(prefijos)
+    }
+
+    /// Igual que `buscar`, pero retorna los productos del catálogo.
+    pub fn buscar_productos<'a>(
+        &self,
+        texto: &str,
+        catalogo: &'a Catalogo,
+        limite: usize,
+    ) -> Vec<&'a Product> {
+        self.buscar(texto, limite)
+            .into_iter()
+            .filter_map(|coincidencia| catalogo.por_id(coincidencia.id))
+            .collect()
+    }
+}
+
+/// Un catálogo con su índice de búsqueda, que se actualiza con cada cambio.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CatalogoIndexado {
+    catalogo: Catalogo,
+    indice: IndiceBusqueda,
+}
+
+impl CatalogoIndexado {
+    pub fn new(catalogo: Catalogo) -> CatalogoIndexado {
+        let indice = IndiceBusqueda::desde_catalogo(&catalogo);
+        CatalogoIndexado { catalogo, indice }
+    }
+
+    pub fn catalogo(&self) -> &Catalogo {
+        &self.catalogo
+    }
+
+    pub fn indice(&self) -> &IndiceBusqueda {
+        &self.indice
+    }
+
+    /// Ver `Catalogo::insertar`.
+    pub fn insertar(&mut self, producto: Product) -> Result<(), ErrorImportacion> {
+        let id = producto.id;
+        self.catalogo.insertar(producto)?;
+        self.reindexar(id);
+        Ok(())
+    }
+
+    /// Ver `Catalogo::eliminar`.
+    pub fn eliminar(&mut self, id: u32) -> Option<Product> {
+        self.indice.quitar(id);
+        self.catalogo.eliminar(id)
+    }
+
+    /// Ver `Catalogo::modificar`; el producto se reindexa aunque haya error, porque los
+    /// cambios que no son del handle se conservan.
+    pub fn modificar<F>(&mut self, id: u32, cambio: F) -> Result<(), ErrorImportacion>
+    where
+        F: FnOnce(&mut Product),
+    {
+        let resultado = self.catalogo.modificar(id, cambio);
+        self.reindexar(id);
+        resultado
+    }
+
+    /// Ver `Catalogo::cambiar_handle`.
+    pub fn cambiar_handle(&mut self, id: u32, nuevo: &str) -> Result<(), ErrorImportacion> {
+        self.catalogo.cambiar_handle(id, nuevo)?;
+        self.reindexar(id);
+        Ok(())
+    }
+
+    /// Ver `IndiceBusqueda::buscar_productos`.
+    pub fn buscar(&self, texto: &str, limite: usize) -> Vec<&Product> {
+        self.indice.buscar_productos(texto, &self.catalogo, limite)
+    }
+
+    fn reindexar(&mut self, id: u32) {
+        if let Some(producto) = self.catalogo.por_id(id) {
+            self.indice.indexar(producto);
+        }
+    }
+}
+
+/// Qué tanto se parece la raíz buscada a una del índice con errores de escritura:
+/// `1 / (1 + distancia)` si la distancia es tolerable, o `None`.
+fn similitud_aproximada(buscada: &str, raiz: &str) -> Option<f64> {
+    let tolerancia = match buscada.len() {
+        0..=3 => return None,
+        4..=7 => 1,
+        _ => 2,
+    };
+    distancia_edicion(buscada, raiz, tolerancia).map(|distancia| 1.0 / (1.0 + distancia as f64))
+}
+
+/// Distancia de Damerau-Levenshtein restringida (una transposición cuenta como un error),
+/// o `None` si pasa de `maximo`. Las raíces son ASCII, así que compara bytes.
+fn distancia_edicion(a: &str, b: &str, maximo: usize) -> Option<usize> {
+    let (a, b) = (a.as_bytes(), b.as_bytes());
+    if a.len().abs_diff(b.len()) > maximo {
+        return None;
+    }
+
+    let mut anterior_2: Vec<usize> = Vec::new();
+    let mut anterior: Vec<usize> = (0..=b.len()).collect();
+    for i in 1..=a.len() {
+        let mut actual = vec![i; b.len() + 1];
+        for j in 1..=b.len() {
+            let costo = usize::from(a[i - 1] != b[j - 1]);
+            actual[j] = (anterior[j] + 1)
+                .min(actual[j - 1] + 1)
+                .min(anterior[j - 1] + costo);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                actual[j] = actual[j].min(anterior_2[j - 2] + 1);
+            }
+        }
+        if actual.iter().all(|&d| d > maximo) {
+            return None;
+        }
+        anterior_2 = std::mem::replace(&mut anterior, actual);
+    }
+    Some(anterior[b.len()]).filter(|&d| d <= maximo)
+}
+
+/// Divide el texto en palabras normalizadas, sin palabras vacías, y las reduce a su raíz.
+fn raices(texto: &str) -> Vec<String> {
+    slugify(texto)
+        .split('-')
+        .filter(|palabra| !palabra.is_empty() && !PALABRAS_VACIAS.contains(palabra))
+        .map(raiz)
+        .collect()
+}
+
+/// Sufijos de plural y derivación comunes en español e inglés, del más largo al más corto.
+const SUFIJOS: [&str; 10] = [
+    "aciones", "acion", "amente", "mente", "ings", "ing", "ed", "es", "s", "ly",
+];
+
+/// Raíz aproximada de una palabra ya normalizada: quita un sufijo de `SUFIJOS` y la vocal
+/// final de género ("camisas" -> "camis", "rojo" -> "roj", "shoes" -> "sho").
+///
+/// No es un lematizador real; basta con que la misma palabra escrita en singular, plural,
+/// masculino o femenino dé la misma raíz.
+fn raiz(palabra: &str) -> String {
+    let mut raiz = palabra;
+    if let Some(sin_sufijo) = SUFIJOS
+        .iter()
+        .filter_map(|sufijo| raiz.strip_suffix(sufijo))
+        .find(|resto| resto.len() >= 3)
+    {
+        raiz = sin_sufijo;
+    }
+    if raiz.len() > 3 && raiz.ends_with(['a', 'o', 'e']) {
+        raiz = &raiz[..raiz.len() - 1];
+    }
+    raiz.to_string()
+}
+
+/// Quita las etiquetas HTML de la descripción ("<p>Hola</p>" -> " Hola ").
+fn quitar_html(html: &str) -> String {
+    let mut texto = String::with_capacity(html.len());
+    let mut dentro = false;
+    for c in html.chars() {
+        match c {
+            '<' => dentro = true,
+            '>' if dentro => {
+                dentro = false;
+                texto.push(' ');
+            }
+            _ if !dentro => texto.push(c),
+            _ => {}
+        }
+    }
+    texto
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn catalogo() -> Catalogo {
+        Catalogo::desde_productos(vec![
+            Product::new(1, "camisa-azul")
+                .con_titulo("Camisa Azul")
+                .con_etiquetas(&["algodón"]),
+            Product::new(2, "camisas-rojas").con_titulo("Camisas Rojas"),
+            Product::new(3, "pantalon-azul").con_titulo("Pantalón azul marino"),
+            Product::new(4, "blue-shoes").con_titulo("Blue Shoes"),
+            Product::new(5, "gorra")
+                .con_titulo("Gorra")
+                .con_descripcion("<p>Para el sol, <b>azul</b></p>"),
+        ])
+        .unwrap()
+    }
+
+    fn ids(indice: &IndiceBusqueda, texto: &str) -> Vec<u32> {
+        indice.buscar(texto, 10).iter().map(|c| c.id).collect()
+    }
+
+    #[test]
+    fn test_ranking() {
+        let indice = IndiceBusqueda::desde_catalogo(&catalogo());
+        // La camisa azul coincide con las dos palabras. Entre el resto, "camis" es más rara
+        // que "azul" en el catálogo, y el título pesa más que la descripción.
+        assert_eq!(ids(&indice, "camisa azul"), vec![1, 2, 3, 5]);
+        assert_eq!(ids(&indice, "CAMISAS"), vec![1, 2]);
+        assert_eq!(ids(&indice, "algodon"), vec![1]);
+        assert!(ids(&indice, "de la").is_empty());
+    }
+
+    #[test]
+    fn test_errores_de_escritura_y_prefijos() {
+        let indice = IndiceBusqueda::desde_catalogo(&catalogo());
+        assert_eq!(ids(&indice, "camsia"), vec![1, 2]);
+        assert_eq!(ids(&indice, "pantlaon"), vec![3]);
+        assert_eq!(ids(&indice, "shoe"), vec![4]);
+        assert_eq!(ids(&indice, "pant"), vec![3]);
+        assert!(ids(&indice, "xyz").is_empty());
+    }
+
+    #[test]
+    fn test_actualizacion_incremental() {
+        let mut catalogo = catalogo();
+        let mut indice = IndiceBusqueda::desde_catalogo(&catalogo);
+        catalogo
+            .modificar(4, |producto| producto.titulo = "Zapatos Azules".to_string())
+            .unwrap();
+        indice.indexar(catalogo.por_id(4).unwrap());
+        assert_eq!(ids(&indice, "zapato"), vec![4]);
+        assert!(ids(&indice, "azules").contains(&4));
+
+        indice.quitar(1);
+        assert_eq!(indice.len(), 4);
+        assert_eq!(ids(&indice, "algodon"), Vec::<u32>::new());
+        let productos = indice.buscar_productos("gorra", &catalogo, 5);
+        assert_eq!(productos[0].handle, "gorra");
+    }
+
+    #[test]
+    fn test_catalogo_indexado() {
+        let mut catalogo = CatalogoIndexado::new(catalogo());
+        let handles = |catalogo: &CatalogoIndexado, texto: &str| -> Vec<String> {
+            let productos = catalogo.buscar(texto, 10);
+            productos.iter().map(|p| p.handle.clone()).collect()
+        };
+
+        catalogo
+            .modificar(4, |producto| producto.titulo = "Zapatos Azules".to_string())
+            .unwrap();
+        assert_eq!(handles(&catalogo, "zapatos"), vec!["blue-shoes"]);
+        catalogo.cambiar_handle(4, "zapatos-azules").unwrap();
+        assert_eq!(handles(&catalogo, "zapatos"), vec!["zapatos-azules"]);
+
+        catalogo
+            .insertar(Product::new(6, "sandalias").con_titulo("Sandalias"))
+            .unwrap();
+        assert_eq!(handles(&catalogo, "sandal"), vec!["sandalias"]);
+        catalogo.eliminar(1);
+        assert_eq!(handles(&catalogo, "algodon"), Vec::<String>::new());
+        assert_eq!(catalogo.indice().len(), catalogo.catalogo().len());
+    }
+
+    #[test]
+    fn test_raiz_y_distancia() {
+        assert_eq!(raiz("camisas"), raiz("camisa"));
+        assert_eq!(raiz("roja"), raiz("rojo"));
+        assert_eq!(raiz("shoes"), raiz("shoe"));
+        assert_eq!(raiz("sol"), "sol");
+        assert_eq!(distancia_edicion("camisa", "camsia", 2), Some(1));
+        assert_eq!(distancia_edicion("kitten", "sitting", 3), Some(3));
+        assert_eq!(distancia_edicion("kitten", "sitting", 2), None);
+    }
+}
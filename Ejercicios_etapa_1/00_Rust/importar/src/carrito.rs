@@ -0,0 +1,774 @@
+// src/carrito.rs
+
+use std::fmt;
+
+use crate::catalogo::Catalogo;
+use crate::consulta::Filtro;
+use crate::producto::{Product, Variante};
+
+/// Errores al armar el carrito o calcular sus totales.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ErrorCarrito {
+    /// No hay ningún producto con ese id en el catálogo.
+    ProductoNoEncontrado(u32),
+    /// El producto existe pero no tiene esa variante.
+    VarianteNoEncontrada { producto: u32, variante: u32 },
+    /// Se intentó agregar cero unidades, o más de las que caben en el carrito (a lo más
+    /// `u32::MAX` en total).
+    CantidadInvalida(u32),
+    /// Se piden más unidades de una variante de las que hay en inventario.
+    LimiteCantidad {
+        producto: u32,
+        variante: u32,
+        maximo: i64,
+        solicitado: i64,
+    },
+    /// Un código de descuento mal definido o que ya está aplicado.
+    CodigoInvalido(String),
+    /// La tarifa de envío no aplica al pedido.
+    EnvioNoDisponible(String),
+}
+
+impl fmt::Display for ErrorCarrito {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ErrorCarrito::ProductoNoEncontrado(id) => write!(f, "no existe el producto {}", id),
+            ErrorCarrito::VarianteNoEncontrada { producto, variante } => {
+                write!(
+                    f,
+                    "el producto {} no tiene la variante {}",
+                    producto, variante
+                )
+            }
+            ErrorCarrito::CantidadInvalida(cantidad) => {
+                write!(f, "cantidad invalida: {}", cantidad)
+            }
+            ErrorCarrito::LimiteCantidad {
+                producto,
+                variante,
+                maximo,
+                solicitado,
+            } => write!(
+                f,
+                "de la variante {} del producto {} hay {} en inventario, se piden {}",
+                variante, producto, maximo, solicitado
+            ),
+            ErrorCarrito::CodigoInvalido(mensaje) => {
+                write!(f, "codigo de descuento invalido: {}", mensaje)
+            }
+            ErrorCarrito::EnvioNoDisponible(tarifa) => {
+                write!(f, "la tarifa de envio \"{}\" no aplica al pedido", tarifa)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ErrorCarrito {}
+
+/// Una línea del carrito: cierta cantidad de una variante, con propiedades libres
+/// ("Grabado" -> "Ana") que distinguen dos líneas de la misma variante.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LineaCarrito {
+    pub producto: u32,
+    pub variante: u32,
+    pub cantidad: u32,
+    pub propiedades: Vec<(String, String)>,
+}
+
+/// Cuánto descuenta un código.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Descuento {
+    /// Porcentaje entero de 1 a 100, redondeado al centavo (la mitad hacia arriba).
+    Porcentaje(u8),
+    /// Centavos fijos: por unidad en un descuento de productos, una sola vez en uno de
+    /// pedido. Nunca deja un precio negativo.
+    Monto(i64),
+}
+
+/// A qué se aplica un código.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AlcanceDescuento {
+    /// Al subtotal del pedido, después de los descuentos de productos.
+    Pedido,
+    /// A las líneas cuyos productos cumplen el filtro.
+    Productos(Filtro),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CodigoDescuento {
+    pub codigo: String,
+    pub descuento: Descuento,
+    pub alcance: AlcanceDescuento,
+    /// Subtotal mínimo (antes de descuentos) para que el código aplique.
+    pub minimo_centavos: i64,
+}
+
+impl CodigoDescuento {
+    pub fn pedido(codigo: &str, descuento: Descuento) -> CodigoDescuento {
+        CodigoDescuento {
+            codigo: codigo.to_string(),
+            descuento,
+            alcance: AlcanceDescuento::Pedido,
+            minimo_centavos: 0,
+        }
+    }
+
+    pub fn productos(codigo: &str, descuento: Descuento, filtro: Filtro) -> CodigoDescuento {
+        CodigoDescuento {
+            alcance: AlcanceDescuento::Productos(filtro),
+            ..CodigoDescuento::pedido(codigo, descuento)
+        }
+    }
+
+    pub fn con_minimo(mut self, centavos: i64) -> CodigoDescuento {
+        self.minimo_centavos = centavos;
+        self
+    }
+
+    fn validar(&self) -> Result<(), ErrorCarrito> {
+        let invalido = |mensaje: &str| {
+            Err(ErrorCarrito::CodigoInvalido(format!(
+                "\"{}\": {}",
+                self.codigo, mensaje
+            )))
+        };
+        if self.codigo.trim().is_empty() {
+            return invalido("el codigo esta vacio");
+        }
+        match self.descuento {
+            Descuento::Porcentaje(0) | Descuento::Porcentaje(101..) => {
+                invalido("el porcentaje debe estar entre 1 y 100")
+            }
+            Descuento::Monto(centavos) if centavos <= 0 => invalido("el monto debe ser positivo"),
+            _ if self.minimo_centavos < 0 => invalido("el minimo no puede ser negativo"),
+            _ => Ok(()),
+        }
+    }
+
+    /// Descuento sobre `centavos` (`unidades` solo cuenta para un monto fijo de productos).
+    fn descontar(&self, centavos: i64, unidades: u32) -> i64 {
+        let descuento = match (self.descuento, &self.alcance) {
+            (Descuento::Porcentaje(porcentaje), _) => {
+                ((centavos as i128 * porcentaje as i128 + 50) / 100) as i64
+            }
+            (Descuento::Monto(monto), AlcanceDescuento::Productos(_)) => {
+                monto.saturating_mul(unidades as i64)
+            }
+            (Descuento::Monto(monto), AlcanceDescuento::Pedido) => monto,
+        };
+        descuento.clamp(0, centavos.max(0))
+    }
+}
+
+/// Una línea con sus precios calculados.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LineaTotal {
+    pub producto: u32,
+    pub variante: u32,
+    /// "Simple Tee - M / Rojo", o solo el título del producto si no tiene opciones.
+    pub titulo: String,
+    pub cantidad: u32,
+    pub precio_unitario_centavos: i64,
+    /// Precio unitario por cantidad.
+    pub subtotal_centavos: i64,
+    /// Suma de los descuentos de productos que aplicaron a la línea.
+    pub descuento_centavos: i64,
+    pub total_centavos: i64,
+}
+
+/// Cuánto descontó un código en todo el pedido (cero si no alcanzó el mínimo o si no
+/// aplicó a ninguna línea).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DescuentoAplicado {
+    pub codigo: String,
+    pub centavos: i64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OpcionEnvio {
+    pub nombre: String,
+    pub centavos: i64,
+}
+
+/// Resumen del carrito, todo en centavos.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Totales {
+    pub lineas: Vec<LineaTotal>,
+    pub unidades: u32,
+    /// Suma de los subtotales de las líneas, antes de descuentos.
+    pub subtotal_centavos: i64,
+    pub descuentos: Vec<DescuentoAplicado>,
+    /// Suma de todos los descuentos, de productos y de pedido.
+    pub descuento_centavos: i64,
+    pub envio: Option<OpcionEnvio>,
+    pub total_centavos: i64,
+}
+
+impl Totales {
+    /// Subtotal después de descuentos y antes del envío; es lo que ven las tarifas.
+    pub fn subtotal_con_descuento(&self) -> i64 {
+        self.subtotal_centavos - self.descuento_centavos
+    }
+
+    /// Cotiza las tarifas y retorna las que aplican, de la más barata a la más cara.
+    pub fn opciones_envio(&self, tarifas: &[&dyn TarifaEnvio]) -> Vec<OpcionEnvio> {
+        let mut opciones: Vec<OpcionEnvio> = tarifas
+            .iter()
+            .filter_map(|tarifa| {
+                Some(OpcionEnvio {
+                    nombre: tarifa.nombre().to_string(),
+                    centavos: tarifa.cotizar(self)?,
+                })
+            })
+            .collect();
+        opciones.sort_by_key(|opcion| opcion.centavos);
+        opciones
+    }
+
+    /// Agrega (o reemplaza) el envío y recalcula el total.
+    pub fn con_envio(mut self, tarifa: &dyn TarifaEnvio) -> Result<Totales, ErrorCarrito> {
+        let centavos = tarifa
+            .cotizar(&self)
+            .ok_or_else(|| ErrorCarrito::EnvioNoDisponible(tarifa.nombre().to_string()))?;
+        self.envio = Some(OpcionEnvio {
+            nombre: tarifa.nombre().to_string(),
+            centavos,
+        });
+        self.total_centavos = self.subtotal_con_descuento() + centavos;
+        Ok(self)
+    }
+}
+
+/// Punto de extensión para calcular el envío (tarifa fija, por peso, por API de la
+/// paquetería...).
+pub trait TarifaEnvio {
+    fn nombre(&self) -> &str;
+
+    /// Precio del envío para el pedido, o `None` si la tarifa no aplica.
+    fn cotizar(&self, totales: &Totales) -> Option<i64>;
+}
+
+/// Siempre cuesta lo mismo.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TarifaFija {
+    pub nombre: String,
+    pub centavos: i64,
+}
+
+impl TarifaEnvio for TarifaFija {
+    fn nombre(&self) -> &str {
+        &self.nombre
+    }
+
+    fn cotizar(&self, _totales: &Totales) -> Option<i64> {
+        Some(self.centavos)
+    }
+}
+
+/// Aplica si el subtotal con descuento está en `[desde, hasta)`, como las tarifas por
+/// precio de Shopify ("envío gratis desde $50").
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TarifaPorPrecio {
+    pub nombre: String,
+    pub desde_centavos: i64,
+    pub hasta_centavos: Option<i64>,
+    pub centavos: i64,
+}
+
+impl TarifaEnvio for TarifaPorPrecio {
+    fn nombre(&self) -> &str {
+        &self.nombre
+    }
+
+    fn cotizar(&self, totales: &Totales) -> Option<i64> {
+        let subtotal = totales.subtotal_con_descuento();
+        let aplica = subtotal >= self.desde_centavos
+            && self.hasta_centavos.is_none_or(|hasta| subtotal < hasta);
+        aplica.then_some(self.centavos)
+    }
+}
+
+/// Carrito de compras armado con ids del catálogo.
+///
+/// Guarda solo ids y cantidades: los precios se leen del catálogo al calcular los
+/// totales, así que siempre reflejan el precio vigente.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Carrito {
+    lineas: Vec<LineaCarrito>,
+    codigos: Vec<CodigoDescuento>,
+}
+
+impl Carrito {
+    pub fn new() -> Carrito {
+        Carrito::default()
+    }
+
+    pub fn lineas(&self) -> &[LineaCarrito] {
+        &self.lineas
+    }
+
+    pub fn codigos(&self) -> &[CodigoDescuento] {
+        &self.codigos
+    }
+
+    pub fn unidades(&self) -> u32 {
+        // No se desborda: `agregar` y `actualizar` revisan el total con `validar_unidades`.
+        self.lineas.iter().map(|linea| linea.cantidad).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.lineas.is_empty()
+    }
+
+    /// Agrega unidades de una variante.
+    ///
+    /// Si ya hay una línea de la misma variante con las mismas propiedades se suma a
+    /// ella. Las unidades de la variante en todo el carrito no pueden pasar de su
+    /// inventario en el catálogo.
+    ///
+    /// # Parámetros
+    /// - `catalogo`: de dónde se leen el producto y su inventario.
+    /// - `producto`, `variante`: ids de lo que se agrega.
+    /// - `cantidad`: unidades a agregar; debe ser mayor que cero.
+    /// - `propiedades`: pares (nombre, valor) de la línea, por ejemplo un grabado.
+    ///
+    /// # Retorno
+    /// La posición de la línea en `lineas()`.
+    pub fn agregar(
+        &mut self,
+        catalogo: &Catalogo,
+        producto: u32,
+        variante: u32,
+        cantidad: u32,
+        propiedades: &[(&str, &str)],
+    ) -> Result<usize, ErrorCarrito> {
+        if cantidad == 0 {
+            return Err(ErrorCarrito::CantidadInvalida(0));
+        }
+        self.validar_unidades(None, cantidad)?;
+        let propiedades: Vec<(String, String)> = propiedades
+            .iter()
+            .map(|(nombre, valor)| (nombre.to_string(), valor.to_string()))
+            .collect();
+        let existente = self.lineas.iter().position(|linea| {
+            linea.producto == producto
+                && linea.variante == variante
+                && linea.propiedades == propiedades
+        });
+        let en_carrito = self.unidades_de(producto, variante, None);
+        validar_cantidad(catalogo, producto, variante, en_carrito + cantidad as i64)?;
+
+        match existente {
+            Some(indice) => {
+                self.lineas[indice].cantidad += cantidad;
+                Ok(indice)
+            }
+            None => {
+                self.lineas.push(LineaCarrito {
+                    producto,
+                    variante,
+                    cantidad,
+                    propiedades,
+                });
+                Ok(self.lineas.len() - 1)
+            }
+        }
+    }
+
+    /// Cambia la cantidad de una línea; con cero la quita.
+    ///
+    /// Entra en pánico si `indice` está fuera de rango, igual que `Vec::remove`.
+    pub fn actualizar(
+        &mut self,
+        catalogo: &Catalogo,
+        indice: usize,
+        cantidad: u32,
+    ) -> Result<(), ErrorCarrito> {
+        if cantidad == 0 {
+            self.quitar(indice);
+            return Ok(());
+        }
+        self.validar_unidades(Some(indice), cantidad)?;
+        let linea = &self.lineas[indice];
+        let otras = self.unidades_de(linea.producto, linea.variante, Some(indice));
+        validar_cantidad(
+            catalogo,
+            linea.producto,
+            linea.variante,
+            otras + cantidad as i64,
+        )?;
+        self.lineas[indice].cantidad = cantidad;
+        Ok(())
+    }
+
+    /// Quita una línea; entra en pánico si `indice` está fuera de rango.
+    pub fn quitar(&mut self, indice: usize) -> LineaCarrito {
+        self.lineas.remove(indice)
+    }
+
+    /// Aplica un código de descuento; los códigos no distinguen mayúsculas y cada uno se
+    /// aplica una sola vez. Los de productos se calculan antes que los de pedido, y dentro
+    /// de cada grupo en el orden en que se aplicaron.
+    pub fn aplicar_codigo(&mut self, codigo: CodigoDescuento) -> Result<(), ErrorCarrito> {
+        codigo.validar()?;
+        if self.tiene_codigo(&codigo.codigo) {
+            return Err(ErrorCarrito::CodigoInvalido(format!(
+                "\"{}\": ya esta aplicado",
+                codigo.codigo
+            )));
+        }
+        self.codigos.push(codigo);
+        Ok(())
+    }
+
+    pub fn quitar_codigo(&mut self, codigo: &str) -> bool {
+        let antes = self.codigos.len();
+        self.codigos
+            .retain(|aplicado| !aplicado.codigo.eq_ignore_ascii_case(codigo));
+        self.codigos.len() != antes
+    }
+
+    fn tiene_codigo(&self, codigo: &str) -> bool {
+        self.codigos
+            .iter()
+            .any(|aplicado| aplicado.codigo.eq_ignore_ascii_case(codigo))
+    }
+
+    /// Calcula precios, descuentos y total sin envío (ver `Totales::con_envio`).
+    ///
+    /// Vuelve a revisar el inventario: si el catálogo cambió desde que se agregó una
+    /// línea, puede fallar con `LimiteCantidad`, `ProductoNoEncontrado` o
+    /// `VarianteNoEncontrada`.
+    pub fn totales(&self, catalogo: &Catalogo) -> Result<Totales, ErrorCarrito> {
+        let mut lineas = Vec::with_capacity(self.lineas.len());
+        let mut productos = Vec::with_capacity(self.lineas.len());
+        for (indice, linea) in self.lineas.iter().enumerate() {
+            let total_variante = self.unidades_de(linea.producto, linea.variante, Some(indice))
+                + linea.cantidad as i64;
+            let (producto, variante) =
+                validar_cantidad(catalogo, linea.producto, linea.variante, total_variante)?;
+            let subtotal = variante.precio_centavos * linea.cantidad as i64;
+            lineas.push(LineaTotal {
+                producto: producto.id,
+                variante: variante.id,
+                titulo: titulo_linea(producto, variante),
+                cantidad: linea.cantidad,
+                precio_unitario_centavos: variante.precio_centavos,
+                subtotal_centavos: subtotal,
+                descuento_centavos: 0,
+                total_centavos: subtotal,
+            });
+            productos.push(producto);
+        }
+        let subtotal: i64 = lineas.iter().map(|linea| linea.subtotal_centavos).sum();
+
+        let mut descuentos = Vec::with_capacity(self.codigos.len());
+        let (de_productos, de_pedido): (Vec<_>, Vec<_>) = self
+            .codigos
+            .iter()
+            .partition(|codigo| matches!(codigo.alcance, AlcanceDescuento::Productos(_)));
+        for codigo in de_productos {
+            let AlcanceDescuento::Productos(filtro) = &codigo.alcance else {
+                continue;
+            };
+            let mut centavos = 0;
+            if subtotal >= codigo.minimo_centavos {
+                for (linea, producto) in lineas.iter_mut().zip(&productos) {
+                    if filtro.cumple(producto) {
+                        let descuento = codigo.descontar(linea.total_centavos, linea.cantidad);
+                        linea.descuento_centavos += descuento;
+                        linea.total_centavos -= descuento;
+                        centavos += descuento;
+                    }
+                }
+            }
+            descuentos.push(DescuentoAplicado {
+                codigo: codigo.codigo.clone(),
+                centavos,
+            });
+        }
+        let mut restante: i64 = lineas.iter().map(|linea| linea.total_centavos).sum();
+        for codigo in de_pedido {
+            let centavos = if subtotal >= codigo.minimo_centavos {
+                codigo.descontar(restante, 0)
+            } else {
+                0
+            };
+            restante -= centavos;
+            descuentos.push(DescuentoAplicado {
+                codigo: codigo.codigo.clone(),
+                centavos,
+            });
+        }
+
+        Ok(Totales {
+            unidades: self.unidades(),
+            subtotal_centavos: subtotal,
+            descuento_centavos: subtotal - restante,
+            descuentos,
+            envio: None,
+            total_centavos: restante,
+            lineas,
+        })
+    }
+
+    /// Revisa que el carrito no pase de `u32::MAX` unidades con `cantidad` más en una línea,
+    /// sin contar la línea `excepto`; así ninguna suma de cantidades se desborda.
+    fn validar_unidades(&self, excepto: Option<usize>, cantidad: u32) -> Result<(), ErrorCarrito> {
+        self.lineas
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| Some(*i) != excepto)
+            .try_fold(cantidad, |total, (_, linea)| {
+                total.checked_add(linea.cantidad)
+            })
+            .map(|_| ())
+            .ok_or(ErrorCarrito::CantidadInvalida(cantidad))
+    }
+
+    /// Unidades de la variante en todo el carrito, sin contar la línea `excepto`.
+    ///
+    /// Los ids de variante solo son únicos dentro de un producto (`MatrizVariantes` los
+    /// numera por producto), así que se compara el par.
+    fn unidades_de(&self, producto: u32, variante: u32, excepto: Option<usize>) -> i64 {
+        self.lineas
+            .iter()
+            .enumerate()
+            .filter(|(i, linea)| {
+                linea.producto == producto && linea.variante == variante && Some(*i) != excepto
+            })
+            .map(|(_, linea)| linea.cantidad as i64)
+            .sum()
+    }
+}
+
+/// Busca la variante y revisa que haya `cantidad` unidades en inventario (las
+/// existencias negativas cuentan como cero).
+fn validar_cantidad(
+    catalogo: &Catalogo,
+    producto: u32,
+    variante: u32,
+    cantidad: i64,
+) -> Result<(&Product, &Variante), ErrorCarrito> {
+    let encontrado = catalogo
+        .por_id(producto)
+        .ok_or(ErrorCarrito::ProductoNoEncontrado(producto))?;
+    let variante = encontrado
+        .variante(variante)
+        .ok_or(ErrorCarrito::VarianteNoEncontrada { producto, variante })?;
+    let maximo = variante.inventario.max(0);
+    if cantidad > maximo {
+        return Err(ErrorCarrito::LimiteCantidad {
+            producto,
+            variante: variante.id,
+            maximo,
+            solicitado: cantidad,
+        });
+    }
+    Ok((encontrado, variante))
+}
+
+fn titulo_linea(producto: &Product, variante: &Variante) -> String {
+    if variante.valores.is_empty() {
+        producto.titulo.clone()
+    } else {
+        format!("{} - {}", producto.titulo, variante.titulo())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn catalogo() -> Catalogo {
+        Catalogo::desde_productos(vec![
+            Product::new(1, "simple-tee")
+                .con_titulo("Simple Tee")
+                .con_etiquetas(&["verano"])
+                .con_opciones(&["Talla"])
+                .con_variante(
+                    Variante::new(10, 2000)
+                        .con_valores(&["M"])
+                        .con_inventario(5),
+                )
+                .con_variante(
+                    Variante::new(11, 2500)
+                        .con_valores(&["L"])
+                        .con_inventario(0),
+                ),
+            Product::new(2, "taza")
+                .con_titulo("Taza")
+                .con_variante(Variante::new(20, 999).con_inventario(10)),
+        ])
+        .unwrap()
+    }
+
+    #[test]
+    fn test_lineas_y_limites() {
+        let catalogo = catalogo();
+        let mut carrito = Carrito::new();
+        assert_eq!(carrito.agregar(&catalogo, 1, 10, 2, &[]), Ok(0));
+        assert_eq!(carrito.agregar(&catalogo, 1, 10, 1, &[]), Ok(0));
+        assert_eq!(
+            carrito.agregar(&catalogo, 1, 10, 1, &[("Grabado", "Ana")]),
+            Ok(1)
+        );
+        assert_eq!(carrito.lineas()[0].cantidad, 3);
+        assert_eq!(carrito.unidades(), 4);
+
+        assert_eq!(
+            carrito.agregar(&catalogo, 1, 10, 2, &[]),
+            Err(ErrorCarrito::LimiteCantidad {
+                producto: 1,
+                variante: 10,
+                maximo: 5,
+                solicitado: 6
+            })
+        );
+        assert!(carrito.agregar(&catalogo, 1, 11, 1, &[]).is_err());
+        assert_eq!(
+            carrito.agregar(&catalogo, 1, 20, 1, &[]),
+            Err(ErrorCarrito::VarianteNoEncontrada {
+                producto: 1,
+                variante: 20
+            })
+        );
+        assert_eq!(
+            carrito.agregar(&catalogo, 2, 20, 0, &[]),
+            Err(ErrorCarrito::CantidadInvalida(0))
+        );
+
+        assert!(carrito.actualizar(&catalogo, 0, 5).is_err());
+        carrito.actualizar(&catalogo, 0, 4).unwrap();
+        carrito.actualizar(&catalogo, 1, 0).unwrap();
+        assert_eq!(carrito.lineas().len(), 1);
+        assert_eq!(carrito.unidades(), 4);
+    }
+
+    #[test]
+    fn test_cantidad_sin_desbordar() {
+        let catalogo = Catalogo::desde_productos(vec![
+            Product::new(1, "tee")
+                .con_variante(Variante::new(1, 100).con_inventario(i64::MAX))
+                .con_variante(Variante::new(2, 100).con_inventario(i64::MAX)),
+        ])
+        .unwrap();
+        let mut carrito = Carrito::new();
+        carrito.agregar(&catalogo, 1, 1, u32::MAX, &[]).unwrap();
+        assert_eq!(
+            carrito.agregar(&catalogo, 1, 1, 1, &[]),
+            Err(ErrorCarrito::CantidadInvalida(1))
+        );
+        assert_eq!(
+            carrito.agregar(&catalogo, 1, 2, 1, &[]),
+            Err(ErrorCarrito::CantidadInvalida(1))
+        );
+        carrito.actualizar(&catalogo, 0, u32::MAX - 1).unwrap();
+        carrito.agregar(&catalogo, 1, 2, 1, &[]).unwrap();
+        assert_eq!(carrito.unidades(), u32::MAX);
+        assert_eq!(
+            carrito.actualizar(&catalogo, 1, 2),
+            Err(ErrorCarrito::CantidadInvalida(2))
+        );
+        assert_eq!(carrito.totales(&catalogo).unwrap().unidades, u32::MAX);
+    }
+
+    #[test]
+    fn test_ids_de_variante_repetidos_entre_productos() {
+        // `MatrizVariantes` numera las variantes por producto, así que dos productos
+        // pueden tener una variante con el mismo id.
+        let catalogo = Catalogo::desde_productos(vec![
+            Product::new(1, "tee").con_variante(Variante::new(1, 2000).con_inventario(2)),
+            Product::new(2, "taza").con_variante(Variante::new(1, 999).con_inventario(3)),
+        ])
+        .unwrap();
+        let mut carrito = Carrito::new();
+        carrito.agregar(&catalogo, 1, 1, 2, &[]).unwrap();
+        assert_eq!(carrito.agregar(&catalogo, 2, 1, 3, &[]), Ok(1));
+        carrito.actualizar(&catalogo, 1, 3).unwrap();
+        assert_eq!(carrito.totales(&catalogo).unwrap().total_centavos, 6997);
+    }
+
+    #[test]
+    fn test_totales_con_descuentos() {
+        let catalogo = catalogo();
+        let mut carrito = Carrito::new();
+        carrito.agregar(&catalogo, 1, 10, 3, &[]).unwrap();
+        carrito.agregar(&catalogo, 2, 20, 1, &[]).unwrap();
+
+        carrito
+            .aplicar_codigo(CodigoDescuento::pedido("MENOS5", Descuento::Monto(500)))
+            .unwrap();
+        carrito
+            .aplicar_codigo(CodigoDescuento::productos(
+                "VERANO15",
+                Descuento::Porcentaje(15),
+                Filtro::Etiqueta("verano".to_string()),
+            ))
+            .unwrap();
+        carrito
+            .aplicar_codigo(
+                CodigoDescuento::pedido("GRANDE", Descuento::Porcentaje(50)).con_minimo(100_000),
+            )
+            .unwrap();
+        assert!(
+            carrito
+                .aplicar_codigo(CodigoDescuento::pedido("menos5", Descuento::Monto(1)))
+                .is_err()
+        );
+
+        let totales = carrito.totales(&catalogo).unwrap();
+        assert_eq!(totales.subtotal_centavos, 6999);
+        assert_eq!(totales.lineas[0].titulo, "Simple Tee - M");
+        assert_eq!(totales.lineas[0].descuento_centavos, 900);
+        assert_eq!(totales.lineas[1].titulo, "Taza");
+        assert_eq!(totales.lineas[1].total_centavos, 999);
+        let descuentos: Vec<(&str, i64)> = totales
+            .descuentos
+            .iter()
+            .map(|d| (d.codigo.as_str(), d.centavos))
+            .collect();
+        assert_eq!(
+            descuentos,
+            vec![("VERANO15", 900), ("MENOS5", 500), ("GRANDE", 0)]
+        );
+        assert_eq!(totales.descuento_centavos, 1400);
+        assert_eq!(totales.total_centavos, 5599);
+
+        assert!(carrito.quitar_codigo("menos5"));
+        assert_eq!(carrito.totales(&catalogo).unwrap().total_centavos, 6099);
+    }
+
+    #[test]
+    fn test_envio() {
+        let catalogo = catalogo();
+        let mut carrito = Carrito::new();
+        carrito.agregar(&catalogo, 2, 20, 2, &[]).unwrap();
+        let totales = carrito.totales(&catalogo).unwrap();
+
+        let estandar = TarifaFija {
+            nombre: "Estandar".to_string(),
+            centavos: 499,
+        };
+        let gratis = TarifaPorPrecio {
+            nombre: "Gratis".to_string(),
+            desde_centavos: 5000,
+            hasta_centavos: None,
+            centavos: 0,
+        };
+        let opciones = totales.opciones_envio(&[&estandar, &gratis]);
+        assert_eq!(opciones.len(), 1);
+        assert_eq!(
+            totales.clone().con_envio(&gratis),
+            Err(ErrorCarrito::EnvioNoDisponible("Gratis".to_string()))
+        );
+        let con_envio = totales.con_envio(&estandar).unwrap();
+        assert_eq!(con_envio.total_centavos, 1998 + 499);
+
+        carrito.actualizar(&catalogo, 0, 6).unwrap();
+        let totales = carrito.totales(&catalogo).unwrap();
+        let opciones = totales.opciones_envio(&[&estandar, &gratis]);
+        assert_eq!(opciones[0].nombre, "Gratis");
+        assert_eq!(totales.con_envio(&gratis).unwrap().total_centavos, 5994);
+    }
+}
@@ -0,0 +1,252 @@
+// src/catalogo.rs
+
+use std::collections::HashMap;
+
+use crate::Product;
+use crate::error::ErrorImportacion;
+use crate::handle::validar_handle;
+
+/// Colección de productos con búsqueda en O(1) por id y por handle.
+///
+/// Garantiza que todos los handles sean válidos (ver `validar_handle`) y que no haya ids
+/// ni handles repetidos. Los productos se guardan en orden de
+/// inserción; para modificarlos se usa `modificar` o `cambiar_handle`, que mantienen los
+/// índices al día.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Catalogo {
+    productos: Vec<Product>,
+    /// Posición en `productos` de cada id.
+    por_id: HashMap<u32, usize>,
+    /// Id del producto que tiene cada handle.
+    por_handle: HashMap<String, u32>,
+}
+
+impl Catalogo {
+    pub fn new() -> Catalogo {
+        Catalogo::default()
+    }
+
+    /// Crea un catálogo con los productos en el orden dado.
+    ///
+    /// # Retorno
+    /// Retorna `Err` con el primer handle inválido o id o handle repetido.
+    pub fn desde_productos(productos: Vec<Product>) -> Result<Catalogo, ErrorImportacion> {
+        let mut catalogo = Catalogo::new();
+        for producto in productos {
+            catalogo.insertar(producto)?;
+        }
+        Ok(catalogo)
+    }
+
+    /// Agrega un producto al final del catálogo.
+    ///
+    /// # Retorno
+    /// Retorna `HandleInvalido` si el handle no es válido, o `IdDuplicado` o
+    /// `HandleDuplicado` si otro producto ya tiene ese id o handle.
+    pub fn insertar(&mut self, producto: Product) -> Result<(), ErrorImportacion> {
+        validar_handle(&producto.handle)?;
+        if self.por_id.contains_key(&producto.id) {
+            return Err(ErrorImportacion::IdDuplicado(producto.id));
+        }
+        if self.por_handle.contains_key(&producto.handle) {
+            return Err(ErrorImportacion::HandleDuplicado(producto.handle));
+        }
+        self.por_id.insert(producto.id, self.productos.len());
+        self.por_handle.insert(producto.handle.clone(), producto.id);
+        self.productos.push(producto);
+        Ok(())
+    }
+
+    /// Quita un producto; los demás conservan su orden. Cuesta O(n) por reindexar.
+    pub fn eliminar(&mut self, id: u32) -> Option<Product> {
+        let posicion = self.por_id.remove(&id)?;
+        let producto = self.productos.remove(posicion);
+        self.por_handle.remove(&producto.handle);
+        for (i, restante) in self.productos.iter().enumerate().skip(posicion) {
+            self.por_id.insert(restante.id, i);
+        }
+        Some(producto)
+    }
+
+    pub fn por_id(&self, id: u32) -> Option<&Product> {
+        self.por_id
+            .get(&id)
+            .map(|&posicion| &self.productos[posicion])
+    }
+
+    pub fn por_handle(&self, handle: &str) -> Option<&Product> {
+        self.por_handle.get(handle).and_then(|&id| self.por_id(id))
+    }
+
+    pub fn contiene(&self, id: u32) -> bool {
+        self.por_id.contains_key(&id)
+    }
+
+    /// Cambia el handle de un producto y actualiza el índice.
+    pub fn cambiar_handle(&mut self, id: u32, nuevo: &str) -> Result<(), ErrorImportacion> {
+        self.modificar(id, |producto| producto.handle = nuevo.to_string())
+    }
+
+    /// Aplica `cambio` al producto y actualiza el índice de handles si cambió.
+    ///
+    /// El id no se puede cambiar: si `cambio` lo modifica, se restaura. Si el nuevo handle
+    /// no es válido (`HandleInvalido`) o ya lo usa otro producto (`HandleDuplicado`), se
+    /// restaura el anterior y se retorna el error (los demás cambios se conservan).
+    pub fn modificar<F>(&mut self, id: u32, cambio: F) -> Result<(), ErrorImportacion>
+    where
+        F: FnOnce(&mut Product),
+    {
+        let posicion = *self
+            .por_id
+            .get(&id)
+            .ok_or(ErrorImportacion::ProductoNoEncontrado(id))?;
+        let producto = &mut self.productos[posicion];
+        let anterior = producto.handle.clone();
+        cambio(producto);
+        producto.id = id;
+
+        if producto.handle == anterior {
+            return Ok(());
+        }
+        if let Err(error) = validar_handle(&producto.handle) {
+            producto.handle = anterior;
+            return Err(error);
+        }
+        if self.por_handle.contains_key(&producto.handle) {
+            let repetido = std::mem::replace(&mut producto.handle, anterior);
+            return Err(ErrorImportacion::HandleDuplicado(repetido));
+        }
+        self.por_handle.remove(&anterior);
+        self.por_handle.insert(producto.handle.clone(), id);
+        Ok(())
+    }
+
+    pub fn len(&self) -> usize {
+        self.productos.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.productos.is_empty()
+    }
+
+    /// Productos en orden de inserción.
+    pub fn iter(&self) -> std::slice::Iter<'_, Product> {
+        self.productos.iter()
+    }
+
+    /// Productos ordenados por id.
+    pub fn iter_por_id(&self) -> impl Iterator<Item = &Product> {
+        let mut ordenados: Vec<&Product> = self.productos.iter().collect();
+        ordenados.sort_unstable_by_key(|p| p.id);
+        ordenados.into_iter()
+    }
+
+    /// Productos ordenados alfabéticamente por handle.
+    pub fn iter_por_handle(&self) -> impl Iterator<Item = &Product> {
+        let mut ordenados: Vec<&Product> = self.productos.iter().collect();
+        ordenados.sort_unstable_by(|a, b| a.handle.cmp(&b.handle));
+        ordenados.into_iter()
+    }
+
+    /// Los productos como slice, en orden de inserción.
+    pub fn como_slice(&self) -> &[Product] {
+        &self.productos
+    }
+}
+
+impl<'a> IntoIterator for &'a Catalogo {
+    type Item = &'a Product;
+    type IntoIter = std::slice::Iter<'a, Product>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn catalogo() -> Catalogo {
+        Catalogo::desde_productos(vec![
+            Product::new(42, "simple-tee"),
+            Product::new(7, "casual-tee"),
+            Product::new(256, "gorra"),
+        ])
+        .unwrap()
+    }
+
+    #[test]
+    fn test_busqueda() {
+        let catalogo = catalogo();
+        assert_eq!(catalogo.por_id(7).unwrap().handle, "casual-tee");
+        assert_eq!(catalogo.por_handle("gorra").unwrap().id, 256);
+        assert!(catalogo.por_id(1).is_none());
+        assert!(catalogo.por_handle("taza").is_none());
+    }
+
+    #[test]
+    fn test_duplicados() {
+        let mut catalogo = catalogo();
+        assert_eq!(
+            catalogo.insertar(Product::new(42, "otro")),
+            Err(ErrorImportacion::IdDuplicado(42))
+        );
+        assert_eq!(
+            catalogo.insertar(Product::new(1, "gorra")),
+            Err(ErrorImportacion::HandleDuplicado("gorra".to_string()))
+        );
+        assert!(matches!(
+            catalogo.insertar(Product::new(1, "Tee Básica")),
+            Err(ErrorImportacion::HandleInvalido { .. })
+        ));
+        assert_eq!(catalogo.len(), 3);
+    }
+
+    #[test]
+    fn test_cambiar_handle() {
+        let mut catalogo = catalogo();
+        catalogo.cambiar_handle(42, "tee-basica").unwrap();
+        assert!(catalogo.por_handle("simple-tee").is_none());
+        assert_eq!(catalogo.por_handle("tee-basica").unwrap().id, 42);
+
+        assert_eq!(
+            catalogo.cambiar_handle(42, "gorra"),
+            Err(ErrorImportacion::HandleDuplicado("gorra".to_string()))
+        );
+        assert_eq!(catalogo.por_id(42).unwrap().handle, "tee-basica");
+        assert!(matches!(
+            catalogo.cambiar_handle(42, "Tee Básica"),
+            Err(ErrorImportacion::HandleInvalido { .. })
+        ));
+        assert_eq!(catalogo.por_id(42).unwrap().handle, "tee-basica");
+        assert!(catalogo.por_handle("Tee Básica").is_none());
+        assert_eq!(
+            catalogo.cambiar_handle(1, "x"),
+            Err(ErrorImportacion::ProductoNoEncontrado(1))
+        );
+
+        catalogo
+            .modificar(7, |producto| {
+                producto.id = 99;
+                producto.titulo = "Casual".to_string();
+            })
+            .unwrap();
+        assert_eq!(catalogo.por_handle("casual-tee").unwrap().titulo, "Casual");
+        assert!(catalogo.contiene(7) && !catalogo.contiene(99));
+    }
+
+    #[test]
+    fn test_orden_y_eliminar() {
+        let mut catalogo = catalogo();
+        let ids = |iter: &mut dyn Iterator<Item = &Product>| iter.map(|p| p.id).collect::<Vec<_>>();
+        assert_eq!(ids(&mut catalogo.iter()), vec![42, 7, 256]);
+        assert_eq!(ids(&mut catalogo.iter_por_id()), vec![7, 42, 256]);
+        assert_eq!(ids(&mut catalogo.iter_por_handle()), vec![7, 256, 42]);
+
+        assert_eq!(catalogo.eliminar(42).unwrap().handle, "simple-tee");
+        assert_eq!(catalogo.eliminar(42), None);
+        assert_eq!(catalogo.por_id(256).unwrap().handle, "gorra");
+        assert!(catalogo.por_handle("simple-tee").is_none());
+    }
+}
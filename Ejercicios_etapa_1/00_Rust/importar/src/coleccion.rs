@@ -0,0 +1,320 @@
+// src/coleccion.rs
+
+use std::collections::HashMap;
+
+use crate::Product;
+use crate::catalogo::Catalogo;
+use crate::consulta::Filtro;
+
+/// Condición de una colección inteligente.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Regla {
+    /// El producto tiene la etiqueta (sin distinguir mayúsculas).
+    EtiquetaIgual(String),
+    /// El precio "desde" del producto es menor que estos centavos.
+    PrecioMenorQue(i64),
+    /// El proveedor es alguno de la lista (sin distinguir mayúsculas).
+    ProveedorEn(Vec<String>),
+}
+
+impl Regla {
+    /// La regla como filtro de `Consulta`, para evaluarla igual que una búsqueda.
+    pub fn a_filtro(&self) -> Filtro {
+        match self {
+            Regla::EtiquetaIgual(etiqueta) => Filtro::Etiqueta(etiqueta.clone()),
+            Regla::PrecioMenorQue(centavos) => Filtro::RangoPrecio {
+                desde: None,
+                hasta: Some(*centavos),
+            },
+            Regla::ProveedorEn(proveedores) => Filtro::O(
+                proveedores
+                    .iter()
+                    .map(|proveedor| Filtro::Proveedor(proveedor.clone()))
+                    .collect(),
+            ),
+        }
+    }
+}
+
+/// Cómo se eligen los productos de una colección.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TipoColeccion {
+    /// Lista fija de ids, en el orden en que se agregaron.
+    Manual(Vec<u32>),
+    /// Los productos del catálogo que cumplen todas las reglas (o alguna, si
+    /// `cualquiera` es verdadero).
+    Inteligente {
+        reglas: Vec<Regla>,
+        cualquiera: bool,
+    },
+}
+
+/// Orden en que se muestran los productos de una colección.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum OrdenColeccion {
+    /// El orden de la lista en una colección manual; en una inteligente, primero los
+    /// productos fijados con `fijar_posiciones` y luego el resto en el orden del catálogo.
+    #[default]
+    Manual,
+    /// Más unidades vendidas primero.
+    MasVendidos,
+    /// Precio "desde" de menor a mayor; los productos sin precio van al final.
+    PrecioAscendente,
+    PrecioDescendente,
+    /// Por título, sin distinguir mayúsculas.
+    Alfabetico,
+}
+
+/// Agrupación de productos como las colecciones de Shopify ("Verano", "Menos de $20"...).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Coleccion {
+    pub handle: String,
+    pub titulo: String,
+    pub tipo: TipoColeccion,
+    pub orden: OrdenColeccion,
+    /// Ids fijados al principio de una colección inteligente con orden manual.
+    posiciones: Vec<u32>,
+}
+
+impl Coleccion {
+    pub fn manual(handle: &str, titulo: &str, ids: &[u32]) -> Coleccion {
+        Coleccion::new(handle, titulo, TipoColeccion::Manual(ids.to_vec()))
+    }
+
+    /// Colección inteligente que exige todas las reglas.
+    pub fn inteligente(handle: &str, titulo: &str, reglas: Vec<Regla>) -> Coleccion {
+        Coleccion::new(
+            handle,
+            titulo,
+            TipoColeccion::Inteligente {
+                reglas,
+                cualquiera: false,
+            },
+        )
+    }
+
+    fn new(handle: &str, titulo: &str, tipo: TipoColeccion) -> Coleccion {
+        Coleccion {
+            handle: handle.to_string(),
+            titulo: titulo.to_string(),
+            tipo,
+            orden: OrdenColeccion::default(),
+            posiciones: Vec::new(),
+        }
+    }
+
+    pub fn con_orden(mut self, orden: OrdenColeccion) -> Coleccion {
+        self.orden = orden;
+        self
+    }
+
+    /// En una colección inteligente, basta con cumplir una de las reglas.
+    pub fn con_cualquier_regla(mut self) -> Coleccion {
+        if let TipoColeccion::Inteligente { cualquiera, .. } = &mut self.tipo {
+            *cualquiera = true;
+        }
+        self
+    }
+
+    /// Agrega un producto al final de una colección manual; no hace nada si ya estaba o
+    /// si la colección es inteligente.
+    pub fn agregar(&mut self, id: u32) {
+        if let TipoColeccion::Manual(ids) = &mut self.tipo
+            && !ids.contains(&id)
+        {
+            ids.push(id);
+        }
+    }
+
+    pub fn quitar(&mut self, id: u32) {
+        if let TipoColeccion::Manual(ids) = &mut self.tipo {
+            ids.retain(|&otro| otro != id);
+        }
+        self.posiciones.retain(|&otro| otro != id);
+    }
+
+    /// Mueve un producto a `posicion` (empezando en 0) dentro del orden manual.
+    ///
+    /// En una colección inteligente lo fija en esa posición entre los fijados; los
+    /// productos no fijados van después, en el orden del catálogo.
+    pub fn mover(&mut self, id: u32, posicion: usize) {
+        let lista = match &mut self.tipo {
+            TipoColeccion::Manual(ids) => ids,
+            TipoColeccion::Inteligente { .. } => &mut self.posiciones,
+        };
+        lista.retain(|&otro| otro != id);
+        lista.insert(posicion.min(lista.len()), id);
+    }
+
+    /// Indica si el producto pertenece a la colección.
+    pub fn contiene(&self, producto: &Product) -> bool {
+        match &self.tipo {
+            TipoColeccion::Manual(ids) => ids.contains(&producto.id),
+            TipoColeccion::Inteligente { reglas, cualquiera } => {
+                let mut cumple = reglas.iter().map(|regla| regla.a_filtro().cumple(producto));
+                if *cualquiera {
+                    cumple.any(|c| c)
+                } else {
+                    cumple.all(|c| c)
+                }
+            }
+        }
+    }
+
+    /// Productos de la colección en su orden, sin datos de ventas (con
+    /// `OrdenColeccion::MasVendidos` quedan en el orden manual).
+    pub fn productos<'a>(&self, catalogo: &'a Catalogo) -> Vec<&'a Product> {
+        self.productos_con_ventas(catalogo, &HashMap::new())
+    }
+
+    /// Productos de la colección en su orden; `ventas` da las unidades vendidas de cada
+    /// producto para `OrdenColeccion::MasVendidos` (los que no aparecen cuentan como cero).
+    ///
+    /// Los empates conservan el orden manual.
+    pub fn productos_con_ventas<'a>(
+        &self,
+        catalogo: &'a Catalogo,
+        ventas: &HashMap<u32, u64>,
+    ) -> Vec<&'a Product> {
+        let mut productos: Vec<&Product> = match &self.tipo {
+            // Los ids que ya no están en el catálogo se omiten.
+            TipoColeccion::Manual(ids) => {
+                ids.iter().filter_map(|&id| catalogo.por_id(id)).collect()
+            }
+            TipoColeccion::Inteligente { .. } => {
+                let fijados = self
+                    .posiciones
+                    .iter()
+                    .filter_map(|&id| catalogo.por_id(id))
+                    .filter(|p| self.contiene(p));
+                let resto = catalogo
+                    .iter()
+                    .filter(|p| !self.posiciones.contains(&p.id) && self.contiene(p));
+                fijados.chain(resto).collect()
+            }
+        };
+
+        match self.orden {
+            OrdenColeccion::Manual => {}
+            OrdenColeccion::MasVendidos => productos
+                .sort_by_key(|p| std::cmp::Reverse(ventas.get(&p.id).copied().unwrap_or(0))),
+            // `None` es menor que cualquier `Some`; se invierte para dejar los sin precio al final.
+            OrdenColeccion::PrecioAscendente => {
+                productos.sort_by_key(|p| (p.precio_minimo().is_none(), p.precio_minimo()))
+            }
+            OrdenColeccion::PrecioDescendente => productos.sort_by_key(|p| {
+                (
+                    p.precio_minimo().is_none(),
+                    std::cmp::Reverse(p.precio_minimo()),
+                )
+            }),
+            OrdenColeccion::Alfabetico => productos.sort_by_key(|p| p.titulo.to_lowercase()),
+        }
+        productos
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Variante;
+
+    fn catalogo() -> Catalogo {
+        let producto = |id: u32, titulo: &str, precio: i64, proveedor: &str, etiquetas: &[&str]| {
+            Product::new(id, &titulo.to_lowercase())
+                .con_titulo(titulo)
+                .con_proveedor(proveedor)
+                .con_etiquetas(etiquetas)
+                .con_variante(Variante::new(id, precio))
+        };
+        Catalogo::desde_productos(vec![
+            producto(1, "Tee", 1500, "Acme", &["verano"]),
+            producto(2, "Gorra", 900, "Otra", &["Verano"]),
+            producto(3, "Hoodie", 4500, "Acme", &["invierno"]),
+            producto(4, "Sandalias", 2500, "Playa", &["verano"]),
+            Product::new(5, "borrador")
+                .con_titulo("Borrador")
+                .con_etiquetas(&["verano"]),
+        ])
+        .unwrap()
+    }
+
+    fn ids(productos: &[&Product]) -> Vec<u32> {
+        productos.iter().map(|p| p.id).collect()
+    }
+
+    #[test]
+    fn test_coleccion_manual() {
+        let catalogo = catalogo();
+        let mut coleccion = Coleccion::manual("destacados", "Destacados", &[3, 1, 99]);
+        coleccion.agregar(2);
+        coleccion.agregar(3);
+        coleccion.mover(2, 0);
+        assert_eq!(ids(&coleccion.productos(&catalogo)), vec![2, 3, 1]);
+
+        coleccion.quitar(3);
+        let coleccion = coleccion.con_orden(OrdenColeccion::PrecioAscendente);
+        assert_eq!(ids(&coleccion.productos(&catalogo)), vec![2, 1]);
+    }
+
+    #[test]
+    fn test_coleccion_inteligente() {
+        let catalogo = catalogo();
+        let verano = Coleccion::inteligente(
+            "verano-barato",
+            "Verano por menos de $20",
+            vec![
+                Regla::EtiquetaIgual("verano".to_string()),
+                Regla::PrecioMenorQue(2000),
+            ],
+        );
+        assert_eq!(ids(&verano.productos(&catalogo)), vec![1, 2]);
+
+        let proveedores = Coleccion::inteligente(
+            "marcas",
+            "Marcas",
+            vec![Regla::ProveedorEn(vec![
+                "acme".to_string(),
+                "Playa".to_string(),
+            ])],
+        );
+        assert_eq!(ids(&proveedores.productos(&catalogo)), vec![1, 3, 4]);
+
+        let cualquiera = Coleccion::inteligente(
+            "ofertas",
+            "Ofertas",
+            vec![
+                Regla::PrecioMenorQue(1000),
+                Regla::EtiquetaIgual("invierno".to_string()),
+            ],
+        )
+        .con_cualquier_regla();
+        assert_eq!(ids(&cualquiera.productos(&catalogo)), vec![2, 3]);
+    }
+
+    #[test]
+    fn test_ordenes() {
+        let catalogo = catalogo();
+        let mut verano = Coleccion::inteligente(
+            "verano",
+            "Verano",
+            vec![Regla::EtiquetaIgual("verano".to_string())],
+        );
+        verano.mover(4, 0);
+        assert_eq!(ids(&verano.productos(&catalogo)), vec![4, 1, 2, 5]);
+
+        let ventas = HashMap::from([(2, 30), (5, 30), (1, 10)]);
+        let verano = verano.con_orden(OrdenColeccion::MasVendidos);
+        assert_eq!(
+            ids(&verano.productos_con_ventas(&catalogo, &ventas)),
+            vec![2, 5, 1, 4]
+        );
+
+        let verano = verano.con_orden(OrdenColeccion::PrecioDescendente);
+        assert_eq!(ids(&verano.productos(&catalogo)), vec![4, 1, 2, 5]);
+        let verano = verano.con_orden(OrdenColeccion::PrecioAscendente);
+        assert_eq!(ids(&verano.productos(&catalogo)), vec![2, 1, 4, 5]);
+        let verano = verano.con_orden(OrdenColeccion::Alfabetico);
+        assert_eq!(ids(&verano.productos(&catalogo)), vec![5, 2, 4, 1]);
+    }
+}
@@ -0,0 +1,418 @@
+// src/consulta.rs
+
+use std::cmp::Ordering;
+
+use crate::Product;
+use crate::handle::slugify;
+
+/// Condición sobre un producto. Se combinan con `Y`, `O` y `No`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Filtro {
+    /// Tiene la etiqueta (sin distinguir mayúsculas).
+    Etiqueta(String),
+    /// Su proveedor es este (sin distinguir mayúsculas).
+    Proveedor(String),
+    /// Su precio "desde" (el de la variante más barata) está en `[desde, hasta)`.
+    /// Los productos sin variantes no cumplen ningún rango.
+    RangoPrecio {
+        desde: Option<i64>,
+        hasta: Option<i64>,
+    },
+    /// Alguna variante tiene existencias.
+    Disponible,
+    /// El texto aparece en el título o el handle, sin distinguir mayúsculas ni acentos.
+    Texto(String),
+    Y(Vec<Filtro>),
+    O(Vec<Filtro>),
+    No(Box<Filtro>),
+}
+
+impl Filtro {
+    pub fn cumple(&self, producto: &Product) -> bool {
+        match self {
+            Filtro::Etiqueta(etiqueta) => producto.tiene_etiqueta(etiqueta),
+            Filtro::Proveedor(proveedor) => producto.proveedor.eq_ignore_ascii_case(proveedor),
+            Filtro::RangoPrecio { desde, hasta } => {
+                producto.precio_minimo().is_some_and(|precio| {
+                    desde.is_none_or(|desde| precio >= desde)
+                        && hasta.is_none_or(|hasta| precio < hasta)
+                })
+            }
+            Filtro::Disponible => producto.disponible(),
+            Filtro::Texto(texto) => {
+                // `slugify` normaliza acentos, mayúsculas y separadores de la misma forma en
+                // la búsqueda y en el título: "Básica" encuentra "camiseta-basica".
+                let buscado = slugify(texto);
+                slugify(&producto.titulo).contains(&buscado) || producto.handle.contains(&buscado)
+            }
+            Filtro::Y(filtros) => filtros.iter().all(|f| f.cumple(producto)),
+            Filtro::O(filtros) => filtros.iter().any(|f| f.cumple(producto)),
+            Filtro::No(filtro) => !filtro.cumple(producto),
+        }
+    }
+}
+
+/// Campo por el que se ordenan los resultados.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CampoOrden {
+    /// Precio "desde"; los productos sin variantes van al final.
+    Precio,
+    /// Título sin distinguir mayúsculas.
+    Titulo,
+    Handle,
+    Proveedor,
+    Inventario,
+    Id,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Orden {
+    pub campo: CampoOrden,
+    pub descendente: bool,
+}
+
+/// Valor de un producto en una clave de orden.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+enum Valor {
+    Numero(i64),
+    Texto(String),
+    /// Precio de un producto sin variantes; va después de cualquier número.
+    SinPrecio,
+}
+
+impl CampoOrden {
+    fn valor(self, producto: &Product) -> Valor {
+        match self {
+            CampoOrden::Precio => producto
+                .precio_minimo()
+                .map_or(Valor::SinPrecio, Valor::Numero),
+            CampoOrden::Titulo => Valor::Texto(producto.titulo.to_lowercase()),
+            CampoOrden::Handle => Valor::Texto(producto.handle.clone()),
+            CampoOrden::Proveedor => Valor::Texto(producto.proveedor.to_lowercase()),
+            CampoOrden::Inventario => Valor::Numero(producto.inventario_total()),
+            CampoOrden::Id => Valor::Numero(i64::from(producto.id)),
+        }
+    }
+}
+
+impl Orden {
+    fn comparar(&self, a: &Valor, b: &Valor) -> Ordering {
+        let orden = a.cmp(b);
+        // Sin precio siempre al final, también en orden descendente.
+        if self.descendente && *a != Valor::SinPrecio && *b != Valor::SinPrecio {
+            orden.reverse()
+        } else {
+            orden
+        }
+    }
+}
+
+/// Dónde terminó una página: los valores de las claves de orden y el id de su último
+/// producto. Se obtiene de `Resultado::siguiente_cursor` y se pasa a `Consulta::despues_de`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cursor {
+    valores: Vec<Valor>,
+    id: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Paginacion {
+    Desplazamiento(usize),
+    Cursor(Cursor),
+}
+
+/// Búsqueda sobre productos: filtros que deben cumplirse todos, varias claves de orden y
+/// paginación por desplazamiento o por cursor.
+///
+/// ```text
+/// Consulta::new()
+///     .etiqueta("summer")
+///     .texto("tee")
+///     .precio_menor_que(2000)
+///     .ordenar_por(CampoOrden::Precio)
+///     .pagina(2, 20)
+///     .ejecutar(&catalogo)
+/// ```
+///
+/// Los resultados siempre desempatan por id, así que el orden es estable entre páginas.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Consulta {
+    filtros: Vec<Filtro>,
+    orden: Vec<Orden>,
+    paginacion: Paginacion,
+    limite: Option<usize>,
+}
+
+/// Una página de resultados.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Resultado<'a> {
+    pub productos: Vec<&'a Product>,
+    /// Cuántos productos cumplen los filtros, sin contar la paginación.
+    pub total: usize,
+    /// Cursor para pedir la página siguiente con `despues_de`, o `None` si es la última.
+    pub siguiente_cursor: Option<Cursor>,
+}
+
+impl Default for Consulta {
+    fn default() -> Consulta {
+        Consulta::new()
+    }
+}
+
+impl Consulta {
+    /// Consulta sin filtros, ordenada por id y sin límite.
+    pub fn new() -> Consulta {
+        Consulta {
+            filtros: Vec::new(),
+            orden: Vec::new(),
+            paginacion: Paginacion::Desplazamiento(0),
+            limite: None,
+        }
+    }
+
+    pub fn filtrar(mut self, filtro: Filtro) -> Consulta {
+        self.filtros.push(filtro);
+        self
+    }
+
+    pub fn etiqueta(self, etiqueta: &str) -> Consulta {
+        self.filtrar(Filtro::Etiqueta(etiqueta.to_string()))
+    }
+
+    pub fn proveedor(self, proveedor: &str) -> Consulta {
+        self.filtrar(Filtro::Proveedor(proveedor.to_string()))
+    }
+
+    /// Precio "desde" mayor o igual que `centavos`.
+    pub fn precio_desde(self, centavos: i64) -> Consulta {
+        self.filtrar(Filtro::RangoPrecio {
+            desde: Some(centavos),
+            hasta: None,
+        })
+    }
+
+    /// Precio "desde" estrictamente menor que `centavos`.
+    pub fn precio_menor_que(self, centavos: i64) -> Consulta {
+        self.filtrar(Filtro::RangoPrecio {
+            desde: None,
+            hasta: Some(centavos),
+        })
+    }
+
+    pub fn disponibles(self) -> Consulta {
+        self.filtrar(Filtro::Disponible)
+    }
+
+    pub fn texto(self, texto: &str) -> Consulta {
+        self.filtrar(Filtro::Texto(texto.to_string()))
+    }
+
+    /// Agrega una clave de orden ascendente; las claves se aplican en el orden agregado.
+    pub fn ordenar_por(mut self, campo: CampoOrden) -> Consulta {
+        self.orden.push(Orden {
+            campo,
+            descendente: false,
+        });
+        self
+    }
+
+    pub fn ordenar_por_desc(mut self, campo: CampoOrden) -> Consulta {
+        self.orden.push(Orden {
+            campo,
+            descendente: true,
+        });
+        self
+    }
+
+    /// Máximo de productos por página.
+    pub fn limite(mut self, limite: usize) -> Consulta {
+        self.limite = Some(limite);
+        self
+    }
+
+    /// Omite los primeros `desplazamiento` resultados.
+    pub fn desplazamiento(mut self, desplazamiento: usize) -> Consulta {
+        self.paginacion = Paginacion::Desplazamiento(desplazamiento);
+        self
+    }
+
+    /// Página `numero` (empezando en 1) de `por_pagina` productos.
+    pub fn pagina(self, numero: usize, por_pagina: usize) -> Consulta {
+        self.desplazamiento(numero.saturating_sub(1) * por_pagina)
+            .limite(por_pagina)
+    }
+
+    /// Continúa después de `cursor` (el `siguiente_cursor` de la página anterior de una
+    /// consulta con las mismas claves de orden).
+    ///
+    /// A diferencia del desplazamiento, la página empieza en el primer producto que va
+    /// después de los valores guardados en el cursor, así que no repite ni salta productos
+    /// si se agregan, quitan o modifican otros entre una página y la siguiente, ni si
+    /// cambia o desaparece el propio producto del cursor.
+    pub fn despues_de(mut self, cursor: Cursor) -> Consulta {
+        self.paginacion = Paginacion::Cursor(cursor);
+        self
+    }
+
+    /// Ejecuta la consulta sobre un slice, un `&Vec<Product>` o un `&Catalogo`.
+    pub fn ejecutar<'a>(&self, productos: impl IntoIterator<Item = &'a Product>) -> Resultado<'a> {
+        let mut encontrados: Vec<(Vec<Valor>, &Product)> = productos
+            .into_iter()
+            .filter(|p| self.filtros.iter().all(|f| f.cumple(p)))
+            .map(|p| (self.valores(p), p))
+            .collect();
+        encontrados.sort_by(|(a, p), (b, q)| self.comparar(a, p.id, b, q.id));
+
+        let inicio = match &self.paginacion {
+            Paginacion::Desplazamiento(desplazamiento) => (*desplazamiento).min(encontrados.len()),
+            Paginacion::Cursor(cursor) => encontrados.partition_point(|(valores, p)| {
+                self.comparar(valores, p.id, &cursor.valores, cursor.id)
+                    .is_le()
+            }),
+        };
+        let fin = match self.limite {
+            Some(limite) => inicio.saturating_add(limite).min(encontrados.len()),
+            None => encontrados.len(),
+        };
+
+        let siguiente_cursor = if fin < encontrados.len() && fin > inicio {
+            let (valores, producto) = &encontrados[fin - 1];
+            Some(Cursor {
+                valores: valores.clone(),
+                id: producto.id,
+            })
+        } else {
+            None
+        };
+        Resultado {
+            total: encontrados.len(),
+            productos: encontrados[inicio..fin].iter().map(|(_, p)| *p).collect(),
+            siguiente_cursor,
+        }
+    }
+
+    fn valores(&self, producto: &Product) -> Vec<Valor> {
+        self.orden.iter().map(|o| o.campo.valor(producto)).collect()
+    }
+
+    /// Compara por las claves de orden y desempata por id.
+    fn comparar(&self, a: &[Valor], id_a: u32, b: &[Valor], id_b: u32) -> Ordering {
+        self.orden
+            .iter()
+            .zip(a.iter().zip(b))
+            .map(|(orden, (x, y))| orden.comparar(x, y))
+            .find(|orden| orden.is_ne())
+            .unwrap_or_else(|| id_a.cmp(&id_b))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Catalogo, Variante};
+
+    fn producto(id: u32, titulo: &str, precio: i64, etiquetas: &[&str]) -> Product {
+        Product::new(id, &slugify(titulo))
+            .con_titulo(titulo)
+            .con_proveedor(if id.is_multiple_of(2) { "Acme" } else { "Otra" })
+            .con_etiquetas(etiquetas)
+            .con_variante(Variante::new(id, precio).con_inventario(i64::from(id % 3)))
+    }
+
+    fn catalogo() -> Catalogo {
+        Catalogo::desde_productos(vec![
+            producto(1, "Summer Tee", 1500, &["summer"]),
+            producto(2, "Camiseta Básica", 1200, &["summer", "basico"]),
+            producto(3, "Winter Hoodie", 4500, &["winter"]),
+            producto(4, "Tee Rayada", 1800, &["Summer"]),
+            producto(5, "Tee Premium", 2500, &["summer"]),
+            producto(6, "Tank Tee", 1500, &["summer"]),
+            Product::new(7, "sin-variantes").con_etiquetas(&["summer"]),
+        ])
+        .unwrap()
+    }
+
+    fn ids(resultado: &Resultado<'_>) -> Vec<u32> {
+        resultado.productos.iter().map(|p| p.id).collect()
+    }
+
+    #[test]
+    fn test_filtros_y_orden() {
+        let catalogo = catalogo();
+        let resultado = Consulta::new()
+            .etiqueta("summer")
+            .texto("tee")
+            .precio_menor_que(2000)
+            .ordenar_por(CampoOrden::Precio)
+            .ejecutar(&catalogo);
+        // 1 y 6 empatan en precio y se desempatan por id.
+        assert_eq!(ids(&resultado), vec![1, 6, 4]);
+        assert_eq!(resultado.total, 3);
+
+        let resultado = Consulta::new()
+            .filtrar(Filtro::O(vec![
+                Filtro::Proveedor("acme".to_string()),
+                Filtro::Texto("hoodie".to_string()),
+            ]))
+            .filtrar(Filtro::No(Box::new(Filtro::Disponible)))
+            .ejecutar(&catalogo);
+        assert_eq!(ids(&resultado), vec![3, 6]);
+    }
+
+    #[test]
+    fn test_texto_sin_acentos_y_varias_claves() {
+        let catalogo = catalogo();
+        let resultado = Consulta::new().texto("BASICA").ejecutar(&catalogo);
+        assert_eq!(ids(&resultado), vec![2]);
+
+        let resultado = Consulta::new()
+            .etiqueta("summer")
+            .ordenar_por(CampoOrden::Proveedor)
+            .ordenar_por_desc(CampoOrden::Precio)
+            .ejecutar(&catalogo);
+        // El producto 7 no tiene proveedor y el texto vacío va primero.
+        assert_eq!(ids(&resultado), vec![7, 4, 6, 2, 5, 1]);
+    }
+
+    #[test]
+    fn test_paginacion() {
+        let mut catalogo = catalogo();
+        let consulta = Consulta::new()
+            .etiqueta("summer")
+            .ordenar_por(CampoOrden::Precio);
+
+        let pagina_2 = consulta.clone().pagina(2, 2).ejecutar(&catalogo);
+        assert_eq!(ids(&pagina_2), vec![6, 4]);
+        assert_eq!(pagina_2.total, 6);
+
+        let primera = consulta.clone().limite(4).ejecutar(&catalogo);
+        assert_eq!(ids(&primera), vec![2, 1, 6, 4]);
+        let cursor = primera.siguiente_cursor.unwrap();
+        let segunda = consulta
+            .clone()
+            .despues_de(cursor.clone())
+            .limite(4)
+            .ejecutar(&catalogo);
+        assert_eq!(ids(&segunda), vec![5, 7]);
+        assert_eq!(segunda.siguiente_cursor, None);
+
+        assert!(
+            consulta
+                .clone()
+                .pagina(9, 2)
+                .ejecutar(&catalogo)
+                .productos
+                .is_empty()
+        );
+
+        // El cursor guarda el precio del producto 4: aunque este cambie de precio o se
+        // elimine, la página siguiente es la misma.
+        catalogo
+            .modificar(4, |producto| producto.variantes[0].precio_centavos = 1000)
+            .unwrap();
+        let siguiente = consulta.clone().despues_de(cursor);
+        assert_eq!(ids(&siguiente.clone().ejecutar(&catalogo)), vec![5, 7]);
+        catalogo.eliminar(4);
+        assert_eq!(ids(&siguiente.ejecutar(&catalogo)), vec![5, 7]);
+    }
+}
@@ -0,0 +1,229 @@
+// src/csv.rs
+
+use std::borrow::Cow;
+
+use crate::error::ErrorImportacion;
+
+/// Un registro del CSV con la línea del archivo donde empieza.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Registro<'a> {
+    pub linea: usize,
+    pub campos: Vec<Cow<'a, str>>,
+}
+
+/// Lee registros CSV (RFC 4180) de un texto sin copiarlo.
+///
+/// Los campos sin comillas se prestan del texto original; solo los entrecomillados con
+/// `""` escapadas se copian. Un campo entrecomillado puede abarcar varias líneas (como el
+/// "Body (HTML)" de Shopify), por eso cada registro guarda la línea donde empieza.
+/// Las líneas vacías se omiten.
+///
+/// Un registro con texto después de cerrar unas comillas se reporta y se salta hasta el
+/// siguiente salto de línea; unas comillas sin cerrar terminan la lectura.
+pub(crate) struct LectorCsv<'a> {
+    texto: &'a str,
+    posicion: usize,
+    linea: usize,
+}
+
+impl<'a> LectorCsv<'a> {
+    pub fn new(texto: &'a str) -> LectorCsv<'a> {
+        // Las exportaciones de Excel suelen empezar con BOM.
+        let texto = texto.strip_prefix('\u{feff}').unwrap_or(texto);
+        LectorCsv {
+            texto,
+            posicion: 0,
+            linea: 1,
+        }
+    }
+
+    fn leer_registro(&mut self) -> Result<Registro<'a>, ErrorImportacion> {
+        let bytes = self.texto.as_bytes();
+        let linea = self.linea;
+        let mut campos = Vec::new();
+
+        loop {
+            let (campo, siguiente) = if bytes.get(self.posicion) == Some(&b'"') {
+                self.leer_entrecomillado(linea)?
+            } else {
+                let inicio = self.posicion;
+                let fin = bytes[inicio..]
+                    .iter()
+                    .position(|&b| matches!(b, b',' | b'\n' | b'\r'))
+                    .map_or(bytes.len(), |i| inicio + i);
+                (Cow::Borrowed(&self.texto[inicio..fin]), fin)
+            };
+            campos.push(campo);
+            self.posicion = siguiente;
+
+            match bytes.get(self.posicion) {
+                Some(b',') => self.posicion += 1,
+                Some(b'\r') if bytes.get(self.posicion + 1) == Some(&b'\n') => {
+                    self.posicion += 2;
+                    break;
+                }
+                Some(b'\n') | Some(b'\r') => {
+                    self.posicion += 1;
+                    break;
+                }
+                None => break,
+                Some(_) => {
+                    let error = ErrorImportacion::Fila {
+                        linea: self.linea,
+                        mensaje: "texto despues de cerrar las comillas".to_string(),
+                    };
+                    // Las comillas sí cerraron, así que el registro termina en este salto de
+                    // línea; se descarta y se sigue con el siguiente.
+                    self.posicion = bytes[self.posicion..]
+                        .iter()
+                        .position(|&b| b == b'\n')
+                        .map_or(bytes.len(), |i| self.posicion + i + 1);
+                    self.linea += 1;
+                    return Err(error);
+                }
+            }
+        }
+        self.linea += 1;
+        Ok(Registro { linea, campos })
+    }
+
+    /// Lee un campo que empieza en comillas; retorna el campo y la posición después de cerrarlas.
+    fn leer_entrecomillado(
+        &mut self,
+        linea: usize,
+    ) -> Result<(Cow<'a, str>, usize), ErrorImportacion> {
+        let bytes = self.texto.as_bytes();
+        let inicio = self.posicion + 1;
+        let mut i = inicio;
+        let mut escapadas = false;
+
+        while i < bytes.len() {
+            match bytes[i] {
+                b'"' if bytes.get(i + 1) == Some(&b'"') => {
+                    escapadas = true;
+                    i += 2;
+                }
+                b'"' => {
+                    let contenido = &self.texto[inicio..i];
+                    let campo = if escapadas {
+                        Cow::Owned(contenido.replace("\"\"", "\""))
+                    } else {
+                        Cow::Borrowed(contenido)
+                    };
+                    return Ok((campo, i + 1));
+                }
+                b'\n' => {
+                    self.linea += 1;
+                    i += 1;
+                }
+                _ => i += 1,
+            }
+        }
+        // Sin saber dónde termina el registro no se puede seguir leyendo.
+        self.posicion = bytes.len();
+        Err(ErrorImportacion::Fila {
+            linea,
+            mensaje: "comillas sin cerrar".to_string(),
+        })
+    }
+}
+
+impl<'a> Iterator for LectorCsv<'a> {
+    type Item = Result<Registro<'a>, ErrorImportacion>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let bytes = self.texto.as_bytes();
+        // Omite las líneas vacías.
+        while let Some(&b) = bytes.get(self.posicion) {
+            match b {
+                b'\n' => self.linea += 1,
+                b'\r' => {}
+                _ => break,
+            }
+            self.posicion += 1;
+        }
+        if self.posicion >= bytes.len() {
+            return None;
+        }
+
+        Some(self.leer_registro())
+    }
+}
+
+/// Escribe un campo entre comillas solo si lo necesita.
+pub(crate) fn escapar(campo: &str) -> Cow<'_, str> {
+    if campo.contains([',', '"', '\n', '\r']) {
+        Cow::Owned(format!("\"{}\"", campo.replace('"', "\"\"")))
+    } else {
+        Cow::Borrowed(campo)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn leer(texto: &str) -> Vec<(usize, Vec<String>)> {
+        LectorCsv::new(texto)
+            .map(|registro| {
+                let registro = registro.unwrap();
+                let campos = registro.campos.iter().map(|c| c.to_string()).collect();
+                (registro.linea, campos)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_campos_simples_y_entrecomillados() {
+        assert_eq!(
+            leer("a,b,c\r\n\n\"x, y\",\"di \"\"hola\"\"\",\n"),
+            vec![
+                (1, vec!["a".to_string(), "b".to_string(), "c".to_string()]),
+                (
+                    3,
+                    vec!["x, y".to_string(), "di \"hola\"".to_string(), String::new()]
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_campo_de_varias_lineas() {
+        let registros = leer("\"<p>uno\ndos</p>\",1\nsiguiente,2");
+        assert_eq!(registros[0].1[0], "<p>uno\ndos</p>");
+        assert_eq!(registros[1].0, 3);
+    }
+
+    #[test]
+    fn test_errores() {
+        let mut lector = LectorCsv::new("ok\n\"abierto,1\n");
+        assert!(lector.next().unwrap().is_ok());
+        assert_eq!(
+            lector.next(),
+            Some(Err(ErrorImportacion::Fila {
+                linea: 2,
+                mensaje: "comillas sin cerrar".to_string()
+            }))
+        );
+        assert_eq!(lector.next(), None);
+
+        let mut lector = LectorCsv::new("\"a\"b,1\r\nsiguiente,2");
+        assert_eq!(
+            lector.next(),
+            Some(Err(ErrorImportacion::Fila {
+                linea: 1,
+                mensaje: "texto despues de cerrar las comillas".to_string()
+            }))
+        );
+        let siguiente = lector.next().unwrap().unwrap();
+        assert_eq!((siguiente.linea, siguiente.campos.len()), (2, 2));
+        assert_eq!(lector.next(), None);
+    }
+
+    #[test]
+    fn test_escapar() {
+        assert_eq!(escapar("simple"), "simple");
+        assert_eq!(escapar("a, \"b\""), "\"a, \"\"b\"\"\"");
+        assert_eq!(escapar("dos\nlineas"), "\"dos\nlineas\"");
+    }
+}
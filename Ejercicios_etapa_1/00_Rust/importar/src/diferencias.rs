@@ -0,0 +1,304 @@
+// src/diferencias.rs
+
+use std::collections::{HashMap, HashSet};
+
+use serde_json::{Value, json};
+
+use crate::producto::{Imagen, Product, Variante};
+
+/// Un campo que cambió entre dos versiones de un producto.
+///
+/// `campo` usa los nombres de Shopify para que el JSON lo entienda un proceso de
+/// sincronización: "title", "tags", "variants[3].price_cents"... Una variante agregada o
+/// quitada aparece como "variants[3]" con `anterior` o `nuevo` en `null`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CambioCampo {
+    pub campo: String,
+    pub anterior: Value,
+    pub nuevo: Value,
+}
+
+/// Un producto presente en las dos versiones con al menos un campo distinto.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProductoModificado {
+    pub id: u32,
+    /// Handle en la versión nueva.
+    pub handle: String,
+    pub cambios: Vec<CambioCampo>,
+}
+
+impl ProductoModificado {
+    /// Si el handle cambió, retorna `(anterior, nuevo)`.
+    pub fn renombre(&self) -> Option<(&str, &str)> {
+        self.cambios
+            .iter()
+            .find(|cambio| cambio.campo == "handle")
+            .and_then(|cambio| Some((cambio.anterior.as_str()?, cambio.nuevo.as_str()?)))
+    }
+}
+
+/// Diferencias entre dos versiones de un catálogo, comparando productos por id.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diferencias<'a> {
+    /// Productos nuevos, en el orden de la versión nueva.
+    pub agregados: Vec<&'a Product>,
+    /// Productos que ya no están, en el orden de la versión anterior.
+    pub eliminados: Vec<&'a Product>,
+    /// Productos con cambios, en el orden de la versión nueva.
+    pub modificados: Vec<ProductoModificado>,
+}
+
+impl Diferencias<'_> {
+    pub fn is_empty(&self) -> bool {
+        self.agregados.is_empty() && self.eliminados.is_empty() && self.modificados.is_empty()
+    }
+
+    /// JSON para un proceso de sincronización:
+    ///
+    /// ```text
+    /// {"added": [{"id": 7, "handle": "gorra", "title": "Gorra"}],
+    ///  "removed": [{"id": 3, "handle": "taza", "title": "Taza"}],
+    ///  "modified": [{"id": 42, "handle": "tee-basica",
+    ///                "changes": [{"field": "handle", "old": "simple-tee", "new": "tee-basica"}]}]}
+    /// ```
+    pub fn a_json(&self) -> Value {
+        json!({
+            "added": self.agregados.iter().map(|p| resumen_json(p)).collect::<Vec<_>>(),
+            "removed": self.eliminados.iter().map(|p| resumen_json(p)).collect::<Vec<_>>(),
+            "modified": self.modificados.iter().map(|modificado| json!({
+                "id": modificado.id,
+                "handle": modificado.handle,
+                "changes": modificado.cambios.iter().map(|cambio| json!({
+                    "field": cambio.campo,
+                    "old": cambio.anterior,
+                    "new": cambio.nuevo,
+                })).collect::<Vec<_>>(),
+            })).collect::<Vec<_>>(),
+        })
+    }
+}
+
+/// Compara dos versiones de un catálogo (slices, `&Vec<Product>` o `&Catalogo`).
+///
+/// Los productos se emparejan por id, así que un cambio de handle es una modificación
+/// (ver `ProductoModificado::renombre`) y no un producto eliminado más uno agregado. Las
+/// variantes también se emparejan por id; las imágenes y las etiquetas se comparan como
+/// listas.
+pub fn comparar<'a>(
+    anterior: impl IntoIterator<Item = &'a Product>,
+    nuevo: impl IntoIterator<Item = &'a Product>,
+) -> Diferencias<'a> {
+    let anteriores: Vec<&Product> = anterior.into_iter().collect();
+    let por_id: HashMap<u32, &Product> = anteriores.iter().map(|p| (p.id, *p)).collect();
+
+    let mut diferencias = Diferencias {
+        agregados: Vec::new(),
+        eliminados: Vec::new(),
+        modificados: Vec::new(),
+    };
+    let mut vistos = HashSet::new();
+    for producto in nuevo {
+        vistos.insert(producto.id);
+        match por_id.get(&producto.id) {
+            None => diferencias.agregados.push(producto),
+            Some(antes) => {
+                let cambios = comparar_producto(antes, producto);
+                if !cambios.is_empty() {
+                    diferencias.modificados.push(ProductoModificado {
+                        id: producto.id,
+                        handle: producto.handle.clone(),
+                        cambios,
+                    });
+                }
+            }
+        }
+    }
+    diferencias.eliminados = anteriores
+        .into_iter()
+        .filter(|p| !vistos.contains(&p.id))
+        .collect();
+    diferencias
+}
+
+fn resumen_json(producto: &Product) -> Value {
+    json!({ "id": producto.id, "handle": producto.handle, "title": producto.titulo })
+}
+
+fn comparar_producto(antes: &Product, despues: &Product) -> Vec<CambioCampo> {
+    let mut cambios = Vec::new();
+    let mut campo = |nombre: &str, anterior: Value, nuevo: Value| {
+        if anterior != nuevo {
+            cambios.push(CambioCampo {
+                campo: nombre.to_string(),
+                anterior,
+                nuevo,
+            });
+        }
+    };
+    campo("handle", json!(antes.handle), json!(despues.handle));
+    campo("title", json!(antes.titulo), json!(despues.titulo));
+    campo(
+        "body_html",
+        json!(antes.descripcion),
+        json!(despues.descripcion),
+    );
+    campo("vendor", json!(antes.proveedor), json!(despues.proveedor));
+    campo(
+        "product_type",
+        json!(antes.tipo_producto),
+        json!(despues.tipo_producto),
+    );
+    campo("tags", json!(antes.etiquetas), json!(despues.etiquetas));
+    campo("options", json!(antes.opciones), json!(despues.opciones));
+    campo(
+        "images",
+        imagenes_json(&antes.imagenes),
+        imagenes_json(&despues.imagenes),
+    );
+
+    let variantes_antes: HashMap<u32, &Variante> =
+        antes.variantes.iter().map(|v| (v.id, v)).collect();
+    for variante in &despues.variantes {
+        let prefijo = format!("variants[{}]", variante.id);
+        match variantes_antes.get(&variante.id) {
+            None => campo(&prefijo, Value::Null, variante_json(variante)),
+            Some(anterior) => {
+                for (nombre, viejo, nuevo) in campos_variante(anterior, variante) {
+                    campo(&format!("{}.{}", prefijo, nombre), viejo, nuevo);
+                }
+            }
+        }
+    }
+    for variante in &antes.variantes {
+        if despues.variante(variante.id).is_none() {
+            campo(
+                &format!("variants[{}]", variante.id),
+                variante_json(variante),
+                Value::Null,
+            );
+        }
+    }
+    cambios
+}
+
+fn campos_variante(antes: &Variante, despues: &Variante) -> [(&'static str, Value, Value); 5] {
+    [
+        ("sku", json!(antes.sku), json!(despues.sku)),
+        ("options", json!(antes.valores), json!(despues.valores)),
+        (
+            "price_cents",
+            json!(antes.precio_centavos),
+            json!(despues.precio_centavos),
+        ),
+        (
+            "compare_at_price_cents",
+            json!(antes.precio_comparacion_centavos),
+            json!(despues.precio_comparacion_centavos),
+        ),
+        (
+            "inventory_quantity",
+            json!(antes.inventario),
+            json!(despues.inventario),
+        ),
+    ]
+}
+
+fn variante_json(variante: &Variante) -> Value {
+    json!({
+        "id": variante.id,
+        "sku": variante.sku,
+        "options": variante.valores,
+        "price_cents": variante.precio_centavos,
+        "compare_at_price_cents": variante.precio_comparacion_centavos,
+        "inventory_quantity": variante.inventario,
+    })
+}
+
+fn imagenes_json(imagenes: &[Imagen]) -> Value {
+    imagenes
+        .iter()
+        .map(|imagen| json!({ "src": imagen.src, "alt": imagen.alt, "position": imagen.posicion }))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn version_anterior() -> Vec<Product> {
+        vec![
+            Product::new(42, "simple-tee")
+                .con_titulo("Simple Tee")
+                .con_etiquetas(&["verano"])
+                .con_variante(Variante::new(1, 1999).con_inventario(5))
+                .con_variante(Variante::new(2, 1999)),
+            Product::new(3, "taza").con_titulo("Taza"),
+            Product::new(5, "gorra").con_titulo("Gorra"),
+        ]
+    }
+
+    #[test]
+    fn test_sin_cambios() {
+        let productos = version_anterior();
+        assert!(comparar(&productos, &productos).is_empty());
+    }
+
+    #[test]
+    fn test_diferencias() {
+        let anterior = version_anterior();
+        let mut nuevo = version_anterior();
+        nuevo.remove(1);
+        nuevo[0].handle = "tee-basica".to_string();
+        nuevo[0].etiquetas.push("oferta".to_string());
+        nuevo[0].variantes[0].precio_centavos = 1499;
+        nuevo[0].variantes.remove(1);
+        nuevo[0].variantes.push(Variante::new(9, 2499));
+        nuevo.push(Product::new(7, "calcetas").con_titulo("Calcetas"));
+
+        let diferencias = comparar(&anterior, &nuevo);
+        assert_eq!(diferencias.agregados[0].id, 7);
+        assert_eq!(diferencias.eliminados[0].id, 3);
+        assert_eq!(diferencias.modificados.len(), 1);
+
+        let modificado = &diferencias.modificados[0];
+        assert_eq!(modificado.renombre(), Some(("simple-tee", "tee-basica")));
+        let campos: Vec<&str> = modificado
+            .cambios
+            .iter()
+            .map(|c| c.campo.as_str())
+            .collect();
+        assert_eq!(
+            campos,
+            vec![
+                "handle",
+                "tags",
+                "variants[1].price_cents",
+                "variants[9]",
+                "variants[2]"
+            ]
+        );
+        assert_eq!(modificado.cambios[2].anterior, json!(1999));
+        assert_eq!(modificado.cambios[3].anterior, Value::Null);
+        assert_eq!(modificado.cambios[4].nuevo, Value::Null);
+    }
+
+    #[test]
+    fn test_json() {
+        let anterior = version_anterior();
+        let mut nuevo = version_anterior();
+        nuevo[2].titulo = "Gorra Azul".to_string();
+        let json = comparar(&anterior, &nuevo).a_json();
+        assert_eq!(
+            json,
+            json!({
+                "added": [],
+                "removed": [],
+                "modified": [{
+                    "id": 5,
+                    "handle": "gorra",
+                    "changes": [{ "field": "title", "old": "Gorra", "new": "Gorra Azul" }],
+                }],
+            })
+        );
+    }
+}
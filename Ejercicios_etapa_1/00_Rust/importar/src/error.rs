@@ -0,0 +1,52 @@
+// src/error.rs
+
+use std::fmt;
+
+/// Errores al importar productos.
+///
+/// Los errores de una fila o de un producto no detienen la importación: se acumulan en
+/// `Importacion::errores` y el resto del archivo se sigue leyendo.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ErrorImportacion {
+    /// El archivo completo no se pudo leer o no tiene la forma esperada.
+    Archivo(String),
+    /// Una fila del CSV no es válida; `linea` empieza en 1 y cuenta el encabezado.
+    Fila { linea: usize, mensaje: String },
+    /// Un producto del JSON no es válido; `indice` es su posición en `products`.
+    Producto { indice: usize, mensaje: String },
+    /// Un handle no cumple las reglas de Shopify (ver `validar_handle`).
+    HandleInvalido { handle: String, motivo: String },
+    /// Ya hay un producto con ese id en el catálogo (o, en `importar_por_bloques`, un
+    /// producto o variante con ese id en un bloque anterior).
+    IdDuplicado(u32),
+    /// Ya hay un producto con ese handle en el catálogo.
+    HandleDuplicado(String),
+    /// No hay ningún producto con ese id en el catálogo.
+    ProductoNoEncontrado(u32),
+}
+
+impl fmt::Display for ErrorImportacion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ErrorImportacion::Archivo(mensaje) => write!(f, "archivo invalido: {}", mensaje),
+            ErrorImportacion::Fila { linea, mensaje } => {
+                write!(f, "linea {}: {}", linea, mensaje)
+            }
+            ErrorImportacion::Producto { indice, mensaje } => {
+                write!(f, "producto {}: {}", indice, mensaje)
+            }
+            ErrorImportacion::HandleInvalido { handle, motivo } => {
+                write!(f, "handle invalido \"{}\": {}", handle, motivo)
+            }
+            ErrorImportacion::IdDuplicado(id) => write!(f, "id duplicado: {}", id),
+            ErrorImportacion::HandleDuplicado(handle) => {
+                write!(f, "handle duplicado: \"{}\"", handle)
+            }
+            ErrorImportacion::ProductoNoEncontrado(id) => {
+                write!(f, "no existe el producto {}", id)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ErrorImportacion {}
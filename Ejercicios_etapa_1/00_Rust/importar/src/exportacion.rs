@@ -0,0 +1,348 @@
+// src/exportacion.rs
+
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::Path;
+
+use serde_json::{Value, json};
+
+use crate::csv::escapar;
+use crate::error::ErrorImportacion;
+use crate::producto::{MAX_OPCIONES, Product, Variante};
+
+/// Columnas que escribe `exportar_csv`, en orden.
+///
+/// Son las del CSV de productos de Shopify más "ID" y "Variant ID", que `importar_csv`
+/// usa para conservar los ids al volver a importar.
+pub const COLUMNAS_CSV: [&str; 21] = [
+    "ID",
+    "Handle",
+    "Title",
+    "Body (HTML)",
+    "Vendor",
+    "Type",
+    "Tags",
+    "Option1 Name",
+    "Option1 Value",
+    "Option2 Name",
+    "Option2 Value",
+    "Option3 Name",
+    "Option3 Value",
+    "Variant ID",
+    "Variant SKU",
+    "Variant Inventory Qty",
+    "Variant Price",
+    "Variant Compare At Price",
+    "Image Src",
+    "Image Position",
+    "Image Alt Text",
+];
+
+/// Escribe el catálogo en el formato CSV de productos de Shopify.
+///
+/// Cada producto ocupa tantas filas como variantes o imágenes tenga (lo que sea mayor):
+/// la primera lleva los datos del producto y las siguientes solo la variante y/o la
+/// imagen. `importar_csv` lee de vuelta los mismos productos siempre que tengan título
+/// y que ninguna etiqueta contenga comas.
+pub fn exportar_csv<'a>(productos: impl IntoIterator<Item = &'a Product>) -> String {
+    let mut csv = COLUMNAS_CSV.join(",");
+    csv.push('\n');
+
+    for producto in productos {
+        let filas = producto.variantes.len().max(producto.imagenes.len()).max(1);
+        for fila in 0..filas {
+            let mut campos: Vec<String> = vec![String::new(); COLUMNAS_CSV.len()];
+            if fila == 0 {
+                campos[0] = producto.id.to_string();
+                campos[2] = producto.titulo.clone();
+                campos[3] = producto.descripcion.clone();
+                campos[4] = producto.proveedor.clone();
+                campos[5] = producto.tipo_producto.clone();
+                campos[6] = producto.etiquetas.join(", ");
+                for (i, nombre) in nombres_opciones(producto).iter().enumerate() {
+                    campos[7 + 2 * i] = nombre.to_string();
+                }
+            }
+            campos[1] = producto.handle.clone();
+
+            if let Some(variante) = producto.variantes.get(fila) {
+                for (i, valor) in valores_opciones(variante).iter().enumerate() {
+                    campos[8 + 2 * i] = valor.to_string();
+                }
+                campos[13] = variante.id.to_string();
+                campos[14] = variante.sku.clone();
+                campos[15] = variante.inventario.to_string();
+                campos[16] = formatear_precio(variante.precio_centavos);
+                campos[17] = variante
+                    .precio_comparacion_centavos
+                    .map(formatear_precio)
+                    .unwrap_or_default();
+            }
+            if let Some(imagen) = producto.imagenes.get(fila) {
+                campos[18] = imagen.src.clone();
+                campos[19] = imagen.posicion.to_string();
+                campos[20] = imagen.alt.clone();
+            }
+
+            let campos: Vec<_> = campos.iter().map(|campo| escapar(campo)).collect();
+            csv.push_str(&campos.join(","));
+            csv.push('\n');
+        }
+    }
+    csv
+}
+
+/// Escribe el catálogo con la forma del `products.json` de Shopify, que `importar_json`
+/// lee de vuelta sin pérdidas. Los precios van como texto decimal ("19.99").
+pub fn exportar_json<'a>(productos: impl IntoIterator<Item = &'a Product>) -> Value {
+    let productos: Vec<Value> = productos
+        .into_iter()
+        .map(|producto| {
+            json!({
+                "id": producto.id,
+                "handle": producto.handle,
+                "title": producto.titulo,
+                "body_html": producto.descripcion,
+                "vendor": producto.proveedor,
+                "product_type": producto.tipo_producto,
+                "tags": producto.etiquetas,
+                "options": opciones_json(producto),
+                "variants": producto.variantes.iter().map(|variante| {
+                    let [option1, option2, option3] = opciones_variante(variante);
+                    let comparacion = variante.precio_comparacion_centavos.map(formatear_precio);
+                    json!({
+                        "id": variante.id,
+                        "title": variante.titulo(),
+                        "sku": variante.sku,
+                        "price": formatear_precio(variante.precio_centavos),
+                        "compare_at_price": comparacion,
+                        "option1": option1,
+                        "option2": option2,
+                        "option3": option3,
+                        "inventory_quantity": variante.inventario,
+                    })
+                }).collect::<Vec<_>>(),
+                "images": producto.imagenes.iter().map(|imagen| json!({
+                    "src": imagen.src,
+                    "alt": if imagen.alt.is_empty() { Value::Null } else { json!(imagen.alt) },
+                    "position": imagen.posicion,
+                })).collect::<Vec<_>>(),
+            })
+        })
+        .collect();
+    json!({ "products": productos })
+}
+
+/// Datos ya calculados para plantillas Liquid, con los nombres del objeto `product` de
+/// Shopify: `{{ product.title }}`, `{{ product.price | money }}`, `product.options`,
+/// `variant.option1`...
+///
+/// Los precios van en centavos, como los espera el filtro `money`. Además de los campos
+/// del producto incluye `url`, `price_min`, `price_max`, `price_varies`, `available`
+/// y `featured_image`, para no tener que calcularlos en la plantilla.
+pub fn exportar_liquid<'a>(productos: impl IntoIterator<Item = &'a Product>) -> Value {
+    productos
+        .into_iter()
+        .map(|producto| {
+            let mas_barata = producto
+                .variantes
+                .iter()
+                .min_by_key(|variante| variante.precio_centavos);
+            json!({
+                "id": producto.id,
+                "handle": producto.handle,
+                "url": format!("/products/{}", producto.handle),
+                "title": producto.titulo,
+                "description": producto.descripcion,
+                "vendor": producto.proveedor,
+                "type": producto.tipo_producto,
+                "tags": producto.etiquetas,
+                "price": producto.precio_minimo(),
+                "price_min": producto.precio_minimo(),
+                "price_max": producto.precio_maximo(),
+                "price_varies": producto.precio_minimo() != producto.precio_maximo(),
+                "compare_at_price": mas_barata.and_then(|v| v.precio_comparacion_centavos),
+                "available": producto.disponible(),
+                "featured_image": producto.imagenes.first().map(|imagen| &imagen.src),
+                "images": producto.imagenes.iter().map(|imagen| &imagen.src).collect::<Vec<_>>(),
+                "options": opciones_json(producto),
+                "variants": producto.variantes.iter().map(|variante| {
+                    let [option1, option2, option3] = opciones_variante(variante);
+                    json!({
+                        "id": variante.id,
+                        "title": variante.titulo(),
+                        "sku": variante.sku,
+                        "price": variante.precio_centavos,
+                        "compare_at_price": variante.precio_comparacion_centavos,
+                        "available": variante.inventario > 0,
+                        "option1": option1,
+                        "option2": option2,
+                        "option3": option3,
+                    })
+                }).collect::<Vec<_>>(),
+            })
+        })
+        .collect()
+}
+
+/// Exporta a un archivo eligiendo el formato por la extensión (`.json` o `.csv`).
+pub fn exportar_archivo<'a>(
+    productos: impl IntoIterator<Item = &'a Product>,
+    ruta: &Path,
+) -> Result<(), ErrorImportacion> {
+    let contenido = match ruta.extension().and_then(|e| e.to_str()) {
+        Some(extension) if extension.eq_ignore_ascii_case("json") => {
+            serde_json::to_string_pretty(&exportar_json(productos))
+                .expect("un Value siempre se puede serializar")
+        }
+        Some(extension) if extension.eq_ignore_ascii_case("csv") => exportar_csv(productos),
+        _ => {
+            return Err(ErrorImportacion::Archivo(format!(
+                "{}: se esperaba un archivo .json o .csv",
+                ruta.display()
+            )));
+        }
+    };
+    fs::write(ruta, contenido)
+        .map_err(|e| ErrorImportacion::Archivo(format!("{}: {}", ruta.display(), e)))
+}
+
+/// Precio en el formato decimal de Shopify: 1999 -> "19.99".
+pub(crate) fn formatear_precio(centavos: i64) -> String {
+    let signo = if centavos < 0 { "-" } else { "" };
+    let centavos = centavos.unsigned_abs();
+    format!("{}{}.{:02}", signo, centavos / 100, centavos % 100)
+}
+
+/// Shopify exporta los productos sin opciones con una opción "Title" de valor
+/// "Default Title"; se hace lo mismo para que otras herramientas lo reconozcan.
+fn nombres_opciones(producto: &Product) -> Vec<&str> {
+    if producto.opciones.is_empty() && !producto.variantes.is_empty() {
+        vec!["Title"]
+    } else {
+        producto.opciones.iter().map(String::as_str).collect()
+    }
+}
+
+fn valores_opciones(variante: &Variante) -> Vec<&str> {
+    if variante.valores.is_empty() {
+        vec!["Default Title"]
+    } else {
+        variante.valores.iter().map(String::as_str).collect()
+    }
+}
+
+fn opciones_variante(variante: &Variante) -> [Option<&str>; MAX_OPCIONES] {
+    let valores = valores_opciones(variante);
+    [0, 1, 2].map(|i| valores.get(i).copied())
+}
+
+/// `[{"name": "Talla", "position": 1, "values": ["M", "L"]}]`, con los valores en el orden
+/// en que aparecen en las variantes.
+fn opciones_json(producto: &Product) -> Vec<Value> {
+    nombres_opciones(producto)
+        .iter()
+        .enumerate()
+        .map(|(i, nombre)| {
+            let mut vistos = BTreeSet::new();
+            let valores: Vec<&str> = producto
+                .variantes
+                .iter()
+                .filter_map(|variante| valores_opciones(variante).get(i).copied())
+                .filter(|valor| vistos.insert(*valor))
+                .collect();
+            json!({ "name": nombre, "position": i + 1, "values": valores })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::importacion::{importar_csv, importar_json};
+
+    fn catalogo() -> Vec<Product> {
+        vec![
+            Product::new(42, "simple-tee")
+                .con_titulo("Simple Tee, \"edición\" 2")
+                .con_descripcion("<p>Suave\ny ligera</p>")
+                .con_proveedor("Acme")
+                .con_tipo("Camisetas")
+                .con_etiquetas(&["verano", "básico"])
+                .con_opciones(&["Talla", "Color"])
+                .con_variante(
+                    Variante::new(1, 1999)
+                        .con_sku("TEE-M-R")
+                        .con_valores(&["M", "Rojo"])
+                        .con_precio_comparacion(2499)
+                        .con_inventario(5),
+                )
+                .con_variante(
+                    Variante::new(2, 2005)
+                        .con_valores(&["L", "Rojo"])
+                        .con_inventario(-1),
+                )
+                .con_imagen("https://cdn/a.jpg", "Frente")
+                .con_imagen("https://cdn/b.jpg", "")
+                .con_imagen("https://cdn/c.jpg", "Detalle"),
+            Product::new(7, "taza")
+                .con_titulo("Taza")
+                .con_variante(Variante::new(3, 900)),
+            Product::new(8, "borrador").con_titulo("Borrador"),
+            // Un precio negativo (un ajuste) también tiene que volver igual.
+            Product::new(9, "ajuste")
+                .con_titulo("Ajuste")
+                .con_variante(Variante::new(4, -150).con_precio_comparacion(-5)),
+        ]
+    }
+
+    #[test]
+    fn test_ida_y_vuelta_csv() {
+        let productos = catalogo();
+        let csv = exportar_csv(&productos);
+        // Tres filas para la camiseta (3 imágenes) y una para cada uno de los demás.
+        assert_eq!(csv.matches("https://cdn/").count(), 3);
+        let importacion = importar_csv(&csv).unwrap();
+        assert_eq!(importacion.errores, vec![]);
+        assert_eq!(importacion.productos, productos);
+    }
+
+    #[test]
+    fn test_ida_y_vuelta_json() {
+        let productos = catalogo();
+        let json = exportar_json(&productos);
+        assert_eq!(json["products"][0]["variants"][0]["price"], "19.99");
+        assert_eq!(json["products"][0]["options"][1]["values"], json!(["Rojo"]));
+        assert_eq!(
+            json["products"][1]["variants"][0]["option1"],
+            "Default Title"
+        );
+
+        let importacion = importar_json(&json.to_string()).unwrap();
+        assert_eq!(importacion.errores, vec![]);
+        assert_eq!(importacion.productos, productos);
+    }
+
+    #[test]
+    fn test_exportar_liquid() {
+        let liquid = exportar_liquid(&catalogo());
+        let tee = &liquid[0];
+        assert_eq!(tee["url"], "/products/simple-tee");
+        assert_eq!(tee["price"], 1999);
+        assert_eq!(tee["price_max"], 2005);
+        assert_eq!(tee["price_varies"], true);
+        assert_eq!(tee["compare_at_price"], 2499);
+        assert_eq!(tee["featured_image"], "https://cdn/a.jpg");
+        assert_eq!(tee["variants"][1]["title"], "L / Rojo");
+        assert_eq!(tee["variants"][1]["available"], false);
+        assert_eq!(liquid[2]["price"], Value::Null);
+        assert_eq!(liquid[2]["available"], false);
+    }
+
+    #[test]
+    fn test_formatear_precio() {
+        assert_eq!(formatear_precio(1999), "19.99");
+        assert_eq!(formatear_precio(5), "0.05");
+        assert_eq!(formatear_precio(-150), "-1.50");
+    }
+}
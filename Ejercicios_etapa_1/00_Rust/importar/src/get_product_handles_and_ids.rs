@@ -1,14 +1,35 @@
 // src/get_product_handles_and_ids.rs
 
-// TODO: Importa `get_product_handles` y `get_product_ids` desde utils.rs
-
 use crate::Product; // Importamos la estructura Product
 
-pub fn get_product_handles_and_ids(products: &[Product]) -> Vec<(u32, String)> {
-    let handles = get_product_handles(products);
-    let ids = get_product_ids(products);
-
-    ids.into_iter()
-        .zip(handles.into_iter())
+// Recorre los productos una sola vez; acepta un slice, un `&Vec<Product>` o un `&Catalogo`.
+// Los handles se prestan de los productos, sin copiarlos.
+pub fn get_product_handles_and_ids<'a>(
+    products: impl IntoIterator<Item = &'a Product>,
+) -> Vec<(u32, &'a str)> {
+    products
+        .into_iter()
+        .map(|p| (p.id, p.handle.as_str()))
         .collect()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Catalogo;
+
+    #[test]
+    fn test_get_product_handles_and_ids() {
+        let products = vec![
+            Product::new(42, "simple-tee"),
+            Product::new(256, "casual-tee"),
+        ];
+        assert_eq!(
+            get_product_handles_and_ids(&products),
+            vec![(42, "simple-tee"), (256, "casual-tee")]
+        );
+
+        let catalogo = Catalogo::desde_productos(products).unwrap();
+        assert_eq!(get_product_handles_and_ids(&catalogo)[1].0, 256);
+    }
+}
@@ -0,0 +1,254 @@
+// src/handle.rs
+
+use std::collections::HashSet;
+
+use crate::Product;
+use crate::error::ErrorImportacion;
+
+/// Longitud máxima de un handle en Shopify.
+pub const MAX_LONGITUD_HANDLE: usize = 255;
+
+/// Convierte un texto en un handle válido: "Camiseta Básica (Roja)" -> "camiseta-basica-roja".
+///
+/// Translitera acentos y letras especiales a ASCII, pasa todo a minúsculas y reemplaza
+/// cualquier grupo de caracteres que no sea letra o dígito por un solo guion. El resultado
+/// puede quedar vacío si el texto no tiene letras ni dígitos.
+pub fn slugify(texto: &str) -> String {
+    let mut handle = String::with_capacity(texto.len());
+    let mut guion_pendiente = false;
+
+    for c in texto.chars().flat_map(char::to_lowercase) {
+        let mut buffer = [0; 4];
+        let ascii = match transliterar(c) {
+            Some(ascii) => ascii,
+            None if c.is_ascii_alphanumeric() => c.encode_utf8(&mut buffer),
+            None => {
+                guion_pendiente = !handle.is_empty();
+                continue;
+            }
+        };
+        if guion_pendiente {
+            handle.push('-');
+            guion_pendiente = false;
+        }
+        handle.push_str(ascii);
+    }
+    recortar(&handle, MAX_LONGITUD_HANDLE).to_string()
+}
+
+/// Equivalente ASCII de las letras acentuadas más comunes en español, francés y alemán.
+fn transliterar(c: char) -> Option<&'static str> {
+    let ascii = match c {
+        'á' | 'à' | 'â' | 'ä' | 'ã' | 'å' | 'ā' => "a",
+        'é' | 'è' | 'ê' | 'ë' | 'ē' => "e",
+        'í' | 'ì' | 'î' | 'ï' | 'ī' => "i",
+        'ó' | 'ò' | 'ô' | 'ö' | 'õ' | 'ø' | 'ō' => "o",
+        'ú' | 'ù' | 'û' | 'ü' | 'ū' => "u",
+        'ý' | 'ÿ' => "y",
+        'ñ' => "n",
+        'ç' => "c",
+        'ß' => "ss",
+        'æ' => "ae",
+        'œ' => "oe",
+        _ => return None,
+    };
+    Some(ascii)
+}
+
+/// Corta el handle a `maximo` bytes sin dejar un guion al final.
+fn recortar(handle: &str, maximo: usize) -> &str {
+    // El handle ya es ASCII, así que cualquier posición es un límite de carácter válido.
+    handle[..handle.len().min(maximo)].trim_end_matches('-')
+}
+
+/// Revisa que un handle cumpla las reglas de Shopify: no vacío, a lo más 255 caracteres,
+/// solo minúsculas ASCII, dígitos y guiones, sin guion al principio o al final y sin
+/// guiones seguidos.
+pub fn validar_handle(handle: &str) -> Result<(), ErrorImportacion> {
+    let invalido = |motivo: String| ErrorImportacion::HandleInvalido {
+        handle: handle.to_string(),
+        motivo,
+    };
+
+    if handle.is_empty() {
+        return Err(invalido("esta vacio".to_string()));
+    }
+    if handle.len() > MAX_LONGITUD_HANDLE {
+        return Err(invalido(format!(
+            "tiene mas de {} caracteres",
+            MAX_LONGITUD_HANDLE
+        )));
+    }
+    if let Some(c) = handle
+        .chars()
+        .find(|c| !(c.is_ascii_lowercase() || c.is_ascii_digit() || *c == '-'))
+    {
+        return Err(invalido(format!("caracter no permitido '{}'", c)));
+    }
+    if handle.starts_with('-') || handle.ends_with('-') {
+        return Err(invalido("empieza o termina con guion".to_string()));
+    }
+    if handle.contains("--") {
+        return Err(invalido("tiene guiones seguidos".to_string()));
+    }
+    Ok(())
+}
+
+/// Retorna `base` si no está en `ocupados`, o el primer `base-1`, `base-2`... libre.
+///
+/// Si hace falta, recorta `base` para que el sufijo quepa en `MAX_LONGITUD_HANDLE`.
+pub fn handle_unico(base: &str, ocupados: &HashSet<String>) -> String {
+    if !ocupados.contains(base) {
+        return base.to_string();
+    }
+    (1..)
+        .map(|n| {
+            let sufijo = format!("-{}", n);
+            let base = recortar(base, MAX_LONGITUD_HANDLE - sufijo.len());
+            format!("{}{}", base, sufijo)
+        })
+        .find(|candidato| !ocupados.contains(candidato))
+        .expect("siempre hay un sufijo libre")
+}
+
+/// Un handle que `normalizar_handles` tuvo que cambiar.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CambioHandle {
+    pub id: u32,
+    pub anterior: String,
+    pub nuevo: String,
+}
+
+/// Deja todos los handles del catálogo válidos y únicos.
+///
+/// Los handles inválidos se regeneran con `slugify` (a partir del handle, o del título si
+/// el handle no tiene letras ni dígitos, o `producto-<id>` como último recurso). Cuando
+/// dos productos comparten handle, el primero lo conserva y los siguientes reciben
+/// `-1`, `-2`... saltando los sufijos que ya usa otro producto del catálogo.
+///
+/// # Retorno
+/// Retorna los cambios realizados, en el orden de los productos.
+pub fn normalizar_handles(productos: &mut [Product]) -> Vec<CambioHandle> {
+    // Primero se reservan los handles que ya son válidos (la primera vez que aparecen),
+    // para que los sufijos nuevos no le quiten el suyo a un producto posterior.
+    let mut ocupados = HashSet::new();
+    let conservan: Vec<bool> = productos
+        .iter()
+        .map(|producto| {
+            validar_handle(&producto.handle).is_ok() && ocupados.insert(producto.handle.clone())
+        })
+        .collect();
+    let mut cambios = Vec::new();
+
+    for (producto, conserva) in productos.iter_mut().zip(conservan) {
+        if conserva {
+            continue;
+        }
+        let mut base = producto.handle.clone();
+        if validar_handle(&base).is_err() {
+            base = [slugify(&producto.handle), slugify(&producto.titulo)]
+                .into_iter()
+                .find(|candidato| !candidato.is_empty())
+                .unwrap_or_else(|| format!("producto-{}", producto.id));
+        }
+        let nuevo = handle_unico(&base, &ocupados);
+        ocupados.insert(nuevo.clone());
+
+        if nuevo != producto.handle {
+            cambios.push(CambioHandle {
+                id: producto.id,
+                anterior: std::mem::replace(&mut producto.handle, nuevo.clone()),
+                nuevo,
+            });
+        }
+    }
+    cambios
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_slugify() {
+        assert_eq!(slugify("Camiseta Básica"), "camiseta-basica");
+        assert_eq!(
+            slugify("  ¡Año Nuevo! -- Pingüino & Crème Brûlée "),
+            "ano-nuevo-pinguino-creme-brulee"
+        );
+        assert_eq!(slugify("Straße 42"), "strasse-42");
+        assert_eq!(slugify("ÉTÉ"), "ete");
+        assert_eq!(slugify("日本"), "");
+        assert_eq!(slugify(&"a ".repeat(200)).len(), MAX_LONGITUD_HANDLE);
+    }
+
+    #[test]
+    fn test_validar_handle() {
+        assert_eq!(validar_handle("simple-tee-2"), Ok(()));
+        let motivo = |handle: &str| match validar_handle(handle) {
+            Err(ErrorImportacion::HandleInvalido { motivo, .. }) => motivo,
+            otro => panic!("se esperaba un error: {:?}", otro),
+        };
+        assert_eq!(motivo(""), "esta vacio");
+        assert_eq!(motivo("Simple-Tee"), "caracter no permitido 'S'");
+        assert_eq!(motivo("tee shirt"), "caracter no permitido ' '");
+        assert_eq!(motivo("-tee"), "empieza o termina con guion");
+        assert_eq!(motivo("simple--tee"), "tiene guiones seguidos");
+        assert_eq!(motivo(&"a".repeat(256)), "tiene mas de 255 caracteres");
+    }
+
+    #[test]
+    fn test_handle_unico() {
+        let ocupados: HashSet<String> = ["tee", "tee-1"].iter().map(|h| h.to_string()).collect();
+        assert_eq!(handle_unico("gorra", &ocupados), "gorra");
+        assert_eq!(handle_unico("tee", &ocupados), "tee-2");
+        let largo = "a".repeat(MAX_LONGITUD_HANDLE);
+        let ocupados = HashSet::from([largo.clone()]);
+        assert_eq!(handle_unico(&largo, &ocupados).len(), MAX_LONGITUD_HANDLE);
+    }
+
+    #[test]
+    fn test_normalizar_handles() {
+        let mut productos = vec![
+            Product::new(1, "tee"),
+            Product::new(2, "tee"),
+            Product::new(3, "Camiseta Básica"),
+            Product::new(4, "!!!").con_titulo("Gorra"),
+            Product::new(5, "tee"),
+            Product::new(6, ""),
+        ];
+        let cambios = normalizar_handles(&mut productos);
+        let handles: Vec<&str> = productos.iter().map(|p| p.handle.as_str()).collect();
+        assert_eq!(
+            handles,
+            vec![
+                "tee",
+                "tee-1",
+                "camiseta-basica",
+                "gorra",
+                "tee-2",
+                "producto-6"
+            ]
+        );
+        assert_eq!(cambios.len(), 5);
+        assert_eq!(
+            cambios[0],
+            CambioHandle {
+                id: 2,
+                anterior: "tee".to_string(),
+                nuevo: "tee-1".to_string()
+            }
+        );
+
+        // Un handle válido que ya existe no se reasigna a un duplicado anterior.
+        let mut productos = vec![
+            Product::new(1, "tee"),
+            Product::new(2, "tee"),
+            Product::new(3, "tee-1"),
+        ];
+        let cambios = normalizar_handles(&mut productos);
+        let handles: Vec<&str> = productos.iter().map(|p| p.handle.as_str()).collect();
+        assert_eq!(handles, vec!["tee", "tee-2", "tee-1"]);
+        assert_eq!(cambios.len(), 1);
+    }
+}
@@ -0,0 +1,686 @@
+// src/importacion.rs
+
+use std::borrow::Cow;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::Path;
+
+use serde_json::Value;
+
+use crate::csv::{LectorCsv, Registro};
+use crate::error::ErrorImportacion;
+use crate::producto::{Imagen, MAX_OPCIONES, Product, Variante};
+
+/// Resultado de una importación: los productos válidos y los errores de las filas omitidas.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Importacion {
+    pub productos: Vec<Product>,
+    pub errores: Vec<ErrorImportacion>,
+}
+
+/// Importa un archivo eligiendo el formato por la extensión (`.json` o `.csv`).
+pub fn importar_archivo(ruta: &Path) -> Result<Importacion, ErrorImportacion> {
+    let texto = fs::read_to_string(ruta)
+        .map_err(|e| ErrorImportacion::Archivo(format!("{}: {}", ruta.display(), e)))?;
+    match ruta.extension().and_then(|e| e.to_str()) {
+        Some(extension) if extension.eq_ignore_ascii_case("json") => importar_json(&texto),
+        Some(extension) if extension.eq_ignore_ascii_case("csv") => importar_csv(&texto),
+        _ => Err(ErrorImportacion::Archivo(format!(
+            "{}: se esperaba un archivo .json o .csv",
+            ruta.display()
+        ))),
+    }
+}
+
+/// Lee el `products.json` de una tienda Shopify (`{"products": [...]}` o la lista sola).
+///
+/// # Retorno
+/// Retorna `Err` solo si el texto no es JSON o no contiene la lista de productos; los
+/// productos inválidos se reportan en `Importacion::errores` con su índice.
+pub fn importar_json(texto: &str) -> Result<Importacion, ErrorImportacion> {
+    let json: Value =
+        serde_json::from_str(texto).map_err(|e| ErrorImportacion::Archivo(e.to_string()))?;
+    let lista = match &json {
+        Value::Array(lista) => lista,
+        _ => json
+            .get("products")
+            .and_then(Value::as_array)
+            .ok_or_else(|| ErrorImportacion::Archivo("falta la lista \"products\"".to_string()))?,
+    };
+
+    let mut importacion = Importacion::default();
+    for (indice, valor) in lista.iter().enumerate() {
+        match producto_desde_json(valor) {
+            Ok(producto) => importacion.productos.push(producto),
+            Err(mensaje) => importacion
+                .errores
+                .push(ErrorImportacion::Producto { indice, mensaje }),
+        }
+    }
+    Ok(importacion)
+}
+
+fn producto_desde_json(valor: &Value) -> Result<Product, String> {
+    let handle = texto_json(valor, "handle");
+    if handle.is_empty() {
+        return Err("falta el handle".to_string());
+    }
+
+    let mut producto = Product::new(id_json(valor, "id")?, &handle);
+    producto.titulo = texto_json(valor, "title");
+    producto.descripcion = texto_json(valor, "body_html");
+    producto.proveedor = texto_json(valor, "vendor");
+    producto.tipo_producto = texto_json(valor, "product_type");
+    producto.etiquetas = match valor.get("tags") {
+        Some(Value::Array(etiquetas)) => etiquetas
+            .iter()
+            .filter_map(Value::as_str)
+            .map(str::to_string)
+            .collect(),
+        Some(Value::String(etiquetas)) => separar_etiquetas(etiquetas),
+        _ => Vec::new(),
+    };
+    producto.opciones = lista_json(valor, "options")
+        .iter()
+        .map(|opcion| match opcion {
+            Value::String(nombre) => nombre.clone(),
+            _ => texto_json(opcion, "name"),
+        })
+        .collect();
+    quitar_opcion_por_defecto(&mut producto.opciones);
+    if producto.opciones.len() > MAX_OPCIONES {
+        return Err(format!("mas de {} opciones", MAX_OPCIONES));
+    }
+
+    for variante in lista_json(valor, "variants") {
+        let mut nueva = Variante::new(id_json(variante, "id")?, 0);
+        nueva.sku = texto_json(variante, "sku");
+        nueva.precio_centavos =
+            precio_json(variante, "price")?.ok_or_else(|| "variante sin precio".to_string())?;
+        nueva.precio_comparacion_centavos = precio_json(variante, "compare_at_price")?;
+        nueva.inventario = match variante.get("inventory_quantity") {
+            None | Some(Value::Null) => 0,
+            Some(cantidad) => cantidad
+                .as_i64()
+                .ok_or_else(|| format!("inventario invalido: {}", cantidad))?,
+        };
+        nueva.valores = ["option1", "option2", "option3"]
+            .iter()
+            .take(producto.opciones.len())
+            .map(|campo| texto_json(variante, campo))
+            .collect();
+        producto.variantes.push(nueva);
+    }
+
+    for (indice, imagen) in lista_json(valor, "images").iter().enumerate() {
+        let src = texto_json(imagen, "src");
+        if src.is_empty() {
+            return Err(format!("la imagen {} no tiene src", indice + 1));
+        }
+        let posicion = imagen.get("position").and_then(Value::as_u64);
+        producto.imagenes.push(Imagen {
+            src,
+            alt: texto_json(imagen, "alt"),
+            posicion: posicion.map_or(indice as u32 + 1, |p| p as u32),
+        });
+    }
+    Ok(producto)
+}
+
+/// Texto de un campo; los campos ausentes o `null` se leen como texto vacío.
+fn texto_json(valor: &Value, campo: &str) -> String {
+    match valor.get(campo) {
+        Some(Value::String(texto)) => texto.clone(),
+        Some(Value::Number(numero)) => numero.to_string(),
+        _ => String::new(),
+    }
+}
+
+fn lista_json<'a>(valor: &'a Value, campo: &str) -> &'a [Value] {
+    valor
+        .get(campo)
+        .and_then(Value::as_array)
+        .map_or(&[], Vec::as_slice)
+}
+
+fn id_json(valor: &Value, campo: &str) -> Result<u32, String> {
+    let id = valor
+        .get(campo)
+        .ok_or_else(|| format!("falta el campo \"{}\"", campo))?;
+    id.as_u64()
+        .and_then(|id| u32::try_from(id).ok())
+        .ok_or_else(|| format!("{} invalido: {}", campo, id))
+}
+
+/// Los precios de Shopify llegan como texto ("19.99"), pero también se aceptan números.
+fn precio_json(valor: &Value, campo: &str) -> Result<Option<i64>, String> {
+    let texto = match valor.get(campo) {
+        None | Some(Value::Null) => return Ok(None),
+        Some(Value::String(texto)) if texto.is_empty() => return Ok(None),
+        Some(Value::String(texto)) => texto.clone(),
+        Some(Value::Number(numero)) => numero.to_string(),
+        Some(otro) => otro.to_string(),
+    };
+    parsear_precio(&texto)
+        .map(Some)
+        .ok_or_else(|| format!("precio invalido: \"{}\"", texto))
+}
+
+/// Lee un precio decimal ("19.99", "20", "5.5") como centavos.
+///
+/// Acepta un `-` al principio, porque `formatear_precio` escribe así los precios negativos.
+pub(crate) fn parsear_precio(texto: &str) -> Option<i64> {
+    let texto = texto.trim();
+    let (signo, texto) = match texto.strip_prefix('-') {
+        Some(resto) => (-1, resto),
+        None => (1, texto),
+    };
+    let (entero, decimales) = texto.split_once('.').unwrap_or((texto, ""));
+    let solo_digitos = |parte: &str| parte.bytes().all(|b| b.is_ascii_digit());
+    if entero.is_empty() || !solo_digitos(entero) || !solo_digitos(decimales) || decimales.len() > 2
+    {
+        return None;
+    }
+    let centavos = format!("{}{:0<2}", entero, decimales);
+    centavos
+        .parse::<i64>()
+        .ok()
+        .map(|centavos| signo * centavos)
+}
+
+/// Lee un id del archivo; el 0 no es válido porque marca los ids que faltan.
+fn parsear_id(texto: &str) -> Option<u32> {
+    texto.parse().ok().filter(|&id| id != 0)
+}
+
+fn separar_etiquetas(etiquetas: &str) -> Vec<String> {
+    etiquetas
+        .split(',')
+        .map(str::trim)
+        .filter(|e| !e.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// Shopify da a los productos sin opciones una opción "Title" con valor "Default Title".
+fn quitar_opcion_por_defecto(opciones: &mut Vec<String>) {
+    if opciones.len() == 1 && opciones[0] == "Title" {
+        opciones.clear();
+    }
+}
+
+/// Columnas con el nombre y el valor de cada opción (Option1 a Option3).
+const COLUMNAS_OPCIONES: [(&str, &str); MAX_OPCIONES] = [
+    ("option1 name", "option1 value"),
+    ("option2 name", "option2 value"),
+    ("option3 name", "option3 value"),
+];
+
+/// Lee la exportación CSV de productos de Shopify.
+///
+/// Cada producto ocupa varias filas seguidas con el mismo "Handle": la primera trae los
+/// datos del producto y su primera variante; las siguientes traen más variantes y/o
+/// imágenes. Una fila inválida se reporta con su número de línea y se omite; si la que
+/// falla es la primera de un producto, se omite el producto completo.
+///
+/// Las columnas "ID" y "Variant ID" no forman parte del formato de Shopify; si están
+/// presentes se usan como ids, y si no, los ids se asignan en orden empezando en 1.
+///
+/// # Retorno
+/// Retorna `Err` si el archivo está vacío o no tiene la columna "Handle".
+pub fn importar_csv(texto: &str) -> Result<Importacion, ErrorImportacion> {
+    leer_csv(texto, true)
+}
+
+/// Como `importar_csv`, pero deja en 0 los ids de productos y variantes que no vienen en
+/// el archivo, para que `ImportadorMasivo` los asigne al unir los bloques en orden.
+pub(crate) fn importar_bloque_csv(texto: &str) -> Result<Importacion, ErrorImportacion> {
+    leer_csv(texto, false)
+}
+
+/// Ids de productos o de variantes ya usados, para asignar los que faltan sin repetir.
+#[derive(Debug, Default)]
+pub(crate) struct Ids {
+    explicitos: HashSet<u32>,
+    generados: HashSet<u32>,
+    siguiente: u32,
+}
+
+impl Ids {
+    /// Marca como usado un id que viene en el archivo.
+    pub fn reservar(&mut self, id: u32) {
+        self.explicitos.insert(id);
+    }
+
+    /// Si `generar` ya entregó este id.
+    pub fn generado(&self, id: u32) -> bool {
+        self.generados.contains(&id)
+    }
+
+    /// El menor id desde 1 que no se ha reservado ni generado.
+    pub fn generar(&mut self) -> u32 {
+        let mut id = self.siguiente.max(1);
+        while self.explicitos.contains(&id) || self.generados.contains(&id) {
+            id += 1;
+        }
+        self.siguiente = id + 1;
+        self.generados.insert(id);
+        id
+    }
+}
+
+/// Asigna ids a los productos y variantes que quedaron en 0, en orden y empezando en 1,
+/// saltando los que ya traen los demás productos y los que ya están en `ids_productos` e
+/// `ids_variantes`.
+pub(crate) fn asignar_ids_faltantes(
+    productos: &mut [Product],
+    ids_productos: &mut Ids,
+    ids_variantes: &mut Ids,
+) {
+    for producto in productos.iter() {
+        if producto.id != 0 {
+            ids_productos.reservar(producto.id);
+        }
+        for variante in producto.variantes.iter().filter(|v| v.id != 0) {
+            ids_variantes.reservar(variante.id);
+        }
+    }
+    for producto in productos.iter_mut() {
+        if producto.id == 0 {
+            producto.id = ids_productos.generar();
+        }
+        for variante in producto.variantes.iter_mut().filter(|v| v.id == 0) {
+            variante.id = ids_variantes.generar();
+        }
+    }
+}
+
+/// Nombre de columna en minúsculas -> posición; `None` si falta la columna "Handle".
+pub(crate) fn columnas_csv(encabezado: &Registro<'_>) -> Option<HashMap<String, usize>> {
+    let columnas: HashMap<String, usize> = encabezado
+        .campos
+        .iter()
+        .enumerate()
+        .map(|(indice, nombre)| (nombre.trim().to_ascii_lowercase(), indice))
+        .collect();
+    columnas.contains_key("handle").then_some(columnas)
+}
+
+fn leer_csv(texto: &str, asignar_ids: bool) -> Result<Importacion, ErrorImportacion> {
+    let mut lector = LectorCsv::new(texto);
+    let encabezado = lector
+        .next()
+        .ok_or_else(|| ErrorImportacion::Archivo("el archivo esta vacio".to_string()))??;
+    let columnas = columnas_csv(&encabezado)
+        .ok_or_else(|| ErrorImportacion::Archivo("falta la columna \"Handle\"".to_string()))?;
+
+    let mut importador = ImportadorCsv {
+        columnas,
+        importacion: Importacion::default(),
+        por_handle: HashMap::new(),
+        descartados: HashSet::new(),
+    };
+    for registro in lector {
+        match registro {
+            Ok(registro) => {
+                if let Err(mensaje) = importador.procesar(&registro) {
+                    importador.importacion.errores.push(ErrorImportacion::Fila {
+                        linea: registro.linea,
+                        mensaje,
+                    });
+                }
+            }
+            Err(error) => importador.importacion.errores.push(error),
+        }
+    }
+    // Los ids se asignan al final, cuando ya se conocen todos los que trae el archivo.
+    if asignar_ids {
+        let productos = &mut importador.importacion.productos;
+        asignar_ids_faltantes(productos, &mut Ids::default(), &mut Ids::default());
+    }
+    Ok(importador.importacion)
+}
+
+struct ImportadorCsv {
+    columnas: HashMap<String, usize>,
+    importacion: Importacion,
+    /// Posición en `importacion.productos` de cada handle ya leído.
+    por_handle: HashMap<String, usize>,
+    /// Handles cuya primera fila falló; sus filas siguientes se omiten sin más errores.
+    descartados: HashSet<String>,
+}
+
+impl ImportadorCsv {
+    fn campo<'r>(&self, registro: &'r Registro<'_>, columna: &str) -> &'r str {
+        self.columnas
+            .get(columna)
+            .and_then(|&indice| registro.campos.get(indice))
+            .map_or("", |campo: &Cow<'_, str>| campo.trim())
+    }
+
+    fn procesar(&mut self, registro: &Registro<'_>) -> Result<(), String> {
+        let handle = self.campo(registro, "handle");
+        if handle.is_empty() {
+            return Err("falta el handle".to_string());
+        }
+        if self.descartados.contains(handle) {
+            return Ok(());
+        }
+
+        if let Some(&indice) = self.por_handle.get(handle) {
+            let mut producto = std::mem::take(&mut self.importacion.productos[indice]);
+            let resultado = self.completar(registro, &mut producto);
+            self.importacion.productos[indice] = producto;
+            return resultado;
+        }
+
+        let nuevo = self
+            .producto_nuevo(registro, handle)
+            .and_then(|mut producto| {
+                self.completar(registro, &mut producto)?;
+                Ok(producto)
+            });
+        match nuevo {
+            Ok(producto) => {
+                self.por_handle
+                    .insert(handle.to_string(), self.importacion.productos.len());
+                self.importacion.productos.push(producto);
+                Ok(())
+            }
+            Err(mensaje) => {
+                self.descartados.insert(handle.to_string());
+                Err(mensaje)
+            }
+        }
+    }
+
+    /// Agrega al producto la variante y la imagen de la fila; si algo no es válido no
+    /// agrega nada.
+    fn completar(&self, registro: &Registro<'_>, producto: &mut Product) -> Result<(), String> {
+        let imagen = self.imagen(registro, producto)?;
+        let variante = self.variante(registro, producto.opciones.len())?;
+        producto.variantes.extend(variante);
+        producto.imagenes.extend(imagen);
+        Ok(())
+    }
+
+    fn producto_nuevo(&self, registro: &Registro<'_>, handle: &str) -> Result<Product, String> {
+        let titulo = self.campo(registro, "title");
+        if titulo.is_empty() {
+            return Err(format!("la primera fila de \"{}\" no tiene titulo", handle));
+        }
+        // Sin id queda en 0 y se asigna al terminar de leer (ver `asignar_ids_faltantes`).
+        let id = match self.campo(registro, "id") {
+            "" => 0,
+            id => parsear_id(id).ok_or_else(|| format!("id invalido: \"{}\"", id))?,
+        };
+
+        let mut producto = Product::new(id, handle);
+        producto.titulo = titulo.to_string();
+        producto.descripcion = self.campo(registro, "body (html)").to_string();
+        producto.proveedor = self.campo(registro, "vendor").to_string();
+        producto.tipo_producto = match self.campo(registro, "type") {
+            "" => self.campo(registro, "product type").to_string(),
+            tipo => tipo.to_string(),
+        };
+        producto.etiquetas = separar_etiquetas(self.campo(registro, "tags"));
+        producto.opciones = COLUMNAS_OPCIONES
+            .iter()
+            .map(|(nombre, _)| self.campo(registro, nombre))
+            .take_while(|nombre| !nombre.is_empty())
+            .map(str::to_string)
+            .collect();
+        quitar_opcion_por_defecto(&mut producto.opciones);
+        Ok(producto)
+    }
+
+    /// Lee la variante de la fila, o `None` si la fila solo trae una imagen.
+    fn variante(
+        &self,
+        registro: &Registro<'_>,
+        opciones: usize,
+    ) -> Result<Option<Variante>, String> {
+        let precio = self.campo(registro, "variant price");
+        let valores: Vec<&str> = COLUMNAS_OPCIONES
+            .iter()
+            .map(|(_, valor)| self.campo(registro, valor))
+            .filter(|valor| !valor.is_empty() && *valor != "Default Title")
+            .collect();
+        if precio.is_empty() && valores.is_empty() && self.campo(registro, "variant sku").is_empty()
+        {
+            return Ok(None);
+        }
+
+        if valores.len() != opciones {
+            return Err(format!(
+                "la variante tiene {} valores de opcion y el producto {} opciones",
+                valores.len(),
+                opciones
+            ));
+        }
+        let precio_invalido = |texto: &str| format!("precio invalido: \"{}\"", texto);
+        let mut variante = Variante::new(
+            0,
+            parsear_precio(precio).ok_or_else(|| precio_invalido(precio))?,
+        )
+        .con_sku(self.campo(registro, "variant sku"))
+        .con_valores(&valores);
+        variante.precio_comparacion_centavos =
+            match self.campo(registro, "variant compare at price") {
+                "" => None,
+                texto => Some(parsear_precio(texto).ok_or_else(|| precio_invalido(texto))?),
+            };
+        variante.inventario = match self.campo(registro, "variant inventory qty") {
+            "" => 0,
+            texto => texto
+                .parse()
+                .map_err(|_| format!("inventario invalido: \"{}\"", texto))?,
+        };
+        variante.id = match self.campo(registro, "variant id") {
+            "" => 0,
+            id => parsear_id(id).ok_or_else(|| format!("id de variante invalido: \"{}\"", id))?,
+        };
+        Ok(Some(variante))
+    }
+
+    fn imagen(
+        &self,
+        registro: &Registro<'_>,
+        producto: &Product,
+    ) -> Result<Option<Imagen>, String> {
+        let src = self.campo(registro, "image src");
+        if src.is_empty() {
+            return Ok(None);
+        }
+        let posicion = match self.campo(registro, "image position") {
+            "" => producto.imagenes.len() as u32 + 1,
+            texto => texto
+                .parse()
+                .map_err(|_| format!("posicion de imagen invalida: \"{}\"", texto))?,
+        };
+        Ok(Some(Imagen {
+            src: src.to_string(),
+            alt: self.campo(registro, "image alt text").to_string(),
+            posicion,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::catalogo::Catalogo;
+
+    const PRODUCTS_JSON: &str = r#"{"products": [
+        {"id": 42, "handle": "simple-tee", "title": "Simple Tee", "body_html": "<p>Algodon</p>",
+         "vendor": "Acme", "product_type": "Camisetas", "tags": ["verano", "basico"],
+         "options": [{"name": "Talla", "position": 1, "values": ["M", "L"]}],
+         "variants": [
+            {"id": 1, "sku": "TEE-M", "price": "19.99", "compare_at_price": "24.99",
+             "option1": "M", "option2": null, "inventory_quantity": 5},
+            {"id": 2, "sku": "TEE-L", "price": 19.5, "compare_at_price": null, "option1": "L"}
+         ],
+         "images": [{"src": "https://cdn/tee.jpg", "alt": null, "position": 1}]},
+        {"id": 256, "handle": "casual-tee", "title": "Casual Tee", "tags": "a, b",
+         "options": [{"name": "Title"}],
+         "variants": [{"id": 3, "price": "abc", "option1": "Default Title"}]},
+        {"handle": "sin-id"}
+    ]}"#;
+
+    #[test]
+    fn test_importar_json() {
+        let importacion = importar_json(PRODUCTS_JSON).unwrap();
+        assert_eq!(importacion.productos.len(), 1);
+        let producto = &importacion.productos[0];
+        assert_eq!(producto.id, 42);
+        assert_eq!(producto.etiquetas, vec!["verano", "basico"]);
+        assert_eq!(producto.opciones, vec!["Talla"]);
+        assert_eq!(
+            producto.variantes[0].precio_comparacion_centavos,
+            Some(2499)
+        );
+        assert_eq!(producto.variantes[0].inventario, 5);
+        assert_eq!(producto.variantes[1].precio_centavos, 1950);
+        assert_eq!(producto.variantes[1].valores, vec!["L"]);
+        assert_eq!(producto.imagenes[0].alt, "");
+
+        assert_eq!(
+            importacion.errores,
+            vec![
+                ErrorImportacion::Producto {
+                    indice: 1,
+                    mensaje: "precio invalido: \"abc\"".to_string()
+                },
+                ErrorImportacion::Producto {
+                    indice: 2,
+                    mensaje: "falta el campo \"id\"".to_string()
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_importar_json_invalido() {
+        assert!(matches!(
+            importar_json("{"),
+            Err(ErrorImportacion::Archivo(_))
+        ));
+        assert_eq!(
+            importar_json("{\"items\": []}"),
+            Err(ErrorImportacion::Archivo(
+                "falta la lista \"products\"".to_string()
+            ))
+        );
+        assert_eq!(importar_json("[]"), Ok(Importacion::default()));
+    }
+
+    const PRODUCTS_CSV: &str = "\
+Handle,Title,Body (HTML),Vendor,Type,Tags,Option1 Name,Option1 Value,Option2 Name,Option2 Value,Variant SKU,Variant Inventory Qty,Variant Price,Variant Compare At Price,Image Src,Image Position,Image Alt Text
+simple-tee,Simple Tee,\"<p>Suave,
+ligera</p>\",Acme,Camisetas,\"verano, basico\",Talla,M,Color,Rojo,TEE-M-R,5,19.99,24.99,https://cdn/a.jpg,1,Frente
+simple-tee,,,,,,,L,,Rojo,TEE-L-R,-1,21,,https://cdn/b.jpg,2,
+simple-tee,,,,,,,XL,,,TEE-XL,,19.99,,,,
+simple-tee,,,,,,,,,,,,,,https://cdn/c.jpg,,
+gorra,,,,,,Title,Default Title,,,,,9.99,,,,
+taza,Taza,,Acme,,,Title,Default Title,,,TAZA,2,x,,,,
+calcetas,Calcetas,,,,,Title,Default Title,,,,,5,,,,
+";
+
+    #[test]
+    fn test_importar_csv() {
+        let importacion = importar_csv(PRODUCTS_CSV).unwrap();
+        let handles: Vec<&str> = importacion
+            .productos
+            .iter()
+            .map(|p| p.handle.as_str())
+            .collect();
+        assert_eq!(handles, vec!["simple-tee", "calcetas"]);
+
+        let tee = &importacion.productos[0];
+        assert_eq!((tee.id, importacion.productos[1].id), (1, 2));
+        assert_eq!(tee.descripcion, "<p>Suave,\nligera</p>");
+        assert_eq!(tee.etiquetas, vec!["verano", "basico"]);
+        assert_eq!(tee.opciones, vec!["Talla", "Color"]);
+        assert_eq!(tee.variantes.len(), 2);
+        assert_eq!(tee.variantes[1].valores, vec!["L", "Rojo"]);
+        assert_eq!(tee.variantes[1].precio_centavos, 2100);
+        assert_eq!(tee.variantes[1].inventario, -1);
+        let posiciones: Vec<u32> = tee.imagenes.iter().map(|i| i.posicion).collect();
+        assert_eq!(posiciones, vec![1, 2, 3]);
+        assert!(importacion.productos[1].opciones.is_empty());
+
+        let lineas: Vec<usize> = importacion
+            .errores
+            .iter()
+            .map(|e| match e {
+                ErrorImportacion::Fila { linea, .. } => *linea,
+                otro => panic!("error inesperado: {}", otro),
+            })
+            .collect();
+        // La descripción ocupa dos líneas, así que las filas de datos empiezan en 2, 4, 5...
+        assert_eq!(lineas, vec![5, 7, 8]);
+        assert_eq!(
+            importacion.errores[2].to_string(),
+            "linea 8: precio invalido: \"x\""
+        );
+    }
+
+    #[test]
+    fn test_importar_csv_ids_mezclados() {
+        // Los ids que faltan saltan los que ya vienen en el archivo, aunque aparezcan después.
+        let texto = "Handle,ID,Title,Variant ID,Variant Price\n\
+                     a,1,A,,1.00\n\
+                     b,,B,,2.00\n\
+                     c,,C,1,3.00\n\
+                     d,2,D,,4.00\n\
+                     e,0,E,,5.00\n";
+        let importacion = importar_csv(texto).unwrap();
+        let ids: Vec<(u32, u32)> = importacion
+            .productos
+            .iter()
+            .map(|p| (p.id, p.variantes[0].id))
+            .collect();
+        assert_eq!(ids, vec![(1, 2), (3, 3), (4, 1), (2, 4)]);
+        assert_eq!(
+            importacion.errores[0].to_string(),
+            "linea 6: id invalido: \"0\""
+        );
+        assert!(Catalogo::desde_productos(importacion.productos).is_ok());
+    }
+
+    #[test]
+    fn test_importar_csv_invalido() {
+        assert_eq!(
+            importar_csv(""),
+            Err(ErrorImportacion::Archivo(
+                "el archivo esta vacio".to_string()
+            ))
+        );
+        assert_eq!(
+            importar_csv("Title,Vendor\n"),
+            Err(ErrorImportacion::Archivo(
+                "falta la columna \"Handle\"".to_string()
+            ))
+        );
+
+        // Un registro mal entrecomillado no impide leer los siguientes.
+        let importacion = importar_csv("Handle,Title\n\"tee\"x,Tee\ntaza,Taza\n").unwrap();
+        assert_eq!(importacion.productos.len(), 1);
+        assert_eq!(importacion.productos[0].handle, "taza");
+        assert_eq!(
+            importacion.errores[0].to_string(),
+            "linea 2: texto despues de cerrar las comillas"
+        );
+    }
+
+    #[test]
+    fn test_parsear_precio() {
+        assert_eq!(parsear_precio("19.99"), Some(1999));
+        assert_eq!(parsear_precio("5.5"), Some(550));
+        assert_eq!(parsear_precio("20"), Some(2000));
+        assert_eq!(parsear_precio("-1.50"), Some(-150));
+        for invalido in ["", "-", "--1", "+1", "- 1", "1.999", "1,5", ".5"] {
+            assert_eq!(parsear_precio(invalido), None);
+        }
+    }
+}
@@ -0,0 +1,666 @@
+// src/inventario.rs
+
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+
+use crate::catalogo::Catalogo;
+
+/// Umbral de stock bajo cuando no se define uno para la variante.
+pub const UMBRAL_BAJO_PREDETERMINADO: i64 = 5;
+
+/// Errores al ajustar, transferir o reservar inventario.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ErrorInventario {
+    /// No hay suficientes unidades disponibles de la variante en la ubicación.
+    StockInsuficiente {
+        variante: u32,
+        ubicacion: String,
+        disponible: i64,
+        solicitado: i64,
+    },
+    /// Una cantidad de inventario debe ser positiva (o distinta de cero en un ajuste).
+    CantidadInvalida(i64),
+    /// No existe la reserva (o ya se liberó o confirmó).
+    ReservaNoEncontrada(u64),
+    /// La reserva venció antes de confirmarse.
+    ReservaExpirada(u64),
+}
+
+impl fmt::Display for ErrorInventario {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ErrorInventario::StockInsuficiente {
+                variante,
+                ubicacion,
+                disponible,
+                solicitado,
+            } => write!(
+                f,
+                "stock insuficiente de la variante {} en {}: hay {} disponibles, se piden {}",
+                variante, ubicacion, disponible, solicitado
+            ),
+            ErrorInventario::CantidadInvalida(cantidad) => {
+                write!(f, "cantidad invalida: {}", cantidad)
+            }
+            ErrorInventario::ReservaNoEncontrada(id) => write!(f, "no existe la reserva {}", id),
+            ErrorInventario::ReservaExpirada(id) => write!(f, "la reserva {} expiro", id),
+        }
+    }
+}
+
+impl std::error::Error for ErrorInventario {}
+
+/// Por qué cambió la cantidad en mano de una variante.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MotivoAjuste {
+    /// Llegó mercancía del proveedor.
+    Recepcion,
+    /// Se despachó un pedido.
+    Venta,
+    /// Un cliente devolvió unidades.
+    Devolucion,
+    /// Unidades dañadas, perdidas o robadas.
+    Merma,
+    /// Conteo físico que corrige la cantidad registrada.
+    Conteo,
+    /// Movimiento entre ubicaciones.
+    Transferencia,
+    Otro(String),
+}
+
+impl fmt::Display for MotivoAjuste {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MotivoAjuste::Recepcion => write!(f, "recepcion"),
+            MotivoAjuste::Venta => write!(f, "venta"),
+            MotivoAjuste::Devolucion => write!(f, "devolucion"),
+            MotivoAjuste::Merma => write!(f, "merma"),
+            MotivoAjuste::Conteo => write!(f, "conteo"),
+            MotivoAjuste::Transferencia => write!(f, "transferencia"),
+            MotivoAjuste::Otro(motivo) => write!(f, "{}", motivo),
+        }
+    }
+}
+
+/// Una entrada del historial de ajustes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Ajuste {
+    pub variante: u32,
+    pub ubicacion: String,
+    /// Unidades agregadas (positivo) o quitadas (negativo).
+    pub cambio: i64,
+    /// Cantidad en mano después del ajuste.
+    pub en_mano: i64,
+    pub motivo: MotivoAjuste,
+    /// Marca de tiempo Unix en segundos.
+    pub fecha: u64,
+}
+
+/// Unidades apartadas por un carrito hasta `expira`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Reserva {
+    pub id: u64,
+    pub variante: u32,
+    pub ubicacion: String,
+    pub cantidad: i64,
+    /// Marca de tiempo Unix (segundos) desde la que la reserva ya no cuenta.
+    pub expira: u64,
+}
+
+/// Aviso que `Inventario` publica a sus oyentes cuando baja el stock de una variante.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EventoInventario {
+    /// Lo disponible en todas las ubicaciones bajó al umbral o por debajo.
+    StockBajo { variante: u32, disponible: i64 },
+    /// Ya no queda nada disponible.
+    Agotado { variante: u32 },
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+struct Existencia {
+    en_mano: i64,
+    comprometido: i64,
+}
+
+type Oyente = Box<dyn FnMut(&EventoInventario)>;
+
+/// Inventario por variante y ubicación.
+///
+/// Para cada variante y ubicación distingue:
+/// - en mano: unidades físicamente en la ubicación;
+/// - comprometido: unidades de pedidos confirmados que aún no se despachan;
+/// - reservado: unidades apartadas por carritos, que se liberan solas al vencer;
+/// - disponible: en mano − comprometido − reservado.
+///
+/// El tiempo se pasa explícitamente (segundos Unix) para que las reservas se puedan probar
+/// sin esperar. Cada cambio en mano queda en el historial con su motivo, y cuando lo
+/// disponible de una variante cruza su umbral se avisa a los oyentes (una sola vez por
+/// cruce, no en cada venta).
+pub struct Inventario {
+    existencias: BTreeMap<(u32, String), Existencia>,
+    reservas: HashMap<u64, Reserva>,
+    historial: Vec<Ajuste>,
+    umbrales: HashMap<u32, i64>,
+    umbral_predeterminado: i64,
+    oyentes: Vec<Oyente>,
+    siguiente_reserva: u64,
+}
+
+impl fmt::Debug for Inventario {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Inventario")
+            .field("existencias", &self.existencias)
+            .field("reservas", &self.reservas)
+            .field("historial", &self.historial)
+            .field("oyentes", &self.oyentes.len())
+            .finish()
+    }
+}
+
+impl Default for Inventario {
+    fn default() -> Inventario {
+        Inventario::new()
+    }
+}
+
+impl Inventario {
+    pub fn new() -> Inventario {
+        Inventario {
+            existencias: BTreeMap::new(),
+            reservas: HashMap::new(),
+            historial: Vec::new(),
+            umbrales: HashMap::new(),
+            umbral_predeterminado: UMBRAL_BAJO_PREDETERMINADO,
+            oyentes: Vec::new(),
+            siguiente_reserva: 1,
+        }
+    }
+
+    /// Crea el inventario con el `inventario` de cada variante del catálogo en una sola
+    /// ubicación. Las cantidades quedan en el historial como `MotivoAjuste::Conteo`.
+    pub fn desde_catalogo(catalogo: &Catalogo, ubicacion: &str, ahora: u64) -> Inventario {
+        let mut inventario = Inventario::new();
+        for variante in catalogo.iter().flat_map(|p| &p.variantes) {
+            if variante.inventario != 0 {
+                inventario.registrar(
+                    variante.id,
+                    ubicacion,
+                    variante.inventario,
+                    MotivoAjuste::Conteo,
+                    ahora,
+                );
+            }
+        }
+        inventario
+    }
+
+    /// Copia lo disponible (sumando ubicaciones) al `inventario` de cada variante del
+    /// catálogo, para que `Product::disponible` y las consultas lo reflejen.
+    pub fn actualizar_catalogo(&self, catalogo: &mut Catalogo, ahora: u64) {
+        let ids: Vec<u32> = catalogo.iter().map(|p| p.id).collect();
+        for id in ids {
+            catalogo
+                .modificar(id, |producto| {
+                    for variante in &mut producto.variantes {
+                        variante.inventario = self.disponible_total(variante.id, ahora);
+                    }
+                })
+                .expect("el id viene del mismo catalogo");
+        }
+    }
+
+    /// Cambia el umbral de stock bajo de todas las variantes sin umbral propio.
+    pub fn con_umbral_bajo(mut self, umbral: i64) -> Inventario {
+        self.umbral_predeterminado = umbral;
+        self
+    }
+
+    pub fn definir_umbral(&mut self, variante: u32, umbral: i64) {
+        self.umbrales.insert(variante, umbral);
+    }
+
+    /// Registra una función que recibe los `EventoInventario`.
+    pub fn suscribir<F>(&mut self, oyente: F)
+    where
+        F: FnMut(&EventoInventario) + 'static,
+    {
+        self.oyentes.push(Box::new(oyente));
+    }
+
+    pub fn en_mano(&self, variante: u32, ubicacion: &str) -> i64 {
+        self.existencia(variante, ubicacion).en_mano
+    }
+
+    pub fn comprometido(&self, variante: u32, ubicacion: &str) -> i64 {
+        self.existencia(variante, ubicacion).comprometido
+    }
+
+    /// Unidades en reservas vigentes a la hora `ahora`.
+    pub fn reservado(&self, variante: u32, ubicacion: &str, ahora: u64) -> i64 {
+        self.reservas
+            .values()
+            .filter(|r| r.variante == variante && r.ubicacion == ubicacion && r.expira > ahora)
+            .map(|r| r.cantidad)
+            .sum()
+    }
+
+    pub fn disponible(&self, variante: u32, ubicacion: &str, ahora: u64) -> i64 {
+        let existencia = self.existencia(variante, ubicacion);
+        existencia.en_mano - existencia.comprometido - self.reservado(variante, ubicacion, ahora)
+    }
+
+    /// Disponible sumando todas las ubicaciones.
+    pub fn disponible_total(&self, variante: u32, ahora: u64) -> i64 {
+        self.ubicaciones(variante)
+            .iter()
+            .map(|ubicacion| self.disponible(variante, ubicacion, ahora))
+            .sum()
+    }
+
+    /// Ubicaciones donde se ha registrado la variante, en orden alfabético.
+    pub fn ubicaciones(&self, variante: u32) -> Vec<&str> {
+        self.existencias
+            .range((variante, String::new())..)
+            .take_while(|((id, _), _)| *id == variante)
+            .map(|((_, ubicacion), _)| ubicacion.as_str())
+            .collect()
+    }
+
+    pub fn historial(&self) -> &[Ajuste] {
+        &self.historial
+    }
+
+    pub fn historial_de(&self, variante: u32) -> impl Iterator<Item = &Ajuste> {
+        self.historial
+            .iter()
+            .filter(move |a| a.variante == variante)
+    }
+
+    pub fn reserva(&self, id: u64) -> Option<&Reserva> {
+        self.reservas.get(&id)
+    }
+
+    /// Suma (o resta, si `cambio` es negativo) unidades en mano.
+    ///
+    /// La cantidad en mano puede quedar por debajo de lo comprometido, como cuando se
+    /// descubre una merma después de vender; entonces lo disponible queda negativo.
+    pub fn ajustar(
+        &mut self,
+        variante: u32,
+        ubicacion: &str,
+        cambio: i64,
+        motivo: MotivoAjuste,
+        ahora: u64,
+    ) -> Result<(), ErrorInventario> {
+        if cambio == 0 {
+            return Err(ErrorInventario::CantidadInvalida(cambio));
+        }
+        self.con_avisos(variante, ahora, |inventario| {
+            inventario.registrar(variante, ubicacion, cambio, motivo, ahora);
+            Ok(())
+        })
+    }
+
+    /// Fija la cantidad en mano tras un conteo físico; el historial guarda la diferencia.
+    pub fn establecer(
+        &mut self,
+        variante: u32,
+        ubicacion: &str,
+        cantidad: i64,
+        ahora: u64,
+    ) -> Result<(), ErrorInventario> {
+        let cambio = cantidad - self.en_mano(variante, ubicacion);
+        if cambio == 0 {
+            return Ok(());
+        }
+        self.ajustar(variante, ubicacion, cambio, MotivoAjuste::Conteo, ahora)
+    }
+
+    /// Mueve unidades disponibles de una ubicación a otra.
+    pub fn transferir(
+        &mut self,
+        variante: u32,
+        desde: &str,
+        hacia: &str,
+        cantidad: i64,
+        ahora: u64,
+    ) -> Result<(), ErrorInventario> {
+        self.validar_disponible(variante, desde, cantidad, ahora)?;
+        self.registrar(
+            variante,
+            desde,
+            -cantidad,
+            MotivoAjuste::Transferencia,
+            ahora,
+        );
+        self.registrar(
+            variante,
+            hacia,
+            cantidad,
+            MotivoAjuste::Transferencia,
+            ahora,
+        );
+        Ok(())
+    }
+
+    /// Aparta unidades para un carrito durante `duracion` segundos.
+    ///
+    /// # Retorno
+    /// Retorna el id de la reserva, o `StockInsuficiente` si no hay suficientes
+    /// unidades disponibles en la ubicación.
+    pub fn reservar(
+        &mut self,
+        variante: u32,
+        ubicacion: &str,
+        cantidad: i64,
+        ahora: u64,
+        duracion: u64,
+    ) -> Result<u64, ErrorInventario> {
+        self.con_avisos(variante, ahora, |inventario| {
+            inventario.validar_disponible(variante, ubicacion, cantidad, ahora)?;
+            let id = inventario.siguiente_reserva;
+            inventario.siguiente_reserva += 1;
+            inventario.reservas.insert(
+                id,
+                Reserva {
+                    id,
+                    variante,
+                    ubicacion: ubicacion.to_string(),
+                    cantidad,
+                    expira: ahora.saturating_add(duracion),
+                },
+            );
+            Ok(id)
+        })
+    }
+
+    /// Cancela una reserva y devuelve sus unidades a lo disponible.
+    pub fn liberar(&mut self, id: u64) -> Result<Reserva, ErrorInventario> {
+        self.reservas
+            .remove(&id)
+            .ok_or(ErrorInventario::ReservaNoEncontrada(id))
+    }
+
+    /// Convierte una reserva vigente en unidades comprometidas (el pedido se pagó).
+    pub fn confirmar(&mut self, id: u64, ahora: u64) -> Result<(), ErrorInventario> {
+        let reserva = self.liberar(id)?;
+        if reserva.expira <= ahora {
+            return Err(ErrorInventario::ReservaExpirada(id));
+        }
+        self.existencias
+            .entry((reserva.variante, reserva.ubicacion))
+            .or_default()
+            .comprometido += reserva.cantidad;
+        Ok(())
+    }
+
+    /// Despacha unidades comprometidas: salen de lo comprometido y de lo que hay en mano.
+    pub fn despachar(
+        &mut self,
+        variante: u32,
+        ubicacion: &str,
+        cantidad: i64,
+        ahora: u64,
+    ) -> Result<(), ErrorInventario> {
+        if cantidad <= 0 {
+            return Err(ErrorInventario::CantidadInvalida(cantidad));
+        }
+        let comprometido = self.comprometido(variante, ubicacion);
+        if comprometido < cantidad {
+            return Err(ErrorInventario::StockInsuficiente {
+                variante,
+                ubicacion: ubicacion.to_string(),
+                disponible: comprometido,
+                solicitado: cantidad,
+            });
+        }
+        self.existencias
+            .entry((variante, ubicacion.to_string()))
+            .or_default()
+            .comprometido -= cantidad;
+        self.registrar(variante, ubicacion, -cantidad, MotivoAjuste::Venta, ahora);
+        Ok(())
+    }
+
+    /// Elimina las reservas vencidas; retorna cuántas quitó.
+    ///
+    /// Las reservas vencidas ya no cuentan en lo disponible aunque no se llame a esta
+    /// función; solo sirve para liberar memoria.
+    pub fn expirar(&mut self, ahora: u64) -> usize {
+        let antes = self.reservas.len();
+        self.reservas.retain(|_, reserva| reserva.expira > ahora);
+        antes - self.reservas.len()
+    }
+
+    fn existencia(&self, variante: u32, ubicacion: &str) -> Existencia {
+        self.existencias
+            .get(&(variante, ubicacion.to_string()))
+            .copied()
+            .unwrap_or_default()
+    }
+
+    fn validar_disponible(
+        &self,
+        variante: u32,
+        ubicacion: &str,
+        cantidad: i64,
+        ahora: u64,
+    ) -> Result<(), ErrorInventario> {
+        if cantidad <= 0 {
+            return Err(ErrorInventario::CantidadInvalida(cantidad));
+        }
+        let disponible = self.disponible(variante, ubicacion, ahora);
+        if disponible < cantidad {
+            return Err(ErrorInventario::StockInsuficiente {
+                variante,
+                ubicacion: ubicacion.to_string(),
+                disponible,
+                solicitado: cantidad,
+            });
+        }
+        Ok(())
+    }
+
+    /// Cambia la cantidad en mano y lo anota en el historial, sin validar ni avisar.
+    fn registrar(
+        &mut self,
+        variante: u32,
+        ubicacion: &str,
+        cambio: i64,
+        motivo: MotivoAjuste,
+        ahora: u64,
+    ) {
+        let existencia = self
+            .existencias
+            .entry((variante, ubicacion.to_string()))
+            .or_default();
+        existencia.en_mano += cambio;
+        self.historial.push(Ajuste {
+            variante,
+            ubicacion: ubicacion.to_string(),
+            cambio,
+            en_mano: existencia.en_mano,
+            motivo,
+            fecha: ahora,
+        });
+    }
+
+    /// Ejecuta `operacion` y avisa a los oyentes si lo disponible de la variante cruzó
+    /// su umbral o se agotó.
+    fn con_avisos<T>(
+        &mut self,
+        variante: u32,
+        ahora: u64,
+        operacion: impl FnOnce(&mut Inventario) -> Result<T, ErrorInventario>,
+    ) -> Result<T, ErrorInventario> {
+        let antes = self.disponible_total(variante, ahora);
+        let resultado = operacion(self)?;
+        let despues = self.disponible_total(variante, ahora);
+        let umbral = self
+            .umbrales
+            .get(&variante)
+            .copied()
+            .unwrap_or(self.umbral_predeterminado);
+
+        let evento = if despues <= 0 && antes > 0 {
+            Some(EventoInventario::Agotado { variante })
+        } else if despues <= umbral && antes > umbral {
+            Some(EventoInventario::StockBajo {
+                variante,
+                disponible: despues,
+            })
+        } else {
+            None
+        };
+        if let Some(evento) = evento {
+            for oyente in &mut self.oyentes {
+                oyente(&evento);
+            }
+        }
+        Ok(resultado)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    use super::*;
+    use crate::{Product, Variante};
+
+    const HORA: u64 = 3600;
+
+    fn inventario() -> Inventario {
+        let mut inventario = Inventario::new();
+        inventario
+            .ajustar(1, "centro", 10, MotivoAjuste::Recepcion, 0)
+            .unwrap();
+        inventario
+            .ajustar(1, "norte", 4, MotivoAjuste::Recepcion, 0)
+            .unwrap();
+        inventario
+    }
+
+    #[test]
+    fn test_reservas_y_compromisos() {
+        let mut inventario = inventario();
+        let reserva = inventario.reservar(1, "centro", 3, 100, HORA).unwrap();
+        assert_eq!(inventario.disponible(1, "centro", 100), 7);
+        assert_eq!(inventario.disponible_total(1, 100), 11);
+
+        inventario.confirmar(reserva, 200).unwrap();
+        assert_eq!(inventario.comprometido(1, "centro"), 3);
+        assert_eq!(inventario.disponible(1, "centro", 200), 7);
+
+        inventario.despachar(1, "centro", 3, 300).unwrap();
+        assert_eq!(inventario.en_mano(1, "centro"), 7);
+        assert_eq!(inventario.comprometido(1, "centro"), 0);
+        assert_eq!(
+            inventario.despachar(1, "centro", 1, 300),
+            Err(ErrorInventario::StockInsuficiente {
+                variante: 1,
+                ubicacion: "centro".to_string(),
+                disponible: 0,
+                solicitado: 1
+            })
+        );
+    }
+
+    #[test]
+    fn test_reservas_vencidas() {
+        let mut inventario = inventario();
+        let reserva = inventario.reservar(1, "norte", 4, 0, HORA).unwrap();
+        assert!(inventario.reservar(1, "norte", 1, 10, HORA).is_err());
+        // Al vencer, las unidades vuelven a estar disponibles sin hacer nada.
+        assert_eq!(inventario.disponible(1, "norte", HORA), 4);
+        assert_eq!(
+            inventario.confirmar(reserva, HORA),
+            Err(ErrorInventario::ReservaExpirada(reserva))
+        );
+        assert_eq!(
+            inventario.liberar(reserva),
+            Err(ErrorInventario::ReservaNoEncontrada(reserva))
+        );
+
+        inventario.reservar(1, "norte", 1, 0, 10).unwrap();
+        assert_eq!(inventario.expirar(10), 1);
+    }
+
+    #[test]
+    fn test_historial_y_transferencias() {
+        let mut inventario = inventario();
+        inventario.transferir(1, "centro", "norte", 6, 50).unwrap();
+        inventario.establecer(1, "norte", 9, 60).unwrap();
+        assert_eq!(inventario.en_mano(1, "norte"), 9);
+        assert_eq!(inventario.ubicaciones(1), vec!["centro", "norte"]);
+
+        let motivos: Vec<(i64, String)> = inventario
+            .historial_de(1)
+            .map(|a| (a.cambio, a.motivo.to_string()))
+            .collect();
+        assert_eq!(
+            motivos,
+            vec![
+                (10, "recepcion".to_string()),
+                (4, "recepcion".to_string()),
+                (-6, "transferencia".to_string()),
+                (6, "transferencia".to_string()),
+                (-1, "conteo".to_string()),
+            ]
+        );
+        assert!(inventario.transferir(1, "centro", "norte", 5, 70).is_err());
+        assert_eq!(
+            inventario.ajustar(1, "centro", 0, MotivoAjuste::Merma, 70),
+            Err(ErrorInventario::CantidadInvalida(0))
+        );
+    }
+
+    #[test]
+    fn test_avisos_de_stock_bajo() {
+        let eventos = Rc::new(RefCell::new(Vec::new()));
+        let mut inventario = inventario();
+        let registro = Rc::clone(&eventos);
+        inventario.suscribir(move |evento| registro.borrow_mut().push(evento.clone()));
+        inventario.definir_umbral(1, 10);
+
+        inventario.reservar(1, "centro", 2, 0, HORA).unwrap(); // 14 -> 12
+        inventario.reservar(1, "centro", 3, 0, HORA).unwrap(); // 12 -> 9: cruza el umbral
+        inventario.reservar(1, "centro", 1, 0, HORA).unwrap(); // 9 -> 8: ya estaba bajo
+        inventario
+            .ajustar(1, "norte", -4, MotivoAjuste::Merma, 0)
+            .unwrap();
+        inventario
+            .ajustar(1, "centro", -4, MotivoAjuste::Merma, 0)
+            .unwrap(); // 4 -> 0
+
+        assert_eq!(
+            *eventos.borrow(),
+            vec![
+                EventoInventario::StockBajo {
+                    variante: 1,
+                    disponible: 9
+                },
+                EventoInventario::Agotado { variante: 1 },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_catalogo() {
+        let mut catalogo = Catalogo::desde_productos(vec![
+            Product::new(1, "tee")
+                .con_variante(Variante::new(7, 1999).con_inventario(3))
+                .con_variante(Variante::new(8, 1999)),
+        ])
+        .unwrap();
+        let mut inventario = Inventario::desde_catalogo(&catalogo, "centro", 0);
+        assert_eq!(inventario.en_mano(7, "centro"), 3);
+        inventario.reservar(7, "centro", 3, 0, HORA).unwrap();
+
+        inventario.actualizar_catalogo(&mut catalogo, 0);
+        assert!(!catalogo.por_id(1).unwrap().disponible());
+        inventario.actualizar_catalogo(&mut catalogo, HORA);
+        assert_eq!(catalogo.por_id(1).unwrap().inventario_total(), 3);
+    }
+}
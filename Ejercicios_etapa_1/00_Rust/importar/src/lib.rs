@@ -0,0 +1,47 @@
+// src/lib.rs
+
+// El modelo del catálogo vive en `producto`; `Product` se reexporta en la raíz para que
+// `utils` y `get_product_handles_and_ids` lo importen como `crate::Product`.
+
+mod busqueda;
+mod carrito;
+mod catalogo;
+mod coleccion;
+mod consulta;
+mod csv;
+mod diferencias;
+mod error;
+mod exportacion;
+pub mod get_product_handles_and_ids;
+mod handle;
+mod importacion;
+mod inventario;
+mod masiva;
+mod matriz;
+mod producto;
+pub mod utils;
+
+pub use busqueda::{CatalogoIndexado, Coincidencia, IndiceBusqueda};
+pub use carrito::{
+    AlcanceDescuento, Carrito, CodigoDescuento, Descuento, DescuentoAplicado, ErrorCarrito,
+    LineaCarrito, LineaTotal, OpcionEnvio, TarifaEnvio, TarifaFija, TarifaPorPrecio, Totales,
+};
+pub use catalogo::Catalogo;
+pub use coleccion::{Coleccion, OrdenColeccion, Regla, TipoColeccion};
+pub use consulta::{CampoOrden, Consulta, Cursor, Filtro, Orden, Resultado};
+pub use diferencias::{CambioCampo, Diferencias, ProductoModificado, comparar};
+pub use error::ErrorImportacion;
+pub use exportacion::{
+    COLUMNAS_CSV, exportar_archivo, exportar_csv, exportar_json, exportar_liquid,
+};
+pub use handle::{
+    CambioHandle, MAX_LONGITUD_HANDLE, handle_unico, normalizar_handles, slugify, validar_handle,
+};
+pub use importacion::{Importacion, importar_archivo, importar_csv, importar_json};
+pub use inventario::{
+    Ajuste, ErrorInventario, EventoInventario, Inventario, MotivoAjuste, Reserva,
+    UMBRAL_BAJO_PREDETERMINADO,
+};
+pub use masiva::{FILAS_POR_BLOQUE_PREDETERMINADO, ImportadorMasivo, Progreso};
+pub use matriz::{DefinicionOpcion, ErrorMatriz, MatrizVariantes, sku_variante};
+pub use producto::{Imagen, MAX_OPCIONES, Product, Variante};
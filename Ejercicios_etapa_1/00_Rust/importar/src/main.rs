@@ -1,26 +1,92 @@
 // src/main.rs
 
-// TODO: Importa get_product_handles_and_ids desde get_product_handles_and_ids.rs
-// TODO: Importa el módulo utils también
+// El catálogo y las funciones auxiliares viven en la librería (lib.rs).
+//
+//   cargo run                       usa un catálogo de ejemplo
+//   cargo run -- products.json      importa un products.json o un CSV de Shopify
+//                                   (los CSV se importan en paralelo, mostrando el avance)
 
-mod utils;
-mod get_product_handles_and_ids;
+use std::path::Path;
+use std::process;
 
-use get_product_handles_and_ids::get_product_handles_and_ids;
-
-#[derive(Debug)]
-pub struct Product {
-    pub id: u32,
-    pub handle: String,
-}
+use importar::get_product_handles_and_ids::get_product_handles_and_ids;
+use importar::{
+    Catalogo, ErrorImportacion, Importacion, ImportadorMasivo, Product, Progreso, Variante,
+    importar_archivo, normalizar_handles,
+};
 
 fn main() {
-    let products = vec![
-        Product { id: 42, handle: "simple-tee".to_string() },
-        Product { id: 256, handle: "casual-tee".to_string() },
-    ];
+    let mut products = match std::env::args().nth(1) {
+        Some(ruta) => match importar(Path::new(&ruta)) {
+            Ok(importacion) => {
+                for error in &importacion.errores {
+                    eprintln!("Aviso: {}", error);
+                }
+                importacion.productos
+            }
+            Err(error) => {
+                eprintln!("Error: {}", error);
+                process::exit(1);
+            }
+        },
+        None => productos_de_ejemplo(),
+    };
+
+    // Los handles importados pueden venir repetidos o con caracteres no permitidos.
+    for cambio in normalizar_handles(&mut products) {
+        eprintln!(
+            "Aviso: producto {}: handle \"{}\" cambiado a \"{}\"",
+            cambio.id, cambio.anterior, cambio.nuevo
+        );
+    }
+
+    let catalogo = match Catalogo::desde_productos(products) {
+        Ok(catalogo) => catalogo,
+        Err(error) => {
+            eprintln!("Error: {}", error);
+            process::exit(1);
+        }
+    };
 
-    let resultado = get_product_handles_and_ids(&products);
+    let resultado = get_product_handles_and_ids(&catalogo);
 
     println!("Resultado: {:?}", resultado);
 }
+
+fn importar(ruta: &Path) -> Result<Importacion, ErrorImportacion> {
+    let es_csv = ruta
+        .extension()
+        .is_some_and(|extension| extension.eq_ignore_ascii_case("csv"));
+    if !es_csv {
+        return importar_archivo(ruta);
+    }
+    ImportadorMasivo::new().importar_archivo(ruta, |avance: &Progreso| {
+        let total = avance.bytes_totales.unwrap_or(0).max(1);
+        eprintln!(
+            "Importados {} productos ({}%)",
+            avance.productos,
+            avance.bytes * 100 / total
+        );
+    })
+}
+
+fn productos_de_ejemplo() -> Vec<Product> {
+    vec![
+        Product::new(42, "simple-tee")
+            .con_titulo("Simple Tee")
+            .con_proveedor("Acme")
+            .con_tipo("Camisetas")
+            .con_etiquetas(&["verano", "algodon"])
+            .con_opciones(&["Talla"])
+            .con_variante(
+                Variante::new(1, 1999)
+                    .con_valores(&["M"])
+                    .con_inventario(10),
+            )
+            .con_variante(Variante::new(2, 1999).con_valores(&["L"]).con_inventario(4)),
+        Product::new(256, "casual-tee")
+            .con_titulo("Casual Tee")
+            .con_proveedor("Acme")
+            .con_variante(Variante::new(3, 2499).con_precio_comparacion(2999)),
+    ]
+}
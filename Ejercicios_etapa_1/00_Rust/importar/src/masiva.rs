@@ -0,0 +1,672 @@
+// src/masiva.rs
+
+use std::collections::{BTreeMap, HashSet};
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::num::NonZero;
+use std::path::Path;
+use std::sync::mpsc::{self, SyncSender};
+use std::sync::{Condvar, Mutex};
+use std::thread;
+
+use crate::csv::LectorCsv;
+use crate::error::ErrorImportacion;
+use crate::importacion::{
+    Ids, Importacion, asignar_ids_faltantes, columnas_csv, importar_bloque_csv,
+};
+use crate::producto::Product;
+
+/// Filas de datos por bloque si no se indica otra cantidad.
+pub const FILAS_POR_BLOQUE_PREDETERMINADO: usize = 5_000;
+
+/// Avance de una importación masiva, acumulado hasta el último bloque terminado.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Progreso {
+    pub bloques: usize,
+    /// Registros de datos leídos (sin el encabezado ni las líneas vacías).
+    pub filas: usize,
+    pub bytes: u64,
+    /// Tamaño del archivo, si se conoce (ver `ImportadorMasivo::importar_archivo`).
+    pub bytes_totales: Option<u64>,
+    pub productos: usize,
+    pub errores: usize,
+}
+
+/// Importa CSV de Shopify grandes en paralelo, sin cargar el archivo completo.
+///
+/// Un hilo lee el archivo y lo corta en bloques de unas `filas_por_bloque` filas, sin
+/// partir nunca las filas de un mismo producto; varios hilos los procesan con las mismas
+/// reglas que `importar_csv` y el hilo que llama une los resultados en el orden del
+/// archivo. El lector no se adelanta más de dos bloques por hilo al siguiente que toca
+/// entregar, así que aunque un bloque tarde, en memoria hay a lo más esos bloques además
+/// de lo ya importado.
+///
+/// El resultado es el mismo que el de `importar_csv`, con una diferencia: las filas de un
+/// producto deben estar juntas (como en las exportaciones de Shopify), y si un handle
+/// vuelve a aparecer en otro bloque se reporta `HandleDuplicado` y se omite.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ImportadorMasivo {
+    hilos: usize,
+    filas_por_bloque: usize,
+}
+
+impl Default for ImportadorMasivo {
+    fn default() -> ImportadorMasivo {
+        ImportadorMasivo::new()
+    }
+}
+
+/// Un pedazo del CSV con el encabezado copiado al principio.
+struct Bloque {
+    indice: usize,
+    /// Línea del archivo donde empieza el primer registro del bloque.
+    primera_linea: usize,
+    lineas_encabezado: usize,
+    filas: usize,
+    bytes: u64,
+    texto: String,
+}
+
+enum Mensaje {
+    Procesado {
+        bloque: Bloque,
+        resultado: Result<Importacion, ErrorImportacion>,
+    },
+    ErrorLectura(ErrorImportacion),
+}
+
+impl ImportadorMasivo {
+    /// Un hilo de trabajo por núcleo y `FILAS_POR_BLOQUE_PREDETERMINADO` filas por bloque.
+    pub fn new() -> ImportadorMasivo {
+        ImportadorMasivo {
+            hilos: thread::available_parallelism().map_or(1, NonZero::get),
+            filas_por_bloque: FILAS_POR_BLOQUE_PREDETERMINADO,
+        }
+    }
+
+    pub fn con_hilos(mut self, hilos: usize) -> ImportadorMasivo {
+        self.hilos = hilos.max(1);
+        self
+    }
+
+    pub fn con_filas_por_bloque(mut self, filas: usize) -> ImportadorMasivo {
+        self.filas_por_bloque = filas.max(1);
+        self
+    }
+
+    /// Importa un archivo CSV; `progreso` se llama después de cada bloque y conoce el
+    /// tamaño del archivo en `Progreso::bytes_totales`.
+    pub fn importar_archivo<F>(
+        &self,
+        ruta: &Path,
+        progreso: F,
+    ) -> Result<Importacion, ErrorImportacion>
+    where
+        F: FnMut(&Progreso),
+    {
+        let archivo_invalido =
+            |e: std::io::Error| ErrorImportacion::Archivo(format!("{}: {}", ruta.display(), e));
+        let archivo = File::open(ruta).map_err(archivo_invalido)?;
+        let bytes_totales = archivo.metadata().map_err(archivo_invalido)?.len();
+        self.acumular(BufReader::new(archivo), Some(bytes_totales), progreso)
+    }
+
+    /// Importa un CSV de cualquier lector; `progreso` se llama después de cada bloque.
+    pub fn importar<R, F>(&self, lector: R, progreso: F) -> Result<Importacion, ErrorImportacion>
+    where
+        R: BufRead + Send,
+        F: FnMut(&Progreso),
+    {
+        self.acumular(lector, None, progreso)
+    }
+
+    /// Entrega cada bloque importado, en orden, sin acumularlos; sirve para guardar los
+    /// productos en otro lado a medida que llegan.
+    ///
+    /// Como los bloques se entregan antes de leer el resto del archivo, los ids que faltan
+    /// solo pueden saltar los que traen los bloques ya leídos. Si un bloque posterior trae
+    /// un id que ya se asignó a otro producto o variante, ese producto se omite y se
+    /// reporta `IdDuplicado`.
+    ///
+    /// # Parámetros
+    /// - `lector`: el CSV, con el encabezado en la primera fila.
+    /// - `al_bloque`: recibe los productos y errores del bloque y el avance acumulado.
+    ///
+    /// # Retorno
+    /// El avance final. Retorna `Err` si el CSV no tiene encabezado o columna "Handle", o
+    /// si falla la lectura; los bloques anteriores al error ya se entregaron y ninguno
+    /// posterior se entrega.
+    pub fn importar_por_bloques<R, F>(
+        &self,
+        lector: R,
+        al_bloque: F,
+    ) -> Result<Progreso, ErrorImportacion>
+    where
+        R: BufRead + Send,
+        F: FnMut(Importacion, &Progreso),
+    {
+        self.ejecutar(lector, None, true, al_bloque)
+    }
+
+    fn acumular<R, F>(
+        &self,
+        lector: R,
+        bytes_totales: Option<u64>,
+        mut progreso: F,
+    ) -> Result<Importacion, ErrorImportacion>
+    where
+        R: BufRead + Send,
+        F: FnMut(&Progreso),
+    {
+        let mut importacion = Importacion::default();
+        // Con todo el archivo en memoria los ids se asignan al final, como en `importar_csv`.
+        self.ejecutar(lector, bytes_totales, false, |bloque, avance| {
+            importacion.productos.extend(bloque.productos);
+            importacion.errores.extend(bloque.errores);
+            progreso(avance);
+        })?;
+        asignar_ids_faltantes(
+            &mut importacion.productos,
+            &mut Ids::default(),
+            &mut Ids::default(),
+        );
+        Ok(importacion)
+    }
+
+    fn ejecutar<R, F>(
+        &self,
+        lector: R,
+        bytes_totales: Option<u64>,
+        asignar_ids: bool,
+        mut al_bloque: F,
+    ) -> Result<Progreso, ErrorImportacion>
+    where
+        R: BufRead + Send,
+        F: FnMut(Importacion, &Progreso),
+    {
+        // Con la ventana del tamaño del canal, el lector nunca se bloquea al enviar: solo
+        // en `Turno::esperar`, de donde se le puede despertar si hay que cancelar.
+        let ventana = 2 * self.hilos;
+        let turno = Turno {
+            estado: Mutex::new(EstadoTurno::default()),
+            cambio: Condvar::new(),
+            ventana,
+        };
+        let (enviar_bloque, recibir_bloque) = mpsc::sync_channel::<Bloque>(ventana);
+        let (enviar_mensaje, recibir_mensaje) = mpsc::sync_channel::<Mensaje>(self.hilos);
+        let recibir_bloque = Mutex::new(recibir_bloque);
+        let mut union = Union {
+            progreso: Progreso {
+                bytes_totales,
+                ..Progreso::default()
+            },
+            handles: HashSet::new(),
+            asignar_ids,
+            ids_productos: Ids::default(),
+            ids_variantes: Ids::default(),
+        };
+
+        thread::scope(|scope| {
+            let mensajes = enviar_mensaje.clone();
+            let filas_por_bloque = self.filas_por_bloque;
+            let turno = &turno;
+            scope.spawn(move || {
+                if let Err(error) = dividir(lector, filas_por_bloque, &enviar_bloque, turno) {
+                    let _ = mensajes.send(Mensaje::ErrorLectura(error));
+                }
+            });
+            for _ in 0..self.hilos {
+                let mensajes = enviar_mensaje.clone();
+                let recibir_bloque = &recibir_bloque;
+                scope.spawn(move || {
+                    loop {
+                        // El candado se suelta antes de procesar el bloque.
+                        let siguiente = recibir_bloque.lock().unwrap().recv();
+                        let Ok(bloque) = siguiente else { break };
+                        let resultado = importar_bloque_csv(&bloque.texto);
+                        if mensajes
+                            .send(Mensaje::Procesado { bloque, resultado })
+                            .is_err()
+                        {
+                            break;
+                        }
+                    }
+                });
+            }
+            drop(enviar_mensaje);
+
+            // Los bloques pueden terminar en desorden; se guardan hasta que toque su turno.
+            let mut pendientes = BTreeMap::new();
+            let mut siguiente = 0;
+            let mut error = None;
+            let mut error_lectura = None;
+            for mensaje in recibir_mensaje {
+                match mensaje {
+                    Mensaje::Procesado { bloque, resultado } => {
+                        pendientes.insert(bloque.indice, (bloque, resultado));
+                    }
+                    // Los bloques ya enviados son anteriores al error y se siguen entregando.
+                    Mensaje::ErrorLectura(e) => error_lectura = Some(e),
+                }
+                while let Some((bloque, resultado)) = pendientes.remove(&siguiente) {
+                    siguiente += 1;
+                    match resultado {
+                        Ok(importacion) => {
+                            let importacion = union.unir(&bloque, importacion);
+                            al_bloque(importacion, &union.progreso);
+                            turno.avanzar();
+                        }
+                        Err(e) => {
+                            error = Some(e);
+                            break;
+                        }
+                    }
+                }
+                if error.is_some() {
+                    // Al soltar `recibir_mensaje` los hilos de trabajo también terminan.
+                    turno.cancelar();
+                    break;
+                }
+            }
+            match error.or(error_lectura) {
+                Some(error) => Err(error),
+                None => Ok(union.progreso),
+            }
+        })
+    }
+}
+
+/// Cuántos bloques se han entregado, para que el lector no se adelante más de la ventana.
+#[derive(Default)]
+struct EstadoTurno {
+    entregados: usize,
+    cancelado: bool,
+}
+
+struct Turno {
+    estado: Mutex<EstadoTurno>,
+    cambio: Condvar,
+    ventana: usize,
+}
+
+impl Turno {
+    /// Espera a que el bloque `indice` quepa en la ventana; retorna `false` si se canceló.
+    fn esperar(&self, indice: usize) -> bool {
+        let mut estado = self.estado.lock().unwrap();
+        while !estado.cancelado && indice >= estado.entregados + self.ventana {
+            estado = self.cambio.wait(estado).unwrap();
+        }
+        !estado.cancelado
+    }
+
+    fn avanzar(&self) {
+        self.estado.lock().unwrap().entregados += 1;
+        self.cambio.notify_all();
+    }
+
+    fn cancelar(&self) {
+        self.estado.lock().unwrap().cancelado = true;
+        self.cambio.notify_all();
+    }
+}
+
+/// Estado que se arrastra de un bloque al siguiente al unirlos.
+struct Union {
+    progreso: Progreso,
+    handles: HashSet<String>,
+    /// Si es falso, los ids que faltan se dejan en 0.
+    asignar_ids: bool,
+    ids_productos: Ids,
+    ids_variantes: Ids,
+}
+
+impl Union {
+    /// Corrige las líneas de los errores y, si corresponde, asigna los ids que quedaron en 0.
+    fn unir(&mut self, bloque: &Bloque, resultado: Importacion) -> Importacion {
+        let mut importacion = Importacion::default();
+        for mut error in resultado.errores {
+            if let ErrorImportacion::Fila { linea, .. } = &mut error {
+                // En el texto del bloque el primer registro va justo después del encabezado.
+                *linea = *linea - bloque.lineas_encabezado - 1 + bloque.primera_linea;
+            }
+            importacion.errores.push(error);
+        }
+        for producto in resultado.productos {
+            if !self.handles.insert(producto.handle.clone()) {
+                importacion
+                    .errores
+                    .push(ErrorImportacion::HandleDuplicado(producto.handle));
+                continue;
+            }
+            if self.asignar_ids
+                && let Some(id) = self.id_ya_generado(&producto)
+            {
+                importacion.errores.push(ErrorImportacion::IdDuplicado(id));
+                continue;
+            }
+            importacion.productos.push(producto);
+        }
+        if self.asignar_ids {
+            asignar_ids_faltantes(
+                &mut importacion.productos,
+                &mut self.ids_productos,
+                &mut self.ids_variantes,
+            );
+        }
+
+        self.progreso.bloques += 1;
+        self.progreso.filas += bloque.filas;
+        self.progreso.bytes += bloque.bytes;
+        self.progreso.productos += importacion.productos.len();
+        self.progreso.errores += importacion.errores.len();
+        importacion
+    }
+
+    /// Un id del producto o de sus variantes que ya se le asignó a otro de un bloque anterior.
+    fn id_ya_generado(&self, producto: &Product) -> Option<u32> {
+        if producto.id != 0 && self.ids_productos.generado(producto.id) {
+            return Some(producto.id);
+        }
+        producto
+            .variantes
+            .iter()
+            .map(|v| v.id)
+            .find(|&id| id != 0 && self.ids_variantes.generado(id))
+    }
+}
+
+/// En qué parte de un registro va la lectura, con las mismas reglas que `LectorCsv`.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Estado {
+    InicioCampo,
+    SinComillas,
+    EntreComillas,
+    /// Justo después de una comilla dentro de un campo entrecomillado: puede ser `""` o
+    /// el cierre.
+    TrasComilla,
+    /// Texto después de cerrar las comillas; `LectorCsv` descarta hasta el salto de línea.
+    Invalido,
+}
+
+/// Lee un registro completo (un campo entrecomillado puede abarcar varias líneas).
+///
+/// Como en `LectorCsv`, una comilla solo abre un campo al principio de este; en
+/// `Funda 15" laptop` es un carácter más.
+///
+/// # Retorno
+/// El texto del registro con sus saltos de línea y cuántas líneas ocupa, o `None` al
+/// final del archivo.
+fn leer_registro<R: BufRead>(lector: &mut R) -> std::io::Result<Option<(String, usize)>> {
+    let mut registro = String::new();
+    let mut lineas = 0;
+    let mut estado = Estado::InicioCampo;
+    loop {
+        let inicio = registro.len();
+        if lector.read_line(&mut registro)? == 0 {
+            break;
+        }
+        lineas += 1;
+        for b in registro[inicio..].bytes() {
+            estado = match (estado, b) {
+                (Estado::EntreComillas, b'"') => Estado::TrasComilla,
+                (Estado::EntreComillas, _) => Estado::EntreComillas,
+                (Estado::TrasComilla, b'"') => Estado::EntreComillas,
+                (Estado::Invalido, b'\n') => Estado::InicioCampo,
+                (Estado::Invalido, _) => Estado::Invalido,
+                (_, b',' | b'\n' | b'\r') => Estado::InicioCampo,
+                (Estado::InicioCampo, b'"') => Estado::EntreComillas,
+                (Estado::TrasComilla, _) => Estado::Invalido,
+                (_, _) => Estado::SinComillas,
+            };
+        }
+        if estado != Estado::EntreComillas {
+            break;
+        }
+    }
+    Ok((lineas > 0).then_some((registro, lineas)))
+}
+
+/// Corta el CSV en bloques y los manda a los hilos de trabajo.
+fn dividir<R: BufRead>(
+    mut lector: R,
+    filas_por_bloque: usize,
+    enviar: &SyncSender<Bloque>,
+    turno: &Turno,
+) -> Result<(), ErrorImportacion> {
+    let mut linea = 1;
+    let mut bytes = 0;
+    let error_lectura = |linea: usize, e: std::io::Error| {
+        ErrorImportacion::Archivo(format!("linea {}: {}", linea, e))
+    };
+
+    // Encabezado, omitiendo las líneas vacías del principio.
+    let (encabezado, lineas_encabezado, indice_handle) = loop {
+        let Some((texto, lineas)) =
+            leer_registro(&mut lector).map_err(|e| error_lectura(linea, e))?
+        else {
+            return Err(ErrorImportacion::Archivo(
+                "el archivo esta vacio".to_string(),
+            ));
+        };
+        linea += lineas;
+        bytes += texto.len() as u64;
+        let mut texto = texto.strip_prefix('\u{feff}').unwrap_or(&texto).to_string();
+        let Some(registro) = LectorCsv::new(&texto).next() else {
+            continue;
+        };
+        let columnas = columnas_csv(&registro?)
+            .ok_or_else(|| ErrorImportacion::Archivo("falta la columna \"Handle\"".to_string()))?;
+        if !texto.ends_with('\n') {
+            texto.push('\n');
+        }
+        break (texto, lineas, columnas["handle"]);
+    };
+
+    let nuevo_bloque = |indice: usize, primera_linea: usize| Bloque {
+        indice,
+        primera_linea,
+        lineas_encabezado,
+        filas: 0,
+        bytes: 0,
+        texto: encabezado.clone(),
+    };
+    let mut bloque = nuevo_bloque(0, linea);
+    bloque.bytes = bytes;
+    let mut ultimo_handle: Option<String> = None;
+
+    while let Some((registro, lineas)) =
+        leer_registro(&mut lector).map_err(|e| error_lectura(linea, e))?
+    {
+        // Las líneas vacías se quedan en el bloque para no desfasar los números de línea.
+        let handle = match LectorCsv::new(&registro).next() {
+            None => None,
+            Some(Ok(campos)) => Some(
+                campos
+                    .campos
+                    .get(indice_handle)
+                    .map_or(String::new(), |handle| handle.trim().to_string()),
+            ),
+            Some(Err(_)) => Some(String::new()),
+        };
+        if let Some(handle) = handle {
+            if bloque.filas >= filas_por_bloque && ultimo_handle.as_ref() != Some(&handle) {
+                let siguiente = nuevo_bloque(bloque.indice + 1, linea);
+                let listo = std::mem::replace(&mut bloque, siguiente);
+                if !turno.esperar(listo.indice) || enviar.send(listo).is_err() {
+                    return Ok(());
+                }
+            }
+            bloque.filas += 1;
+            ultimo_handle = Some(handle);
+        }
+        bloque.bytes += registro.len() as u64;
+        bloque.texto.push_str(&registro);
+        linea += lineas;
+    }
+    if (bloque.filas > 0 || bloque.indice == 0) && turno.esperar(bloque.indice) {
+        let _ = enviar.send(bloque);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::importacion::importar_csv;
+    use std::io::{self, Read};
+
+    struct ErrorDeLectura;
+
+    impl Read for ErrorDeLectura {
+        fn read(&mut self, _: &mut [u8]) -> io::Result<usize> {
+            Err(io::Error::other("disco desconectado"))
+        }
+    }
+
+    /// CSV con productos de varias filas, un cuerpo de varias líneas, líneas vacías y
+    /// filas inválidas repartidas.
+    fn csv_grande() -> String {
+        let mut texto = String::from(
+            "\u{feff}Handle,Title,Body (HTML),Option1 Name,Option1 Value,Variant Price\n",
+        );
+        for i in 0..40 {
+            match i % 7 {
+                0 => texto.push_str(&format!(
+                    "p{0},Producto {0},\"<p>Linea 1\nLinea 2</p>\",Talla,S,10.00\n\
+                     p{0},,,,M,x\np{0},,,,L,12.00\n",
+                    i
+                )),
+                3 => texto.push_str(&format!("p{0},,,,,1.00\n\n", i)),
+                _ => texto.push_str(&format!(
+                    "p{0},Producto {0},,Talla,S,{0}.50\np{0},,,,M,{0}.75\n",
+                    i
+                )),
+            }
+        }
+        texto
+    }
+
+    #[test]
+    fn test_igual_que_importar_csv() {
+        let texto = csv_grande();
+        let esperado = importar_csv(&texto).unwrap();
+        assert!(!esperado.errores.is_empty());
+        for (hilos, filas) in [(1, 1), (3, 2), (4, 7), (2, 1000)] {
+            let importacion = ImportadorMasivo::new()
+                .con_hilos(hilos)
+                .con_filas_por_bloque(filas)
+                .importar(texto.as_bytes(), |_| {})
+                .unwrap();
+            assert_eq!(importacion, esperado, "hilos {}, filas {}", hilos, filas);
+        }
+    }
+
+    #[test]
+    fn test_comillas_dentro_de_un_campo() {
+        // La comilla de `15"` no abre un campo; si se contara, el cuerpo de varias líneas
+        // de "b" quedaría partido entre bloques.
+        let texto = "Handle,Title,Body (HTML),Variant Price\n\
+                     a,Funda 15\" laptop,,10.00\n\
+                     b,B,\"<p>uno\ndos</p>\",2.00\n\
+                     c,C,,3.00\n\
+                     d,D,\"x\"y,4.00\n\
+                     e,E,\"<p>tres\ncuatro</p>\",5.00\n";
+        let esperado = importar_csv(texto).unwrap();
+        assert_eq!(esperado.productos.len(), 4);
+        let mut filas = 0;
+        let importacion = ImportadorMasivo::new()
+            .con_filas_por_bloque(1)
+            .importar(texto.as_bytes(), |avance| filas = avance.filas)
+            .unwrap();
+        assert_eq!(importacion, esperado);
+        assert_eq!(filas, 5);
+    }
+
+    #[test]
+    fn test_progreso_y_bloques() {
+        let texto = csv_grande();
+        let mut avances = Vec::new();
+        let importacion = ImportadorMasivo::new()
+            .con_hilos(2)
+            .con_filas_por_bloque(10)
+            .importar(texto.as_bytes(), |avance| avances.push(*avance))
+            .unwrap();
+        let ultimo = avances.last().unwrap();
+        assert!(avances.len() > 1);
+        assert!(avances.windows(2).all(|par| par[0].filas < par[1].filas));
+        assert_eq!(ultimo.bytes, texto.len() as u64);
+        assert_eq!(ultimo.productos, importacion.productos.len());
+        assert_eq!(ultimo.errores, importacion.errores.len());
+
+        let mut recibidos = 0;
+        let final_ = ImportadorMasivo::new()
+            .con_filas_por_bloque(10)
+            .importar_por_bloques(texto.as_bytes(), |bloque, _| {
+                recibidos += bloque.productos.len()
+            })
+            .unwrap();
+        assert_eq!(recibidos, final_.productos);
+        assert_eq!(final_, *ultimo);
+    }
+
+    #[test]
+    fn test_ids_mezclados() {
+        let texto = "Handle,ID,Title,Variant ID,Variant Price\n\
+                     a,,A,,1.00\n\
+                     b,1,B,,2.00\n\
+                     c,,C,2,3.00\n";
+        let esperado = importar_csv(texto).unwrap();
+        let importacion = ImportadorMasivo::new()
+            .con_filas_por_bloque(1)
+            .importar(texto.as_bytes(), |_| {})
+            .unwrap();
+        assert_eq!(importacion, esperado);
+        let ids: Vec<u32> = importacion.productos.iter().map(|p| p.id).collect();
+        assert_eq!(ids, vec![2, 1, 3]);
+
+        // Por bloques el id 1 ya se le asignó a "a" cuando llega "b", que se omite.
+        let mut ids = Vec::new();
+        let mut errores = Vec::new();
+        ImportadorMasivo::new()
+            .con_filas_por_bloque(1)
+            .importar_por_bloques(texto.as_bytes(), |bloque, _| {
+                ids.extend(bloque.productos.iter().map(|p| (p.id, p.variantes[0].id)));
+                errores.extend(bloque.errores);
+            })
+            .unwrap();
+        assert_eq!(ids, vec![(1, 1), (2, 2)]);
+        assert_eq!(errores, vec![ErrorImportacion::IdDuplicado(1)]);
+    }
+
+    #[test]
+    fn test_handles_separados_y_errores() {
+        let texto = "Handle,Title,Variant Price\na,A,1\nb,B,2\na,A otra vez,3\n";
+        let importacion = ImportadorMasivo::new()
+            .con_filas_por_bloque(1)
+            .importar(texto.as_bytes(), |_| {})
+            .unwrap();
+        assert_eq!(importacion.productos.len(), 2);
+        assert_eq!(
+            importacion.errores,
+            vec![ErrorImportacion::HandleDuplicado("a".to_string())]
+        );
+
+        // Un error de lectura entrega los bloques anteriores y ninguno después.
+        let lector = texto
+            .as_bytes()
+            .chain(ErrorDeLectura)
+            .chain("c,C,4\n".as_bytes());
+        let mut handles = Vec::new();
+        let resultado = ImportadorMasivo::new()
+            .con_filas_por_bloque(1)
+            .importar_por_bloques(BufReader::new(lector), |bloque, _| {
+                handles.extend(bloque.productos.into_iter().map(|p| p.handle))
+            });
+        assert!(matches!(resultado, Err(ErrorImportacion::Archivo(_))));
+        assert_eq!(handles, vec!["a", "b"]);
+
+        let masivo = ImportadorMasivo::new();
+        assert!(masivo.importar("".as_bytes(), |_| {}).is_err());
+        assert!(masivo.importar("Title\nA\n".as_bytes(), |_| {}).is_err());
+    }
+}
@@ -0,0 +1,393 @@
+// src/matriz.rs
+
+use std::fmt;
+
+use crate::handle::slugify;
+use crate::producto::{MAX_OPCIONES, Product, Variante};
+
+/// Errores al generar variantes con `MatrizVariantes`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ErrorMatriz {
+    /// Las opciones o las exclusiones no son válidas (ver `MatrizVariantes::validar`).
+    OpcionInvalida(String),
+    /// Las exclusiones no dejan ninguna combinación.
+    TodasExcluidas,
+}
+
+impl fmt::Display for ErrorMatriz {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ErrorMatriz::OpcionInvalida(mensaje) => write!(f, "opcion invalida: {}", mensaje),
+            ErrorMatriz::TodasExcluidas => write!(f, "todas las combinaciones estan excluidas"),
+        }
+    }
+}
+
+impl std::error::Error for ErrorMatriz {}
+
+/// Una opción del producto con sus valores posibles: "Talla" -> ["S", "M", "L"].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DefinicionOpcion {
+    pub nombre: String,
+    pub valores: Vec<String>,
+}
+
+/// Genera todas las variantes de un producto a partir de sus opciones (talla × color...).
+///
+/// ```text
+/// MatrizVariantes::new()
+///     .con_opcion("Talla", &["S", "M", "L"])
+///     .con_opcion("Color", &["Rojo", "Azul"])
+///     .excluir(&[("Talla", "S"), ("Color", "Azul")])
+///     .aplicar(&mut producto)?;   // 5 variantes: S/Rojo, M/Rojo, M/Azul, L/Rojo, L/Azul
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct MatrizVariantes {
+    opciones: Vec<DefinicionOpcion>,
+    /// Cada exclusión es una lista de pares (opción, valor) que deben cumplirse todos.
+    excluidas: Vec<Vec<(String, String)>>,
+    precio_centavos: Option<i64>,
+    primer_id: u32,
+}
+
+impl MatrizVariantes {
+    pub fn new() -> MatrizVariantes {
+        MatrizVariantes::default()
+    }
+
+    pub fn con_opcion(mut self, nombre: &str, valores: &[&str]) -> MatrizVariantes {
+        self.opciones.push(DefinicionOpcion {
+            nombre: nombre.to_string(),
+            valores: valores.iter().map(|v| v.to_string()).collect(),
+        });
+        self
+    }
+
+    /// Excluye las combinaciones que tienen todos los pares (opción, valor) indicados;
+    /// `&[("Color", "Oro")]` quita todas las variantes doradas.
+    pub fn excluir(mut self, condiciones: &[(&str, &str)]) -> MatrizVariantes {
+        self.excluidas.push(
+            condiciones
+                .iter()
+                .map(|(opcion, valor)| (opcion.to_string(), valor.to_string()))
+                .collect(),
+        );
+        self
+    }
+
+    /// Precio de las variantes nuevas; sin él se usa el precio más bajo del producto.
+    pub fn con_precio(mut self, centavos: i64) -> MatrizVariantes {
+        self.precio_centavos = Some(centavos);
+        self
+    }
+
+    /// Id mínimo para las variantes nuevas, para no repetir ids de otros productos del
+    /// catálogo. Sin él siguen al mayor id de variante del producto.
+    pub fn con_primer_id(mut self, id: u32) -> MatrizVariantes {
+        self.primer_id = id;
+        self
+    }
+
+    pub fn opciones(&self) -> &[DefinicionOpcion] {
+        &self.opciones
+    }
+
+    /// Revisa que haya a lo más `MAX_OPCIONES` opciones, que cada una tenga nombre y
+    /// valores sin repetir, y que las exclusiones nombren opciones y valores existentes.
+    pub fn validar(&self) -> Result<(), ErrorMatriz> {
+        let invalida = |mensaje: String| Err(ErrorMatriz::OpcionInvalida(mensaje));
+        if self.opciones.len() > MAX_OPCIONES {
+            return invalida(format!(
+                "hay {} opciones y el maximo es {}",
+                self.opciones.len(),
+                MAX_OPCIONES
+            ));
+        }
+        for (i, opcion) in self.opciones.iter().enumerate() {
+            if opcion.nombre.trim().is_empty() {
+                return invalida(format!("la opcion {} no tiene nombre", i + 1));
+            }
+            if self.opciones[..i].iter().any(|o| o.nombre == opcion.nombre) {
+                return invalida(format!("\"{}\" esta repetida", opcion.nombre));
+            }
+            if opcion.valores.is_empty() {
+                return invalida(format!("\"{}\" no tiene valores", opcion.nombre));
+            }
+            for (j, valor) in opcion.valores.iter().enumerate() {
+                if valor.trim().is_empty() {
+                    return invalida(format!("\"{}\" tiene un valor vacio", opcion.nombre));
+                }
+                if opcion.valores[..j].contains(valor) {
+                    return invalida(format!(
+                        "\"{}\" repite el valor \"{}\"",
+                        opcion.nombre, valor
+                    ));
+                }
+            }
+        }
+        for (nombre, valor) in self.excluidas.iter().flatten() {
+            match self.opciones.iter().find(|o| &o.nombre == nombre) {
+                None => return invalida(format!("se excluye \"{}\", que no existe", nombre)),
+                Some(opcion) if !opcion.valores.contains(valor) => {
+                    return invalida(format!(
+                        "se excluye \"{}: {}\", que no existe",
+                        nombre, valor
+                    ));
+                }
+                Some(_) => {}
+            }
+        }
+        Ok(())
+    }
+
+    /// Todas las combinaciones de valores sin las excluidas, en el orden de Shopify: la
+    /// primera opción cambia más lento. Sin opciones hay una sola combinación vacía.
+    pub fn combinaciones(&self) -> Vec<Vec<String>> {
+        let mut combinaciones = vec![Vec::new()];
+        for opcion in &self.opciones {
+            combinaciones = combinaciones
+                .into_iter()
+                .flat_map(|prefijo: Vec<String>| {
+                    opcion.valores.iter().map(move |valor| {
+                        let mut combinacion = prefijo.clone();
+                        combinacion.push(valor.clone());
+                        combinacion
+                    })
+                })
+                .collect();
+        }
+        combinaciones.retain(|combinacion| !self.excluida(combinacion));
+        combinaciones
+    }
+
+    fn excluida(&self, combinacion: &[String]) -> bool {
+        self.excluidas.iter().any(|condiciones| {
+            condiciones.iter().all(|(nombre, valor)| {
+                self.opciones
+                    .iter()
+                    .position(|o| &o.nombre == nombre)
+                    .is_some_and(|i| &combinacion[i] == valor)
+            })
+        })
+    }
+
+    /// Reemplaza las opciones y variantes del producto por las de la matriz.
+    ///
+    /// Las variantes existentes conservan su id, SKU, precio e inventario: se emparejan por
+    /// el nombre de la opción, no por su posición, así que reordenar opciones no cambia
+    /// ningún id. Al agregar una opción, las variantes existentes toman su primer valor;
+    /// al quitarla, se conserva la primera variante de cada grupo que queda igual.
+    ///
+    /// # Parámetros
+    /// - `producto`: producto a modificar; su handle da el SKU de las variantes nuevas.
+    ///
+    /// # Retorno
+    /// Las variantes que ya no corresponden a ninguna combinación y se quitaron. Si las
+    /// opciones no son válidas o todas las combinaciones están excluidas, el producto no
+    /// se modifica.
+    pub fn aplicar(&self, producto: &mut Product) -> Result<Vec<Variante>, ErrorMatriz> {
+        self.validar()?;
+        let combinaciones = self.combinaciones();
+        if combinaciones.is_empty() {
+            return Err(ErrorMatriz::TodasExcluidas);
+        }
+
+        let precio = self
+            .precio_centavos
+            .or_else(|| producto.precio_minimo())
+            .unwrap_or(0);
+        let mut siguiente_id = producto
+            .variantes
+            .iter()
+            .map(|v| v.id + 1)
+            .max()
+            .unwrap_or(1)
+            .max(self.primer_id);
+        let mut anteriores: Vec<Option<Variante>> =
+            producto.variantes.drain(..).map(Some).collect();
+
+        for combinacion in combinaciones {
+            let existente = anteriores.iter_mut().find(|variante| {
+                variante
+                    .as_ref()
+                    .is_some_and(|v| self.corresponde(&producto.opciones, v, &combinacion))
+            });
+            let variante = match existente.and_then(Option::take) {
+                Some(mut variante) => {
+                    if variante.sku.is_empty() {
+                        variante.sku = sku_variante(&producto.handle, &combinacion);
+                    }
+                    variante.valores = combinacion;
+                    variante
+                }
+                None => {
+                    siguiente_id += 1;
+                    Variante {
+                        id: siguiente_id - 1,
+                        sku: sku_variante(&producto.handle, &combinacion),
+                        valores: combinacion,
+                        precio_centavos: precio,
+                        ..Variante::default()
+                    }
+                }
+            };
+            producto.variantes.push(variante);
+        }
+        producto.opciones = self.opciones.iter().map(|o| o.nombre.clone()).collect();
+        Ok(anteriores.into_iter().flatten().collect())
+    }
+
+    /// Indica si una variante con las opciones `anteriores` corresponde a la combinación.
+    fn corresponde(
+        &self,
+        anteriores: &[String],
+        variante: &Variante,
+        combinacion: &[String],
+    ) -> bool {
+        self.opciones
+            .iter()
+            .zip(combinacion)
+            .all(|(opcion, valor)| {
+                match anteriores
+                    .iter()
+                    .position(|nombre| nombre == &opcion.nombre)
+                {
+                    Some(i) => variante.valores.get(i) == Some(valor),
+                    // Opción nueva: la variante existente toma el primer valor.
+                    None => opcion.valores.first() == Some(valor),
+                }
+            })
+    }
+}
+
+/// SKU de una variante a partir del handle y sus valores: "simple-tee", ["M", "Rojo"]
+/// -> "SIMPLE-TEE-M-ROJO".
+pub fn sku_variante(handle: &str, valores: &[String]) -> String {
+    let mut partes = vec![slugify(handle)];
+    partes.extend(valores.iter().map(|valor| slugify(valor)));
+    partes.retain(|parte| !parte.is_empty());
+    partes.join("-").to_uppercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn titulos(producto: &Product) -> Vec<(u32, String)> {
+        producto
+            .variantes
+            .iter()
+            .map(|v| (v.id, v.titulo()))
+            .collect()
+    }
+
+    #[test]
+    fn test_combinaciones() {
+        let matriz = MatrizVariantes::new()
+            .con_opcion("Talla", &["S", "M"])
+            .con_opcion("Color", &["Rojo", "Azul"])
+            .con_opcion("Tela", &["Algodón", "Lino"])
+            .excluir(&[("Color", "Azul"), ("Tela", "Lino")])
+            .excluir(&[("Talla", "S"), ("Color", "Rojo"), ("Tela", "Algodón")]);
+        let combinaciones = matriz.combinaciones();
+        assert_eq!(combinaciones.len(), 5);
+        assert_eq!(combinaciones[0], vec!["S", "Rojo", "Lino"]);
+        assert_eq!(combinaciones[4], vec!["M", "Azul", "Algodón"]);
+        assert_eq!(
+            MatrizVariantes::new().combinaciones(),
+            vec![Vec::<String>::new()]
+        );
+        assert_eq!(
+            sku_variante("simple-tee", &combinaciones[0]),
+            "SIMPLE-TEE-S-ROJO-LINO"
+        );
+    }
+
+    #[test]
+    fn test_aplicar() {
+        let mut producto = Product::new(42, "simple-tee")
+            .con_variante(Variante::new(7, 1999).con_sku("TEE").con_inventario(4));
+        let quitadas = MatrizVariantes::new()
+            .con_opcion("Talla", &["S", "M"])
+            .excluir(&[("Talla", "M")])
+            .aplicar(&mut producto)
+            .unwrap();
+        assert!(quitadas.is_empty());
+        assert_eq!(producto.opciones, vec!["Talla"]);
+        assert_eq!(titulos(&producto), vec![(7, "S".to_string())]);
+        assert_eq!(producto.variantes[0].sku, "TEE");
+        assert_eq!(producto.variantes[0].inventario, 4);
+
+        // Agregar una opción y un valor: la variante existente toma "Rojo".
+        MatrizVariantes::new()
+            .con_opcion("Talla", &["S", "M"])
+            .con_opcion("Color", &["Rojo", "Azul"])
+            .aplicar(&mut producto)
+            .unwrap();
+        assert_eq!(producto.variantes.len(), 4);
+        assert_eq!(producto.variante(7).unwrap().titulo(), "S / Rojo");
+        assert_eq!(producto.variante(8).unwrap().sku, "SIMPLE-TEE-S-AZUL");
+        assert_eq!(producto.variante(8).unwrap().precio_centavos, 1999);
+
+        // Reordenar las opciones no cambia ids.
+        let antes: Vec<(u32, Vec<String>)> = producto
+            .variantes
+            .iter()
+            .map(|v| (v.id, vec![v.valores[1].clone(), v.valores[0].clone()]))
+            .collect();
+        MatrizVariantes::new()
+            .con_opcion("Color", &["Rojo", "Azul"])
+            .con_opcion("Talla", &["S", "M"])
+            .aplicar(&mut producto)
+            .unwrap();
+        for (id, valores) in antes {
+            assert_eq!(producto.variante(id).unwrap().valores, valores);
+        }
+
+        // Quitar un valor devuelve las variantes que sobran.
+        let quitadas = MatrizVariantes::new()
+            .con_opcion("Color", &["Rojo"])
+            .con_opcion("Talla", &["S", "M"])
+            .con_primer_id(100)
+            .aplicar(&mut producto)
+            .unwrap();
+        assert_eq!(quitadas.len(), 2);
+        assert!(quitadas.iter().all(|v| v.valores[0] == "Azul"));
+        assert_eq!(producto.variante(7).unwrap().titulo(), "Rojo / S");
+    }
+
+    #[test]
+    fn test_opciones_invalidas() {
+        let mut producto = Product::new(1, "gorra").con_variante(Variante::new(1, 500));
+        let invalidas = [
+            MatrizVariantes::new()
+                .con_opcion("A", &["1"])
+                .con_opcion("B", &["1"])
+                .con_opcion("C", &["1"])
+                .con_opcion("D", &["1"]),
+            MatrizVariantes::new().con_opcion("Talla", &[]),
+            MatrizVariantes::new().con_opcion("Talla", &["S", "S"]),
+            MatrizVariantes::new()
+                .con_opcion("Talla", &["S"])
+                .con_opcion("Talla", &["M"]),
+            MatrizVariantes::new()
+                .con_opcion("Talla", &["S"])
+                .excluir(&[("Color", "Rojo")]),
+            MatrizVariantes::new()
+                .con_opcion("Talla", &["S"])
+                .excluir(&[("Talla", "S")]),
+        ];
+        let (excluida, invalidas) = invalidas.split_last().unwrap();
+        for matriz in invalidas {
+            assert!(matches!(
+                matriz.aplicar(&mut producto),
+                Err(ErrorMatriz::OpcionInvalida(_))
+            ));
+        }
+        assert_eq!(
+            excluida.aplicar(&mut producto),
+            Err(ErrorMatriz::TodasExcluidas)
+        );
+        assert_eq!(titulos(&producto), vec![(1, "Default Title".to_string())]);
+    }
+}
@@ -0,0 +1,238 @@
+// src/producto.rs
+
+/// Máximo de opciones (por ejemplo "Talla", "Color", "Material") que admite un producto,
+/// igual que en Shopify.
+pub const MAX_OPCIONES: usize = 3;
+
+/// Producto del catálogo, con la forma de los productos de una tienda Shopify.
+///
+/// El precio y el inventario viven en las variantes; un producto sin opciones tiene
+/// una sola variante con `valores` vacío.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Product {
+    pub id: u32,
+    pub handle: String,
+    pub titulo: String,
+    pub descripcion: String,
+    pub proveedor: String,
+    pub tipo_producto: String,
+    pub etiquetas: Vec<String>,
+    /// Nombres de las opciones en orden ("Talla", "Color"); a lo más `MAX_OPCIONES`.
+    pub opciones: Vec<String>,
+    pub variantes: Vec<Variante>,
+    pub imagenes: Vec<Imagen>,
+}
+
+/// Una combinación vendible de un producto ("M / Rojo") con su precio e inventario.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Variante {
+    pub id: u32,
+    pub sku: String,
+    /// Valor de cada opción del producto, en el mismo orden que `Product::opciones`.
+    pub valores: Vec<String>,
+    pub precio_centavos: i64,
+    /// Precio "antes" que se muestra tachado; solo tiene sentido si es mayor que el precio.
+    pub precio_comparacion_centavos: Option<i64>,
+    /// Unidades en existencia; puede ser negativo si se vendió sin inventario.
+    pub inventario: i64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Imagen {
+    pub src: String,
+    pub alt: String,
+    /// Posición en la galería, empezando en 1.
+    pub posicion: u32,
+}
+
+impl Product {
+    /// Crea un producto sin variantes ni imágenes; el título queda igual al handle.
+    pub fn new(id: u32, handle: &str) -> Product {
+        Product {
+            id,
+            handle: handle.to_string(),
+            titulo: handle.to_string(),
+            ..Product::default()
+        }
+    }
+
+    pub fn con_titulo(mut self, titulo: &str) -> Product {
+        self.titulo = titulo.to_string();
+        self
+    }
+
+    pub fn con_descripcion(mut self, descripcion: &str) -> Product {
+        self.descripcion = descripcion.to_string();
+        self
+    }
+
+    pub fn con_proveedor(mut self, proveedor: &str) -> Product {
+        self.proveedor = proveedor.to_string();
+        self
+    }
+
+    pub fn con_tipo(mut self, tipo_producto: &str) -> Product {
+        self.tipo_producto = tipo_producto.to_string();
+        self
+    }
+
+    pub fn con_etiquetas(mut self, etiquetas: &[&str]) -> Product {
+        self.etiquetas = etiquetas.iter().map(|e| e.to_string()).collect();
+        self
+    }
+
+    pub fn con_opciones(mut self, opciones: &[&str]) -> Product {
+        self.opciones = opciones.iter().map(|o| o.to_string()).collect();
+        self
+    }
+
+    pub fn con_variante(mut self, variante: Variante) -> Product {
+        self.variantes.push(variante);
+        self
+    }
+
+    pub fn con_imagen(mut self, src: &str, alt: &str) -> Product {
+        let posicion = self.imagenes.len() as u32 + 1;
+        self.imagenes.push(Imagen {
+            src: src.to_string(),
+            alt: alt.to_string(),
+            posicion,
+        });
+        self
+    }
+
+    /// Indica si el producto tiene la etiqueta, sin distinguir mayúsculas.
+    pub fn tiene_etiqueta(&self, etiqueta: &str) -> bool {
+        self.etiquetas
+            .iter()
+            .any(|e| e.eq_ignore_ascii_case(etiqueta))
+    }
+
+    /// Precio de la variante más barata, o `None` si no hay variantes.
+    pub fn precio_minimo(&self) -> Option<i64> {
+        self.variantes.iter().map(|v| v.precio_centavos).min()
+    }
+
+    /// Precio de la variante más cara, o `None` si no hay variantes.
+    pub fn precio_maximo(&self) -> Option<i64> {
+        self.variantes.iter().map(|v| v.precio_centavos).max()
+    }
+
+    /// Unidades en existencia sumando todas las variantes (las negativas cuentan como cero).
+    pub fn inventario_total(&self) -> i64 {
+        self.variantes.iter().map(|v| v.inventario.max(0)).sum()
+    }
+
+    /// Indica si alguna variante tiene existencias.
+    pub fn disponible(&self) -> bool {
+        self.variantes.iter().any(|v| v.inventario > 0)
+    }
+
+    pub fn variante(&self, id: u32) -> Option<&Variante> {
+        self.variantes.iter().find(|v| v.id == id)
+    }
+
+    /// Retorna el valor que tiene la variante para la opción `nombre` ("Talla" -> "M").
+    pub fn valor_opcion<'a>(&self, variante: &'a Variante, nombre: &str) -> Option<&'a str> {
+        let indice = self.opciones.iter().position(|o| o == nombre)?;
+        variante.valores.get(indice).map(String::as_str)
+    }
+}
+
+impl Variante {
+    pub fn new(id: u32, precio_centavos: i64) -> Variante {
+        Variante {
+            id,
+            precio_centavos,
+            ..Variante::default()
+        }
+    }
+
+    pub fn con_sku(mut self, sku: &str) -> Variante {
+        self.sku = sku.to_string();
+        self
+    }
+
+    pub fn con_valores(mut self, valores: &[&str]) -> Variante {
+        self.valores = valores.iter().map(|v| v.to_string()).collect();
+        self
+    }
+
+    pub fn con_precio_comparacion(mut self, centavos: i64) -> Variante {
+        self.precio_comparacion_centavos = Some(centavos);
+        self
+    }
+
+    pub fn con_inventario(mut self, inventario: i64) -> Variante {
+        self.inventario = inventario;
+        self
+    }
+
+    /// Título de la variante como lo muestra Shopify: "M / Rojo", o "Default Title" sin opciones.
+    pub fn titulo(&self) -> String {
+        if self.valores.is_empty() {
+            "Default Title".to_string()
+        } else {
+            self.valores.join(" / ")
+        }
+    }
+
+    /// Indica si la variante tiene un precio de comparación mayor que su precio.
+    pub fn en_oferta(&self) -> bool {
+        self.precio_comparacion_centavos
+            .is_some_and(|antes| antes > self.precio_centavos)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn camiseta() -> Product {
+        Product::new(42, "simple-tee")
+            .con_titulo("Simple Tee")
+            .con_etiquetas(&["verano", "Algodon"])
+            .con_opciones(&["Talla", "Color"])
+            .con_variante(
+                Variante::new(1, 1999)
+                    .con_valores(&["M", "Rojo"])
+                    .con_inventario(3),
+            )
+            .con_variante(
+                Variante::new(2, 2499)
+                    .con_valores(&["L", "Rojo"])
+                    .con_precio_comparacion(2999)
+                    .con_inventario(-2),
+            )
+    }
+
+    #[test]
+    fn test_precios_e_inventario() {
+        let producto = camiseta();
+        assert_eq!(producto.precio_minimo(), Some(1999));
+        assert_eq!(producto.precio_maximo(), Some(2499));
+        assert_eq!(producto.inventario_total(), 3);
+        assert!(producto.disponible());
+        assert!(producto.variante(2).unwrap().en_oferta());
+        assert_eq!(Product::new(1, "vacio").precio_minimo(), None);
+    }
+
+    #[test]
+    fn test_opciones_y_etiquetas() {
+        let producto = camiseta();
+        let variante = producto.variante(2).unwrap();
+        assert_eq!(variante.titulo(), "L / Rojo");
+        assert_eq!(producto.valor_opcion(variante, "Color"), Some("Rojo"));
+        assert_eq!(producto.valor_opcion(variante, "Material"), None);
+        assert!(producto.tiene_etiqueta("algodon"));
+        assert_eq!(Variante::new(3, 100).titulo(), "Default Title");
+    }
+
+    #[test]
+    fn test_imagenes_numeradas() {
+        let producto = Product::new(1, "tee")
+            .con_imagen("a.jpg", "Frente")
+            .con_imagen("b.jpg", "");
+        assert_eq!(producto.imagenes[1].posicion, 2);
+    }
+}
@@ -1,11 +1,38 @@
 // src/utils.rs
 
-// Esta función extrae los handles de los productos.
-pub fn get_product_handles(products: &[Product]) -> Vec<String> {
-    products.iter().map(|p| p.handle.clone()).collect()
+use crate::Product; // Importamos la estructura Product
+
+// Estas funciones aceptan cualquier colección que se recorra como `&Product`:
+// un slice (`&[Product]`), un `&Vec<Product>` o un `&Catalogo`.
+
+// Esta función extrae los handles de los productos; los presta en vez de copiarlos.
+pub fn get_product_handles<'a>(products: impl IntoIterator<Item = &'a Product>) -> Vec<&'a str> {
+    products.into_iter().map(|p| p.handle.as_str()).collect()
 }
 
 // Esta función extrae los ids de los productos.
-pub fn get_product_ids(products: &[Product]) -> Vec<u32> {
-    products.iter().map(|p| p.id).collect()
+pub fn get_product_ids<'a>(products: impl IntoIterator<Item = &'a Product>) -> Vec<u32> {
+    products.into_iter().map(|p| p.id).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Catalogo;
+
+    #[test]
+    fn test_handles_e_ids() {
+        let products = vec![
+            Product::new(42, "simple-tee"),
+            Product::new(256, "casual-tee"),
+        ];
+        assert_eq!(
+            get_product_handles(&products),
+            vec!["simple-tee", "casual-tee"]
+        );
+        assert_eq!(get_product_ids(&products), vec![42, 256]);
+
+        let catalogo = Catalogo::desde_productos(products).unwrap();
+        assert_eq!(get_product_ids(&catalogo), vec![42, 256]);
+    }
 }
@@ -0,0 +1,69 @@
+// benches/perezoso.rs
+
+// Compara la cadena mi_mapa -> mi_filter -> mi_reduce ansiosa (un vector nuevo por paso)
+// con la perezosa (un solo recorrido) y con los iteradores de la biblioteca estándar.
+//
+//   cargo bench
+//
+// No usa dependencias externas: cada caso se repite varias veces y se reporta el tiempo
+// promedio por recorrido.
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use vec::perezoso::{self, IteradorPerezoso};
+use vec::{mi_filter, mi_mapa, mi_reduce};
+
+const ELEMENTOS: i64 = 1_000_000;
+const REPETICIONES: u32 = 50;
+
+/// Ejecuta `caso` unas veces para calentar y luego mide el promedio de `REPETICIONES`.
+fn medir(nombre: &str, mut caso: impl FnMut() -> i64) -> Duration {
+    let esperado = caso();
+    for _ in 0..3 {
+        black_box(caso());
+    }
+    let inicio = Instant::now();
+    for _ in 0..REPETICIONES {
+        assert_eq!(black_box(caso()), esperado);
+    }
+    let promedio = inicio.elapsed() / REPETICIONES;
+    println!(
+        "{:<40} {:>10.3} ms",
+        nombre,
+        promedio.as_secs_f64() * 1000.0
+    );
+    promedio
+}
+
+fn main() {
+    let datos: Vec<i64> = (0..ELEMENTOS).collect();
+    let datos = black_box(&datos);
+    println!(
+        "mi_mapa(x * 3) -> mi_filter(par) -> mi_reduce(suma), {} elementos",
+        ELEMENTOS
+    );
+
+    let ansiosa = medir("ansiosa (vectores intermedios)", || {
+        let mapeados = mi_mapa(datos, |x| x * 3);
+        let filtrados = mi_filter(&mapeados, |x| x % 2 == 0);
+        mi_reduce(&filtrados, |acc, x| acc + x, 0)
+    });
+    let perezosa = medir("perezosa (un solo recorrido)", || {
+        perezoso::mi_mapa(datos, |x| x * 3)
+            .mi_filter(|x| x % 2 == 0)
+            .mi_reduce(|acc, x| acc + x, 0)
+    });
+    medir("iteradores de std", || {
+        datos
+            .iter()
+            .map(|x| x * 3)
+            .filter(|x| x % 2 == 0)
+            .sum::<i64>()
+    });
+
+    println!(
+        "la perezosa tarda {:.2}x lo que la ansiosa",
+        perezosa.as_secs_f64() / ansiosa.as_secs_f64()
+    );
+}
@@ -0,0 +1,13 @@
+// src/lib.rs
+
+// Las funciones de orden superior viven en la librería para que `main.rs` y los
+// benchmarks (`benches/perezoso.rs`) usen las mismas:
+//
+// - `orden_superior`: versiones ansiosas (mi_mapa, mi_filter, mi_reduce), que crean
+//   un vector nuevo en cada paso.
+// - `perezoso`: adaptadores perezosos encadenables que no crean vectores intermedios.
+
+pub mod orden_superior;
+pub mod perezoso;
+
+pub use orden_superior::{mi_filter, mi_mapa, mi_reduce};
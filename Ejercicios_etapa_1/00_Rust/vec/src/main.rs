@@ -6,6 +6,12 @@
 // como acceder a un elemento, manipular un array, sumar elementos, generar un rango, organizar por tipo
 // (usando un enum para simular arrays heterogéneos), y versiones propias (mi_mapa, mi_filter, mi_reduce,
 // usa_map, usa_filter, usa_reduce) que imitan los métodos .map, .filter y .reduce.
+//
+// Las funciones mi_mapa, mi_filter y mi_reduce viven en la librería (lib.rs), junto con sus
+// versiones perezosas (`vec::perezoso`). Para comparar ambas: `cargo bench`.
+
+use vec::perezoso::{self, IteradorPerezoso};
+use vec::{mi_filter, mi_mapa, mi_reduce};
 
 /// Devuelve el elemento en la posición indicada de un array (Vec).
 /// Si el índice es inválido, retorna None.
@@ -55,42 +61,6 @@ fn organizar_por_tipo(arr: &Vec<Valor>) -> Organizado {
     unimplemented!()
 }
 
-/// mi_mapa
-///
-/// Esta función recibe un vector y una función; aplica la función a cada elemento del vector y
-/// retorna un nuevo vector con los resultados.
-fn mi_mapa<T, U, F>(arreglo: &Vec<T>, mut fnc: F) -> Vec<U>
-where
-    F: FnMut(&T) -> U,
-{
-    // TODO: Implementar iterando con un bucle for y retornando el nuevo vector.
-    unimplemented!()
-}
-
-/// mi_filter
-///
-/// Esta función recibe un vector y una función predicado; retorna un nuevo vector que contiene
-/// solo aquellos elementos para los cuales la función predicado retorna true.
-fn mi_filter<T: Clone, F>(arreglo: &Vec<T>, mut predicado: F) -> Vec<T>
-where
-    F: FnMut(&T) -> bool,
-{
-    // TODO: Implementar iterando con un bucle for y retornando el vector filtrado.
-    unimplemented!()
-}
-
-/// mi_reduce
-///
-/// Esta función recibe un vector, una función reductora y un valor inicial. Aplica la función reductora
-/// acumulando un resultado a lo largo de los elementos del vector y retorna el resultado final.
-fn mi_reduce<T, U, F>(arreglo: &Vec<T>, mut fnc: F, valor_inicial: U) -> U
-where
-    F: FnMut(U, &T) -> U,
-{
-    // TODO: Implementar usando un bucle for para acumular el resultado.
-    unimplemented!()
-}
-
 /// usa_map
 ///
 /// Esta función utiliza el método de iteradores para simular el método .map de JavaScript.
@@ -158,15 +128,15 @@ fn main() {
     println!("organizar_por_tipo: {:?}", organizado);
     
     // Prueba de mi_mapa:
-    let resultado_mapa = mi_mapa(&vec![1, 2, 3, 4], |x| x * 2);
+    let resultado_mapa = mi_mapa(&[1, 2, 3, 4], |x| x * 2);
     println!("mi_mapa: {:?}", resultado_mapa); // Espera [2, 4, 6, 8]
     
     // Prueba de mi_filter:
-    let resultado_filter = mi_filter(&vec![1, 2, 3, 4, 5, 6], |x| x % 2 == 0);
+    let resultado_filter = mi_filter(&[1, 2, 3, 4, 5, 6], |x| x % 2 == 0);
     println!("mi_filter: {:?}", resultado_filter); // Espera [2, 4, 6]
     
     // Prueba de mi_reduce:
-    let resultado_reduce = mi_reduce(&vec![1, 2, 3, 4], |acc, x| acc + x, 0);
+    let resultado_reduce = mi_reduce(&[1, 2, 3, 4], |acc, x| acc + x, 0);
     println!("mi_reduce: {}", resultado_reduce); // Espera 10
     
     // Prueba de usa_map:
@@ -180,4 +150,10 @@ fn main() {
     // Prueba de usa_reduce:
     let resultado_usa_reduce = usa_reduce(vec![1, 2, 3, 4], |acc, x| acc * x, 1);
     println!("usa_reduce: {}", resultado_usa_reduce); // Espera 24
+
+    // Prueba de la cadena perezosa: un solo recorrido, sin vectores intermedios.
+    let resultado_perezoso = perezoso::mi_mapa(&[1, 2, 3, 4, 5, 6], |x| x * 2)
+        .mi_filter(|x| x % 4 == 0)
+        .mi_reduce(|acc, x| acc + x, 0);
+    println!("perezoso: {}", resultado_perezoso); // Espera 24
 }
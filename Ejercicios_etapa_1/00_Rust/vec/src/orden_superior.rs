@@ -0,0 +1,70 @@
+// src/orden_superior.rs
+
+// Versiones propias de .map, .filter y .reduce de JavaScript. Son ansiosas: cada una
+// recorre el arreglo completo y retorna un vector (o valor) nuevo. Para encadenarlas
+// sin vectores intermedios ver `perezoso`.
+
+/// mi_mapa
+///
+/// Esta función recibe un arreglo y una función; aplica la función a cada elemento del arreglo y
+/// retorna un nuevo vector con los resultados.
+pub fn mi_mapa<T, U, F>(arreglo: &[T], mut fnc: F) -> Vec<U>
+where
+    F: FnMut(&T) -> U,
+{
+    let mut nuevo_vector = Vec::with_capacity(arreglo.len());
+    for item in arreglo {
+        nuevo_vector.push(fnc(item));
+    }
+    nuevo_vector
+}
+
+/// mi_filter
+///
+/// Esta función recibe un arreglo y una función predicado; retorna un nuevo vector que contiene
+/// solo aquellos elementos para los cuales la función predicado retorna true.
+pub fn mi_filter<T: Clone, F>(arreglo: &[T], mut predicado: F) -> Vec<T>
+where
+    F: FnMut(&T) -> bool,
+{
+    let mut filtrado = Vec::new();
+    for item in arreglo {
+        if predicado(item) {
+            filtrado.push(item.clone());
+        }
+    }
+    filtrado
+}
+
+/// mi_reduce
+///
+/// Esta función recibe un arreglo, una función reductora y un valor inicial. Aplica la función
+/// reductora acumulando un resultado a lo largo de los elementos y retorna el resultado final.
+pub fn mi_reduce<T, U, F>(arreglo: &[T], mut fnc: F, valor_inicial: U) -> U
+where
+    F: FnMut(U, &T) -> U,
+{
+    let mut acumulador = valor_inicial;
+    for item in arreglo {
+        acumulador = fnc(acumulador, item);
+    }
+    acumulador
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_versiones_propias() {
+        assert_eq!(mi_mapa(&[1, 2, 3, 4], |x| x * 2), vec![2, 4, 6, 8]);
+        assert_eq!(
+            mi_filter(&[1, 2, 3, 4, 5, 6], |x| x % 2 == 0),
+            vec![2, 4, 6]
+        );
+        assert_eq!(mi_reduce(&[1, 2, 3, 4], |acc, x| acc + x, 0), 10);
+        // También aceptan un &Vec.
+        let numeros: Vec<i32> = (1..=3).collect();
+        assert_eq!(mi_reduce(&numeros, |acc, x| acc * x, 1), 6);
+    }
+}
@@ -0,0 +1,189 @@
+// src/perezoso.rs
+
+// Versiones perezosas de mi_mapa, mi_filter y mi_reduce.
+//
+// `mi_mapa` y `mi_filter` no recorren nada al llamarse: retornan una estructura que
+// implementa `Iterator` y aplica la función a cada elemento solo cuando se le pide el
+// siguiente. Así una cadena como
+//
+//     mi_mapa(&numeros, |x| x * 2).mi_filter(|x| x % 3 == 0).mi_reduce(|acc, x| acc + x, 0)
+//
+// recorre `numeros` una sola vez y no crea vectores intermedios, igual que
+// `iter().map().filter().fold()` de la biblioteca estándar.
+
+/// Iterador que aplica una función a cada elemento (ver `mi_mapa`).
+#[derive(Debug, Clone)]
+#[must_use = "los iteradores son perezosos: no hacen nada hasta consumirse"]
+pub struct MiMapa<I, F> {
+    iter: I,
+    fnc: F,
+}
+
+/// Iterador que deja pasar solo los elementos que cumplen el predicado (ver `mi_filter`).
+#[derive(Debug, Clone)]
+#[must_use = "los iteradores son perezosos: no hacen nada hasta consumirse"]
+pub struct MiFilter<I, P> {
+    iter: I,
+    predicado: P,
+}
+
+impl<I, U, F> Iterator for MiMapa<I, F>
+where
+    I: Iterator,
+    F: FnMut(I::Item) -> U,
+{
+    type Item = U;
+
+    fn next(&mut self) -> Option<U> {
+        self.iter.next().map(&mut self.fnc)
+    }
+
+    // Cada elemento produce exactamente uno, así que el tamaño es el del iterador original.
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<I, P> Iterator for MiFilter<I, P>
+where
+    I: Iterator,
+    P: FnMut(&I::Item) -> bool,
+{
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<I::Item> {
+        self.iter.find(&mut self.predicado)
+    }
+
+    // Puede que no pase ninguno, pero nunca más que los del iterador original.
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.iter.size_hint().1)
+    }
+}
+
+/// Métodos encadenables para cualquier iterador: `iter.mi_mapa(f).mi_filter(p)`.
+pub trait IteradorPerezoso: Iterator + Sized {
+    fn mi_mapa<U, F>(self, fnc: F) -> MiMapa<Self, F>
+    where
+        F: FnMut(Self::Item) -> U,
+    {
+        MiMapa { iter: self, fnc }
+    }
+
+    fn mi_filter<P>(self, predicado: P) -> MiFilter<Self, P>
+    where
+        P: FnMut(&Self::Item) -> bool,
+    {
+        MiFilter {
+            iter: self,
+            predicado,
+        }
+    }
+
+    /// Consume el iterador acumulando el resultado; es aquí donde se recorre la cadena.
+    fn mi_reduce<U, F>(self, mut fnc: F, valor_inicial: U) -> U
+    where
+        F: FnMut(U, Self::Item) -> U,
+    {
+        let mut acumulador = valor_inicial;
+        for item in self {
+            acumulador = fnc(acumulador, item);
+        }
+        acumulador
+    }
+}
+
+impl<I: Iterator> IteradorPerezoso for I {}
+
+/// mi_mapa perezoso
+///
+/// Recibe cualquier cosa que se pueda recorrer (un `&Vec`, un slice, un rango, otro
+/// iterador...) y una función; retorna un iterador que aplica la función a cada elemento
+/// a medida que se consume.
+///
+/// # Parámetros
+/// - `arreglo`: Los elementos a transformar; con `&vec` la función recibe referencias.
+/// - `fnc`: La función que se aplica a cada elemento.
+///
+/// # Retorno
+/// Un `MiMapa`, que se puede encadenar con `mi_filter`, `mi_reduce` o cualquier método
+/// de `Iterator`.
+pub fn mi_mapa<I, U, F>(arreglo: I, fnc: F) -> MiMapa<I::IntoIter, F>
+where
+    I: IntoIterator,
+    F: FnMut(I::Item) -> U,
+{
+    arreglo.into_iter().mi_mapa(fnc)
+}
+
+/// mi_filter perezoso
+///
+/// Retorna un iterador con solo los elementos para los que el predicado retorna true; el
+/// predicado se evalúa a medida que se consume.
+pub fn mi_filter<I, P>(arreglo: I, predicado: P) -> MiFilter<I::IntoIter, P>
+where
+    I: IntoIterator,
+    P: FnMut(&I::Item) -> bool,
+{
+    arreglo.into_iter().mi_filter(predicado)
+}
+
+/// mi_reduce para cualquier `IntoIterator`; a diferencia de los otros dos consume los
+/// elementos de inmediato y retorna el valor acumulado.
+pub fn mi_reduce<I, U, F>(arreglo: I, fnc: F, valor_inicial: U) -> U
+where
+    I: IntoIterator,
+    F: FnMut(U, I::Item) -> U,
+{
+    arreglo.into_iter().mi_reduce(fnc, valor_inicial)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+
+    #[test]
+    fn test_cadena_igual_que_ansiosa() {
+        let numeros: Vec<i32> = (1..=20).collect();
+        let perezosa = mi_mapa(&numeros, |x| x * 2)
+            .mi_filter(|x| x % 3 == 0)
+            .mi_reduce(|acc, x| acc + x, 0);
+        let mapeados = crate::mi_mapa(&numeros, |x| x * 2);
+        let filtrados = crate::mi_filter(&mapeados, |x| x % 3 == 0);
+        assert_eq!(perezosa, crate::mi_reduce(&filtrados, |acc, x| acc + x, 0));
+
+        // Funciona con cualquier IntoIterator y con los métodos de Iterator.
+        let textos: Vec<String> = mi_filter(1..=6, |x| x % 2 == 0)
+            .mi_mapa(|x| x.to_string())
+            .collect();
+        assert_eq!(textos, vec!["2", "4", "6"]);
+        assert_eq!(
+            mi_reduce(["a", "b", "c"], |acc, x| acc + x, String::new()),
+            "abc"
+        );
+    }
+
+    #[test]
+    fn test_es_perezoso() {
+        let llamadas = Cell::new(0);
+        let mut pares = mi_mapa(1.., |x: u64| {
+            llamadas.set(llamadas.get() + 1);
+            x * x
+        })
+        .mi_filter(|x| x % 2 == 0);
+        assert_eq!(llamadas.get(), 0);
+        assert_eq!(pares.next(), Some(4));
+        assert_eq!(pares.next(), Some(16));
+        // Solo se calcularon los elementos necesarios de un rango infinito.
+        assert_eq!(llamadas.get(), 4);
+    }
+
+    #[test]
+    fn test_size_hint() {
+        let numeros = [1, 2, 3, 4];
+        assert_eq!(mi_mapa(&numeros, |x| x + 1).size_hint(), (4, Some(4)));
+        assert_eq!(mi_filter(&numeros, |x| **x > 2).size_hint(), (0, Some(4)));
+        assert_eq!(mi_filter(&numeros, |x| **x > 2).count(), 2);
+    }
+}